@@ -41,6 +41,8 @@ pub struct Database {
     vector_type: RefCell<Option<Entity>>,
     map_type: RefCell<Option<Entity>>,
     jsval_type: RefCell<Option<Entity>>,
+    generator_type: RefCell<Option<Entity>>,
+    iterator_type: RefCell<Option<Entity>>,
     proxy_type: RefCell<Option<Entity>>,
     byte_array_type: RefCell<Option<Entity>>,
     mxmlextrema_proxy_ns_uri: String,
@@ -124,6 +126,8 @@ impl Database {
             vector_type: RefCell::new(None),
             map_type: RefCell::new(None),
             jsval_type: RefCell::new(None),
+            generator_type: RefCell::new(None),
+            iterator_type: RefCell::new(None),
             proxy_type: RefCell::new(None),
             byte_array_type: RefCell::new(None),
             mxmlextrema_proxy_ns_uri: options.mxmlextrema_proxy_ns_uri,
@@ -305,6 +309,8 @@ impl Database {
     global_lookup!(vector_type, "Vector");
     global_lookup!(map_type, "Map");
     global_lookup!(jsval_type, "JSVal");
+    global_lookup!(generator_type, "Generator");
+    global_lookup!(iterator_type, "Iterator");
 
     pub fn array_type_of_any(&self) -> Result<Entity, DeferError> {
         let origin = self.array_type().defer()?;
@@ -316,6 +322,11 @@ impl Database {
         Ok(self.factory().create_type_after_substitution(&origin, &shared_array![self.any_type()]))
     }
 
+    pub fn generator_type_of_any(&self) -> Result<Entity, DeferError> {
+        let origin = self.generator_type().defer()?;
+        Ok(self.factory().create_type_after_substitution(&origin, &shared_array![self.any_type()]))
+    }
+
     pub fn vector_type_of_any(&self) -> Result<Entity, DeferError> {
         let origin = self.vector_type().defer()?;
         Ok(self.factory().create_type_after_substitution(&origin, &shared_array![self.any_type()]))
@@ -179,6 +179,14 @@ smodel! {
             panic!();
         }
 
+        pub fn default_xml_namespace(&self) -> Option<Entity> {
+            panic!();
+        }
+
+        pub fn set_default_xml_namespace(&self, ns: Option<Entity>) {
+            panic!();
+        }
+
         pub fn concat_open_ns_set_of_scope_chain(&self) -> SharedArray<Entity> {
             let mut open_ns_set = SharedArray::new();
             open_ns_set.extend(self.open_ns_set().iter());
@@ -384,6 +392,14 @@ smodel! {
             panic!();
         }
 
+        pub fn embedded_asset(&self) -> Option<Rc<EmbeddedAsset>> {
+            panic!();
+        }
+
+        pub fn set_embedded_asset(&self, asset: Option<Rc<EmbeddedAsset>>) {
+            panic!();
+        }
+
         pub fn scope(&self) -> Entity {
             panic!();
         }
@@ -539,6 +555,22 @@ smodel! {
             }
         }
 
+        /// If a type is `Generator.<T>` or `Iterator.<T>`, returns `T`, either as an origin
+        /// type parameter or as a substitute type. Either type may be missing from the built-ins.
+        pub fn iterator_element_type(&self, host: &Database) -> Option<Entity> {
+            for origin in [host.generator_type(), host.iterator_type()] {
+                if origin.is::<UnresolvedEntity>() {
+                    continue;
+                }
+                if self == &origin {
+                    return Some(origin.type_params().unwrap().get(0).unwrap());
+                } else if self.type_after_sub_has_origin(&origin) {
+                    return Some(self.substitute_types().get(0).unwrap());
+                }
+            }
+            None
+        }
+
         pub fn type_after_sub_has_origin(&self, origin: &Entity) -> bool {
             self.is::<TypeAfterSubstitution>() && &self.origin() == origin
         }
@@ -2517,6 +2549,7 @@ smodel! {
         let ref m_parent: Option<Entity> = None;
         let m_flags: VariableSlotFlags = VariableSlotFlags::empty();
         let ref m_bindable_event: Option<String> = None;
+        let ref m_embedded_asset: Option<Rc<EmbeddedAsset>> = None;
 
         pub(crate) fn OriginalVariableSlot(name: &QName, read_only: bool, static_type: &Entity) {
             super();
@@ -2578,6 +2611,15 @@ smodel! {
             self.set_m_bindable_event(name);
         }
 
+        /// The asset indicated by a `[Embed]` meta-data tag.
+        pub override fn embedded_asset(&self) -> Option<Rc<EmbeddedAsset>> {
+            self.m_embedded_asset()
+        }
+
+        pub override fn set_embedded_asset(&self, asset: Option<Rc<EmbeddedAsset>>) {
+            self.set_m_embedded_asset(asset);
+        }
+
         pub override fn parent(&self) -> Option<Entity> {
             self.m_parent()
         }
@@ -2678,6 +2720,11 @@ smodel! {
             self.origin().bindable_event()
         }
 
+        /// The asset indicated by a `[Embed]` meta-data tag.
+        pub override fn embedded_asset(&self) -> Option<Rc<EmbeddedAsset>> {
+            self.origin().embedded_asset()
+        }
+
         pub override fn parent(&self) -> Option<Entity> {
             self.origin().parent()
         }
@@ -3341,6 +3388,7 @@ smodel! {
         let ref m_cfg: ControlFlowGraph = ControlFlowGraph::new();
         let ref m_public_ns: Option<Entity> = None;
         let ref m_internal_ns: Option<Entity> = None;
+        let ref m_default_xml_namespace: Option<Entity> = None;
 
         pub(crate) fn Activation(of_method: &Entity) {
             super();
@@ -3424,6 +3472,16 @@ smodel! {
         pub override fn set_internal_ns(&self, ns: Option<Entity>) {
             self.set_m_internal_ns(ns);
         }
+
+        /// The value assigned by the last verified `default xml namespace`
+        /// statement of the activation, either a `Namespace` or a `String`.
+        pub override fn default_xml_namespace(&self) -> Option<Entity> {
+            self.m_default_xml_namespace()
+        }
+
+        pub override fn set_default_xml_namespace(&self, ns: Option<Entity>) {
+            self.set_m_default_xml_namespace(ns);
+        }
     }
 
     pub struct FixtureScope: Scope {
@@ -4558,6 +4616,14 @@ pub struct Event {
     pub bubbles: Option<bool>,
 }
 
+/// An asset embedded through `[Embed(source="path")]` meta-data.
+#[derive(Clone)]
+pub struct EmbeddedAsset {
+    /// Canonical path of the asset file.
+    pub path: String,
+    pub mime_type: String,
+}
+
 const DEFAULT_ACTIVATION: u8 = 0;
 const PACKAGE_INIT_ACTIVATION: u8 = 1;
 const GLOBAL_INIT_ACTIVATION: u8 = 2;
\ No newline at end of file
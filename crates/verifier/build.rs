@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Checks that every shipped locale under `locales/` contains a message
+/// for every `WhackDiagnosticKind`, and embeds the shipped locales.
+fn main() {
+    println!("cargo:rerun-if-changed=locales");
+    println!("cargo:rerun-if-changed=src/diagnostics/whack_diagnostic_kind.rs");
+
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    // Read `Name = ID,` lines from the WhackDiagnosticKind enumeration.
+    let kinds_source = std::fs::read_to_string(manifest_dir.join("src/diagnostics/whack_diagnostic_kind.rs")).unwrap();
+    let mut kinds: Vec<(String, i32)> = vec![];
+    for line in kinds_source.lines() {
+        let Some((name, id)) = line.trim().strip_suffix(',').and_then(|l| l.split_once(" = ")) else {
+            continue;
+        };
+        if let Ok(id) = id.parse::<i32>() {
+            kinds.push((name.to_owned(), id));
+        }
+    }
+
+    let mut locales: Vec<(String, PathBuf)> = vec![];
+    for entry in std::fs::read_dir(manifest_dir.join("locales")).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map(|e| e == "toml").unwrap_or(false) {
+            locales.push((path.file_stem().unwrap().to_str().unwrap().to_owned(), path));
+        }
+    }
+    locales.sort();
+
+    for (locale, path) in locales.iter() {
+        let messages = match toml::from_str::<HashMap<String, String>>(&std::fs::read_to_string(path).unwrap()) {
+            Ok(messages) => messages,
+            Err(error) => panic!("Locale {locale} contains invalid TOML: {}", error.message()),
+        };
+        let missing = kinds.iter()
+            .filter(|(_, id)| !messages.contains_key(&id.to_string()))
+            .map(|(name, id)| format!("{name} ({id})"))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            panic!("Locale {locale} is missing messages for: {}", missing.join(", "));
+        }
+    }
+
+    let shipped = locales.iter()
+        .map(|(locale, path)| format!("    ({locale:?}, include_str!({:?})),\n", path.to_str().unwrap()))
+        .collect::<String>();
+    let out_path = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("shipped_locales.rs");
+    std::fs::write(out_path, format!("&[\n{shipped}]")).unwrap();
+}
@@ -19,6 +19,27 @@ impl CompilerOptions {
 #[non_exhaustive]
 pub struct CompilerWarningOptions {
     pub unused: bool,
+    /// Level of every warning that does not appear in `levels`.
+    pub default_level: WarningLevel,
+    /// Levels of individual warnings by diagnostic ID.
+    pub levels: HashMap<i32, WarningLevel>,
+}
+
+impl CompilerWarningOptions {
+    pub fn level_of(&self, kind: WhackDiagnosticKind) -> WarningLevel {
+        self.levels.get(&kind.id()).copied().unwrap_or(self.default_level)
+    }
+}
+
+/// Indicates how a warning is reported.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WarningLevel {
+    /// The warning is not reported.
+    Allow,
+    /// The warning is reported as a warning.
+    Warn,
+    /// The warning is reported as an error.
+    Deny,
 }
 
 impl Default for CompilerOptions {
@@ -34,6 +55,8 @@ impl Default for CompilerWarningOptions {
     fn default() -> Self {
         Self {
             unused: true,
+            default_level: WarningLevel::Warn,
+            levels: HashMap::new(),
         }
     }
 }
\ No newline at end of file
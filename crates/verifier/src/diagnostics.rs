@@ -2,4 +2,10 @@ mod whack_diagnostic;
 pub use whack_diagnostic::*;
 
 mod whack_diagnostic_kind;
-pub use whack_diagnostic_kind::*;
\ No newline at end of file
+pub use whack_diagnostic_kind::*;
+
+mod diagnostic_messages;
+pub use diagnostic_messages::*;
+
+mod diagnostic_fix;
+pub use diagnostic_fix::*;
\ No newline at end of file
@@ -0,0 +1,105 @@
+use crate::ns::*;
+
+/// A suggested change to source text that resolves a diagnostic.
+#[derive(Clone)]
+pub struct DiagnosticFix {
+    /// Short description of the change, such as `Remove unused import`.
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+    pub applicability: DiagnosticFixApplicability,
+}
+
+impl DiagnosticFix {
+    /// Creates a fix that should be reviewed before being applied.
+    pub fn new(title: &str, edits: Vec<TextEdit>) -> Self {
+        Self {
+            title: title.to_owned(),
+            edits,
+            applicability: DiagnosticFixApplicability::MaybeIncorrect,
+        }
+    }
+
+    /// Creates a fix that may be applied without review.
+    pub fn new_machine_applicable(title: &str, edits: Vec<TextEdit>) -> Self {
+        Self {
+            title: title.to_owned(),
+            edits,
+            applicability: DiagnosticFixApplicability::MachineApplicable,
+        }
+    }
+
+    pub fn is_machine_applicable(&self) -> bool {
+        self.applicability == DiagnosticFixApplicability::MachineApplicable
+    }
+}
+
+/// Whether a fix is known to preserve the intent of the code.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticFixApplicability {
+    /// The fix is what the code means, such as removing an unused import,
+    /// and may be applied automatically by `whack fix`.
+    MachineApplicable,
+    /// The fix is a suggestion, such as a similarly named property.
+    MaybeIncorrect,
+}
+
+impl DiagnosticFixApplicability {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MachineApplicable => "machine-applicable",
+            Self::MaybeIncorrect => "maybe-incorrect",
+        }
+    }
+}
+
+/// Replacement of the source text at a location.
+#[derive(Clone)]
+pub struct TextEdit {
+    pub location: Location,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn replace(location: &Location, replacement: &str) -> Self {
+        Self {
+            location: location.clone(),
+            replacement: replacement.to_owned(),
+        }
+    }
+
+    pub fn insert(compilation_unit: &Rc<CompilationUnit>, offset: usize, text: &str) -> Self {
+        Self::replace(&Location::with_offset(compilation_unit, offset), text)
+    }
+
+    pub fn delete(location: &Location) -> Self {
+        Self::replace(location, "")
+    }
+
+    /// Deletes a directive along with a following semicolon. If the directive
+    /// occupies its own line, the whole line is deleted.
+    pub fn delete_directive(location: &Location) -> Self {
+        let cu = location.compilation_unit();
+        let text = cu.text();
+        let mut first = location.first_offset();
+        let mut last = location.last_offset();
+
+        // Following semicolon
+        let rest = &text[last..];
+        let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        if rest[spaces..].starts_with(';') {
+            last += spaces + 1;
+        }
+
+        // Whole line
+        let line_start = text[..first].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let rest = &text[last..];
+        let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        let rest = &rest[spaces..];
+        if text[line_start..first].trim().is_empty() && (rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n")) {
+            first = line_start;
+            last += spaces + if rest.starts_with("\r\n") { 2 } else if rest.starts_with('\n') { 1 } else { 0 };
+        }
+
+        Self::delete(&Location::with_offsets(&cu, first, last))
+    }
+}
@@ -0,0 +1,86 @@
+use crate::ns::*;
+
+/// Shipped locales as (locale, TOML source) pairs, read from
+/// the `locales` directory of this crate.
+static SHIPPED_LOCALES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/shipped_locales.rs"));
+
+/// Diagnostic message templates of a locale.
+///
+/// Messages are read from TOML tables whose keys are diagnostic IDs:
+///
+/// ```toml
+/// 2158 = "{1} is unused."
+/// ```
+///
+/// Messages missing from the table fall back to English.
+pub struct DiagnosticMessages {
+    locale: String,
+    messages: HashMap<i32, String>,
+}
+
+impl DiagnosticMessages {
+    pub fn english() -> Self {
+        Self {
+            locale: "en".to_owned(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Returns the names of the locales shipped with the verifier,
+    /// other than English.
+    pub fn shipped_locales() -> Vec<&'static str> {
+        SHIPPED_LOCALES.iter().map(|(locale, _)| *locale).collect()
+    }
+
+    /// Returns the messages of a shipped locale. The locale may be given as
+    /// a POSIX locale such as `pt_BR.UTF-8`, and matches a shipped locale
+    /// of the same language if there is no exact match.
+    ///
+    /// English locales return English messages.
+    pub fn shipped(locale: &str) -> Option<Self> {
+        let locale = locale.split(['.', '@']).next().unwrap().replace('_', "-");
+        let language = locale.split('-').next().unwrap();
+        if language.eq_ignore_ascii_case("en") || locale == "C" || locale == "POSIX" {
+            return Some(Self::english());
+        }
+        let (name, source) = SHIPPED_LOCALES.iter().find(|(name, _)| name.eq_ignore_ascii_case(&locale))
+            .or_else(|| SHIPPED_LOCALES.iter().find(|(name, _)| name.split('-').next().unwrap().eq_ignore_ascii_case(language)))?;
+        Some(Self::from_toml(name, source).unwrap())
+    }
+
+    /// Reads messages from a TOML source.
+    pub fn from_toml(locale: &str, source: &str) -> Result<Self, String> {
+        let table = toml::from_str::<HashMap<String, String>>(source).map_err(|error| error.message().to_owned())?;
+        let mut messages = HashMap::new();
+        for (id, message) in table {
+            let Ok(id) = id.parse::<i32>() else {
+                return Err(format!("Key is not a diagnostic ID: {id}"));
+            };
+            messages.insert(id, message);
+        }
+        Ok(Self {
+            locale: locale.to_owned(),
+            messages,
+        })
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Formats the message of a diagnostic.
+    pub fn format_message(&self, diagnostic: &Diagnostic) -> String {
+        let diagnostic = WhackDiagnostic(diagnostic);
+        if self.messages.contains_key(&diagnostic.id()) {
+            diagnostic.format_message(&self.messages)
+        } else {
+            diagnostic.format_message_english()
+        }
+    }
+
+    /// Formats a diagnostic, relativizing its source path to `base_path`.
+    pub fn format_with_base_path(&self, diagnostic: &Diagnostic, base_path: &str) -> String {
+        let id = WhackDiagnostic(diagnostic).id();
+        diagnostic.format_with_message_and_base_path(&self.format_message(diagnostic), Some(id), Some(base_path))
+    }
+}
@@ -5,34 +5,49 @@ mod data;
 
 pub struct WhackDiagnostic<'a>(pub &'a Diagnostic);
 
+/// Data attached to a `Diagnostic` as its custom kind.
+struct WhackDiagnosticData {
+    kind: WhackDiagnosticKind,
+    fixes: Vec<DiagnosticFix>,
+}
+
 impl<'a> WhackDiagnostic<'a> {
     pub fn new_syntax_error(location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) -> Diagnostic {
         let d = Diagnostic::new_syntax_error(location, DiagnosticKind::Expecting, arguments);
-        d.set_custom_kind(Some(Rc::new(kind)));
+        d.set_custom_kind(Some(Rc::new(WhackDiagnosticData { kind, fixes: vec![] })));
         d
     }
 
     pub fn new_verify_error(location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) -> Diagnostic {
         let d = Diagnostic::new_verify_error(location, DiagnosticKind::Expecting, arguments);
-        d.set_custom_kind(Some(Rc::new(kind)));
+        d.set_custom_kind(Some(Rc::new(WhackDiagnosticData { kind, fixes: vec![] })));
         d
     }
 
     pub fn new_warning(location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) -> Diagnostic {
         let d = Diagnostic::new_warning(location, DiagnosticKind::Expecting, arguments);
-        d.set_custom_kind(Some(Rc::new(kind)));
+        d.set_custom_kind(Some(Rc::new(WhackDiagnosticData { kind, fixes: vec![] })));
         d
     }
 
+    fn data(&self) -> Option<Rc<WhackDiagnosticData>> {
+        Rc::downcast(self.custom_kind()?).ok()
+    }
+
     pub fn fx_kind(&self) -> Option<WhackDiagnosticKind> {
-        if let Some(k) = self.custom_kind() {
-            if let Ok(k) = Rc::downcast(k) {
-                Some(*k)
-            } else {
-                None
-            }
-        } else {
-            None
+        self.data().map(|data| data.kind)
+    }
+
+    /// Returns the suggested fixes of the diagnostic.
+    pub fn fixes(&self) -> Vec<DiagnosticFix> {
+        self.data().map(|data| data.fixes.clone()).unwrap_or_default()
+    }
+
+    /// Sets the suggested fixes of a diagnostic created
+    /// through `WhackDiagnostic`.
+    pub fn set_fixes(&self, fixes: Vec<DiagnosticFix>) {
+        if let Some(kind) = self.fx_kind() {
+            self.set_custom_kind(Some(Rc::new(WhackDiagnosticData { kind, fixes })));
         }
     }
 
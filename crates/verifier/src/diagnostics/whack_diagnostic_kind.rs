@@ -39,7 +39,6 @@ pub enum WhackDiagnosticKind {
     AwaitOperandMustBeAPromise = 2083,
     OperandMustBeNumber = 2084,
     ReferenceIsAlreadyNonNullable = 2085,
-    YieldIsNotSupported = 2086,
     UnrelatedMathOperation = 2087,
     ComparisonBetweenUnrelatedTypes = 2088,
     UnrelatedTernaryOperands = 2089,
@@ -54,7 +53,6 @@ pub enum WhackDiagnosticKind {
     EntityIsNotAConstant = 2098,
     ReturnValueHasNoTypeDeclaration = 2099,
     ReturnTypeDeclarationMustBePromise = 2100,
-    ReturnTypeInferenceIsNotImplemented = 2101,
     NanComparison = 2102,
     NotABooleanConstant = 2103,
     EmptyPackage = 2104,
@@ -106,7 +104,6 @@ pub enum WhackDiagnosticKind {
     ExpectedToIterateType = 2151,
     IllegalReturnStatement = 2152,
     ReturnValueMustBeSpecified = 2153,
-    DxnsStatementIsNotSupported = 2154,
     ReturnNotAllowedInPackageInit = 2155,
     ReturnNotAllowedInGlobalInit = 2156,
     NamespaceConflictsWithConfigurationNs = 2157,
@@ -114,10 +111,364 @@ pub enum WhackDiagnosticKind {
     RecordClassMustHaveEmptyConstructor = 2159,
     IllegalMapKey = 2160,
     UnusedImport = 2161,
+    NotAllCodePathsReturnAValue = 2162,
+    UnreachableCode = 2163,
+    MxmlSourceOutsideSourcePath = 2164,
+    UnknownMxmlNamespace = 2165,
+    UnknownMxmlAttribute = 2166,
+    MxmlValueNotConvertible = 2167,
+    UnsupportedMxmlFeature = 2168,
+    MxmlChildrenNotAccepted = 2169,
+    MxmlSourceNameIsNotAnIdentifier = 2170,
+    VariableNotDefinitelyAssigned = 2171,
+    ConstantCondition = 2172,
+    DuplicateLabel = 2173,
+    VoidCondition = 2174,
+    NonBooleanConstantCondition = 2175,
+    UnawaitedPromiseCondition = 2176,
+    UnrelatedSwitchTypeCase = 2177,
+    MalformedBindableMetadata = 2178,
+    BindableNotAllowedHere = 2179,
+    BindableRequiresEventDispatcher = 2180,
+    UnbindableDataBindingSource = 2181,
+    MalformedEmbedMetadata = 2182,
+    EmbedNotAllowedHere = 2183,
+    EmbeddedAssetNotFound = 2184,
+    UnsupportedEmbedMimeType = 2185,
+    EmbedMustBeClassOrByteArray = 2186,
+    UnknownEventName = 2187,
+    IncompatibleEventListener = 2188,
+    ReturnTypeDeclarationMustBeGenerator = 2189,
+    DefaultXmlNamespaceMustBeNamespaceOrString = 2190,
+    EmbedConstantMustNotContainInitializer = 2191,
+    EmbedMustApplyToSingleBinding = 2192,
 }
 
 impl WhackDiagnosticKind {
+    pub const VALUES: [WhackDiagnosticKind; 141] = [
+        Self::EntityIsNotAType,
+        Self::ImplicitCoercionToUnrelatedType,
+        Self::EntityIsReadOnly,
+        Self::EntityIsWriteOnly,
+        Self::EntityMustNotBeDeleted,
+        Self::UndefinedProperty,
+        Self::AmbiguousReference,
+        Self::AccessOfVoid,
+        Self::AccessOfNullable,
+        Self::CouldNotExpandInlineConstant,
+        Self::ReachedMaximumCycles,
+        Self::NullNotExpectedHere,
+        Self::CouldNotParseNumber,
+        Self::NoMatchingEnumMember,
+        Self::UnexpectedThis,
+        Self::ArrayLengthNotEqualsTupleLength,
+        Self::UnexpectedElision,
+        Self::UnexpectedArray,
+        Self::UnexpectedRest,
+        Self::UnexpectedObject,
+        Self::DynamicOptionNotSupported,
+        Self::UnknownOptionForClass,
+        Self::MustSpecifyOption,
+        Self::UnexpectedFieldName,
+        Self::UnexpectedNewBase,
+        Self::IncorrectNumArguments,
+        Self::IncorrectNumArgumentsNoMoreThan,
+        Self::UndefinedPropertyWithStaticType,
+        Self::InapplicableFilter,
+        Self::InapplicableDescendants,
+        Self::ASuperExpCanBeUsedOnlyIn,
+        Self::ASuperExpCanOnlyBeUsedInSubclasses,
+        Self::CallOnArrayType,
+        Self::CallOnNonFunction,
+        Self::NonParameterizedType,
+        Self::AwaitOperandMustBeAPromise,
+        Self::OperandMustBeNumber,
+        Self::ReferenceIsAlreadyNonNullable,
+        Self::UnrelatedMathOperation,
+        Self::ComparisonBetweenUnrelatedTypes,
+        Self::UnrelatedTernaryOperands,
+        Self::SystemNamespaceNotFound,
+        Self::RestParameterMustBeArray,
+        Self::AConflictExistsWithDefinition,
+        Self::DuplicateVariableDefinition,
+        Self::DuplicateClassDefinition,
+        Self::DuplicateInterfaceDefinition,
+        Self::DuplicateFunctionDefinition,
+        Self::UnexpectedFieldNameInDestructuring,
+        Self::EntityIsNotAConstant,
+        Self::ReturnValueHasNoTypeDeclaration,
+        Self::ReturnTypeDeclarationMustBePromise,
+        Self::NanComparison,
+        Self::NotABooleanConstant,
+        Self::EmptyPackage,
+        Self::ImportOfUndefined,
+        Self::NotANamespaceConstant,
+        Self::CannotResolveConfigConstant,
+        Self::ConcatenatingSelfReferentialPackage,
+        Self::CallOnDateType,
+        Self::AccessControlNamespaceNotAllowedHere,
+        Self::CannotUseDestructuringHere,
+        Self::ShadowingDefinitionInBaseClass,
+        Self::VariableHasNoTypeAnnotation,
+        Self::ConstantMustContainInitializer,
+        Self::ExternalFunctionMustBeNativeOrAbstract,
+        Self::IncompatibleOverride,
+        Self::MustOverrideAMethod,
+        Self::OverridingFinalMethod,
+        Self::RedefiningConstructor,
+        Self::ConstructorMustContainSuperStatement,
+        Self::GetterMustTakeNoParameters,
+        Self::SetterMustTakeOneParameter,
+        Self::GetterMustReturnDataType,
+        Self::SetterMustTakeDataType,
+        Self::SetterMustReturnVoid,
+        Self::ExternalClassMustSetSlots,
+        Self::NotAClass,
+        Self::CannotExtendFinalClass,
+        Self::ExtendingSelfReferentialClass,
+        Self::NotAnInterface,
+        Self::MalformedEventMetadata,
+        Self::AbstractMethodMustBeOverriden,
+        Self::AbstractGetterMustBeOverriden,
+        Self::AbstractSetterMustBeOverriden,
+        Self::ClassMustDefineAConstructor,
+        Self::MethodNotImplemented,
+        Self::GetterNotImplemented,
+        Self::SetterNotImplemented,
+        Self::IncompatibleMethodSignature,
+        Self::IncompatibleGetterSignature,
+        Self::IncompatibleSetterSignature,
+        Self::PropertyMustBeMethod,
+        Self::PropertyMustBeVirtual,
+        Self::IllegalEnumConstInit,
+        Self::DuplicateEnumString,
+        Self::DuplicateEnumValue,
+        Self::DuplicateEnumConstant,
+        Self::ExtendingSelfReferentialInterface,
+        Self::CannotIterateType,
+        Self::ExpectedToIterateType,
+        Self::IllegalReturnStatement,
+        Self::ReturnValueMustBeSpecified,
+        Self::ReturnNotAllowedInPackageInit,
+        Self::ReturnNotAllowedInGlobalInit,
+        Self::NamespaceConflictsWithConfigurationNs,
+        Self::Unused,
+        Self::RecordClassMustHaveEmptyConstructor,
+        Self::IllegalMapKey,
+        Self::UnusedImport,
+        Self::NotAllCodePathsReturnAValue,
+        Self::UnreachableCode,
+        Self::MxmlSourceOutsideSourcePath,
+        Self::UnknownMxmlNamespace,
+        Self::UnknownMxmlAttribute,
+        Self::MxmlValueNotConvertible,
+        Self::UnsupportedMxmlFeature,
+        Self::MxmlChildrenNotAccepted,
+        Self::MxmlSourceNameIsNotAnIdentifier,
+        Self::VariableNotDefinitelyAssigned,
+        Self::ConstantCondition,
+        Self::DuplicateLabel,
+        Self::VoidCondition,
+        Self::NonBooleanConstantCondition,
+        Self::UnawaitedPromiseCondition,
+        Self::UnrelatedSwitchTypeCase,
+        Self::MalformedBindableMetadata,
+        Self::BindableNotAllowedHere,
+        Self::BindableRequiresEventDispatcher,
+        Self::UnbindableDataBindingSource,
+        Self::MalformedEmbedMetadata,
+        Self::EmbedNotAllowedHere,
+        Self::EmbeddedAssetNotFound,
+        Self::UnsupportedEmbedMimeType,
+        Self::EmbedMustBeClassOrByteArray,
+        Self::UnknownEventName,
+        Self::IncompatibleEventListener,
+        Self::ReturnTypeDeclarationMustBeGenerator,
+        Self::DefaultXmlNamespaceMustBeNamespaceOrString,
+        Self::EmbedConstantMustNotContainInitializer,
+        Self::EmbedMustApplyToSingleBinding,
+    ];
+
     pub fn id(&self) -> i32 {
         *self as i32
     }
+
+    /// Returns the name of the diagnostic kind, such as `"Unused"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EntityIsNotAType => "EntityIsNotAType",
+            Self::ImplicitCoercionToUnrelatedType => "ImplicitCoercionToUnrelatedType",
+            Self::EntityIsReadOnly => "EntityIsReadOnly",
+            Self::EntityIsWriteOnly => "EntityIsWriteOnly",
+            Self::EntityMustNotBeDeleted => "EntityMustNotBeDeleted",
+            Self::UndefinedProperty => "UndefinedProperty",
+            Self::AmbiguousReference => "AmbiguousReference",
+            Self::AccessOfVoid => "AccessOfVoid",
+            Self::AccessOfNullable => "AccessOfNullable",
+            Self::CouldNotExpandInlineConstant => "CouldNotExpandInlineConstant",
+            Self::ReachedMaximumCycles => "ReachedMaximumCycles",
+            Self::NullNotExpectedHere => "NullNotExpectedHere",
+            Self::CouldNotParseNumber => "CouldNotParseNumber",
+            Self::NoMatchingEnumMember => "NoMatchingEnumMember",
+            Self::UnexpectedThis => "UnexpectedThis",
+            Self::ArrayLengthNotEqualsTupleLength => "ArrayLengthNotEqualsTupleLength",
+            Self::UnexpectedElision => "UnexpectedElision",
+            Self::UnexpectedArray => "UnexpectedArray",
+            Self::UnexpectedRest => "UnexpectedRest",
+            Self::UnexpectedObject => "UnexpectedObject",
+            Self::DynamicOptionNotSupported => "DynamicOptionNotSupported",
+            Self::UnknownOptionForClass => "UnknownOptionForClass",
+            Self::MustSpecifyOption => "MustSpecifyOption",
+            Self::UnexpectedFieldName => "UnexpectedFieldName",
+            Self::UnexpectedNewBase => "UnexpectedNewBase",
+            Self::IncorrectNumArguments => "IncorrectNumArguments",
+            Self::IncorrectNumArgumentsNoMoreThan => "IncorrectNumArgumentsNoMoreThan",
+            Self::UndefinedPropertyWithStaticType => "UndefinedPropertyWithStaticType",
+            Self::InapplicableFilter => "InapplicableFilter",
+            Self::InapplicableDescendants => "InapplicableDescendants",
+            Self::ASuperExpCanBeUsedOnlyIn => "ASuperExpCanBeUsedOnlyIn",
+            Self::ASuperExpCanOnlyBeUsedInSubclasses => "ASuperExpCanOnlyBeUsedInSubclasses",
+            Self::CallOnArrayType => "CallOnArrayType",
+            Self::CallOnNonFunction => "CallOnNonFunction",
+            Self::NonParameterizedType => "NonParameterizedType",
+            Self::AwaitOperandMustBeAPromise => "AwaitOperandMustBeAPromise",
+            Self::OperandMustBeNumber => "OperandMustBeNumber",
+            Self::ReferenceIsAlreadyNonNullable => "ReferenceIsAlreadyNonNullable",
+            Self::UnrelatedMathOperation => "UnrelatedMathOperation",
+            Self::ComparisonBetweenUnrelatedTypes => "ComparisonBetweenUnrelatedTypes",
+            Self::UnrelatedTernaryOperands => "UnrelatedTernaryOperands",
+            Self::SystemNamespaceNotFound => "SystemNamespaceNotFound",
+            Self::RestParameterMustBeArray => "RestParameterMustBeArray",
+            Self::AConflictExistsWithDefinition => "AConflictExistsWithDefinition",
+            Self::DuplicateVariableDefinition => "DuplicateVariableDefinition",
+            Self::DuplicateClassDefinition => "DuplicateClassDefinition",
+            Self::DuplicateInterfaceDefinition => "DuplicateInterfaceDefinition",
+            Self::DuplicateFunctionDefinition => "DuplicateFunctionDefinition",
+            Self::UnexpectedFieldNameInDestructuring => "UnexpectedFieldNameInDestructuring",
+            Self::EntityIsNotAConstant => "EntityIsNotAConstant",
+            Self::ReturnValueHasNoTypeDeclaration => "ReturnValueHasNoTypeDeclaration",
+            Self::ReturnTypeDeclarationMustBePromise => "ReturnTypeDeclarationMustBePromise",
+            Self::NanComparison => "NanComparison",
+            Self::NotABooleanConstant => "NotABooleanConstant",
+            Self::EmptyPackage => "EmptyPackage",
+            Self::ImportOfUndefined => "ImportOfUndefined",
+            Self::NotANamespaceConstant => "NotANamespaceConstant",
+            Self::CannotResolveConfigConstant => "CannotResolveConfigConstant",
+            Self::ConcatenatingSelfReferentialPackage => "ConcatenatingSelfReferentialPackage",
+            Self::CallOnDateType => "CallOnDateType",
+            Self::AccessControlNamespaceNotAllowedHere => "AccessControlNamespaceNotAllowedHere",
+            Self::CannotUseDestructuringHere => "CannotUseDestructuringHere",
+            Self::ShadowingDefinitionInBaseClass => "ShadowingDefinitionInBaseClass",
+            Self::VariableHasNoTypeAnnotation => "VariableHasNoTypeAnnotation",
+            Self::ConstantMustContainInitializer => "ConstantMustContainInitializer",
+            Self::ExternalFunctionMustBeNativeOrAbstract => "ExternalFunctionMustBeNativeOrAbstract",
+            Self::IncompatibleOverride => "IncompatibleOverride",
+            Self::MustOverrideAMethod => "MustOverrideAMethod",
+            Self::OverridingFinalMethod => "OverridingFinalMethod",
+            Self::RedefiningConstructor => "RedefiningConstructor",
+            Self::ConstructorMustContainSuperStatement => "ConstructorMustContainSuperStatement",
+            Self::GetterMustTakeNoParameters => "GetterMustTakeNoParameters",
+            Self::SetterMustTakeOneParameter => "SetterMustTakeOneParameter",
+            Self::GetterMustReturnDataType => "GetterMustReturnDataType",
+            Self::SetterMustTakeDataType => "SetterMustTakeDataType",
+            Self::SetterMustReturnVoid => "SetterMustReturnVoid",
+            Self::ExternalClassMustSetSlots => "ExternalClassMustSetSlots",
+            Self::NotAClass => "NotAClass",
+            Self::CannotExtendFinalClass => "CannotExtendFinalClass",
+            Self::ExtendingSelfReferentialClass => "ExtendingSelfReferentialClass",
+            Self::NotAnInterface => "NotAnInterface",
+            Self::MalformedEventMetadata => "MalformedEventMetadata",
+            Self::AbstractMethodMustBeOverriden => "AbstractMethodMustBeOverriden",
+            Self::AbstractGetterMustBeOverriden => "AbstractGetterMustBeOverriden",
+            Self::AbstractSetterMustBeOverriden => "AbstractSetterMustBeOverriden",
+            Self::ClassMustDefineAConstructor => "ClassMustDefineAConstructor",
+            Self::MethodNotImplemented => "MethodNotImplemented",
+            Self::GetterNotImplemented => "GetterNotImplemented",
+            Self::SetterNotImplemented => "SetterNotImplemented",
+            Self::IncompatibleMethodSignature => "IncompatibleMethodSignature",
+            Self::IncompatibleGetterSignature => "IncompatibleGetterSignature",
+            Self::IncompatibleSetterSignature => "IncompatibleSetterSignature",
+            Self::PropertyMustBeMethod => "PropertyMustBeMethod",
+            Self::PropertyMustBeVirtual => "PropertyMustBeVirtual",
+            Self::IllegalEnumConstInit => "IllegalEnumConstInit",
+            Self::DuplicateEnumString => "DuplicateEnumString",
+            Self::DuplicateEnumValue => "DuplicateEnumValue",
+            Self::DuplicateEnumConstant => "DuplicateEnumConstant",
+            Self::ExtendingSelfReferentialInterface => "ExtendingSelfReferentialInterface",
+            Self::CannotIterateType => "CannotIterateType",
+            Self::ExpectedToIterateType => "ExpectedToIterateType",
+            Self::IllegalReturnStatement => "IllegalReturnStatement",
+            Self::ReturnValueMustBeSpecified => "ReturnValueMustBeSpecified",
+            Self::ReturnNotAllowedInPackageInit => "ReturnNotAllowedInPackageInit",
+            Self::ReturnNotAllowedInGlobalInit => "ReturnNotAllowedInGlobalInit",
+            Self::NamespaceConflictsWithConfigurationNs => "NamespaceConflictsWithConfigurationNs",
+            Self::Unused => "Unused",
+            Self::RecordClassMustHaveEmptyConstructor => "RecordClassMustHaveEmptyConstructor",
+            Self::IllegalMapKey => "IllegalMapKey",
+            Self::UnusedImport => "UnusedImport",
+            Self::NotAllCodePathsReturnAValue => "NotAllCodePathsReturnAValue",
+            Self::UnreachableCode => "UnreachableCode",
+            Self::MxmlSourceOutsideSourcePath => "MxmlSourceOutsideSourcePath",
+            Self::UnknownMxmlNamespace => "UnknownMxmlNamespace",
+            Self::UnknownMxmlAttribute => "UnknownMxmlAttribute",
+            Self::MxmlValueNotConvertible => "MxmlValueNotConvertible",
+            Self::UnsupportedMxmlFeature => "UnsupportedMxmlFeature",
+            Self::MxmlChildrenNotAccepted => "MxmlChildrenNotAccepted",
+            Self::MxmlSourceNameIsNotAnIdentifier => "MxmlSourceNameIsNotAnIdentifier",
+            Self::VariableNotDefinitelyAssigned => "VariableNotDefinitelyAssigned",
+            Self::ConstantCondition => "ConstantCondition",
+            Self::DuplicateLabel => "DuplicateLabel",
+            Self::VoidCondition => "VoidCondition",
+            Self::NonBooleanConstantCondition => "NonBooleanConstantCondition",
+            Self::UnawaitedPromiseCondition => "UnawaitedPromiseCondition",
+            Self::UnrelatedSwitchTypeCase => "UnrelatedSwitchTypeCase",
+            Self::MalformedBindableMetadata => "MalformedBindableMetadata",
+            Self::BindableNotAllowedHere => "BindableNotAllowedHere",
+            Self::BindableRequiresEventDispatcher => "BindableRequiresEventDispatcher",
+            Self::UnbindableDataBindingSource => "UnbindableDataBindingSource",
+            Self::MalformedEmbedMetadata => "MalformedEmbedMetadata",
+            Self::EmbedNotAllowedHere => "EmbedNotAllowedHere",
+            Self::EmbeddedAssetNotFound => "EmbeddedAssetNotFound",
+            Self::UnsupportedEmbedMimeType => "UnsupportedEmbedMimeType",
+            Self::EmbedMustBeClassOrByteArray => "EmbedMustBeClassOrByteArray",
+            Self::UnknownEventName => "UnknownEventName",
+            Self::IncompatibleEventListener => "IncompatibleEventListener",
+            Self::ReturnTypeDeclarationMustBeGenerator => "ReturnTypeDeclarationMustBeGenerator",
+            Self::DefaultXmlNamespaceMustBeNamespaceOrString => "DefaultXmlNamespaceMustBeNamespaceOrString",
+            Self::EmbedConstantMustNotContainInitializer => "EmbedConstantMustNotContainInitializer",
+            Self::EmbedMustApplyToSingleBinding => "EmbedMustApplyToSingleBinding",
+        }
+    }
+
+    /// Whether the diagnostic kind is reported as a warning,
+    /// whose level may be configured through lints.
+    pub fn is_warning(&self) -> bool {
+        matches!(self,
+            Self::CallOnArrayType
+            | Self::ReferenceIsAlreadyNonNullable
+            | Self::ComparisonBetweenUnrelatedTypes
+            | Self::DuplicateVariableDefinition
+            | Self::ReturnValueHasNoTypeDeclaration
+            | Self::NanComparison
+            | Self::CallOnDateType
+            | Self::VariableHasNoTypeAnnotation
+            | Self::Unused
+            | Self::UnusedImport
+            | Self::UnreachableCode
+            | Self::ConstantCondition
+            | Self::VoidCondition
+            | Self::NonBooleanConstantCondition
+            | Self::UnawaitedPromiseCondition
+            | Self::UnrelatedSwitchTypeCase
+            | Self::UnbindableDataBindingSource
+            | Self::UnknownEventName)
+    }
+
+    pub fn from_id(id: i32) -> Option<Self> {
+        Self::VALUES.iter().find(|k| k.id() == id).copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::VALUES.iter().find(|k| k.name() == name).copied()
+    }
 }
\ No newline at end of file
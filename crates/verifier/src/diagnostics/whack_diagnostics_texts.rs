@@ -43,7 +43,6 @@ lazy_static! {
         WhackDiagnosticKind::AwaitOperandMustBeAPromise.id() => "Await operand must be a Promise.".into(),
         WhackDiagnosticKind::OperandMustBeNumber.id() => "Operand must be a Number.".into(),
         WhackDiagnosticKind::ReferenceIsAlreadyNonNullable.id() => "Reference is already non nullable.".into(),
-        WhackDiagnosticKind::YieldIsNotSupported.id() => "Yield operator is currently not supported.".into(),
         WhackDiagnosticKind::UnrelatedMathOperation.id() => "Unrelated mathematical operation using type {1}.".into(),
         WhackDiagnosticKind::ComparisonBetweenUnrelatedTypes.id() => "Comparison between a value of type {1} and an unrelated type {2}.".into(),
         WhackDiagnosticKind::UnrelatedTernaryOperands.id() => "Unrelated ternary operands of types {1} and {2}.".into(),
@@ -58,7 +57,6 @@ lazy_static! {
         WhackDiagnosticKind::EntityIsNotAConstant.id() => "Entity is not a constant.".into(),
         WhackDiagnosticKind::ReturnValueHasNoTypeDeclaration.id() => "Return value has no type declaration.".into(),
         WhackDiagnosticKind::ReturnTypeDeclarationMustBePromise.id() => "Return type declaration must be Promise.".into(),
-        WhackDiagnosticKind::ReturnTypeInferenceIsNotImplemented.id() => "Return type inference is not implemented in the present. Using an untyped type.".into(),
         WhackDiagnosticKind::NanComparison.id() => "Comparison involving NaN. Use isNaN() or !isNaN() instead.".into(),
         WhackDiagnosticKind::NotABooleanConstant.id() => "Not a Boolean constant.".into(),
         WhackDiagnosticKind::EmptyPackage.id() => "Package {1} is empty.".into(),
@@ -110,7 +108,6 @@ lazy_static! {
         WhackDiagnosticKind::ExpectedToIterateType.id() => "Expected to iterate item of data type {1}.".into(),
         WhackDiagnosticKind::IllegalReturnStatement.id() => "Illegal return statement.".into(),
         WhackDiagnosticKind::ReturnValueMustBeSpecified.id() => "Return value must be specified.".into(),
-        WhackDiagnosticKind::DxnsStatementIsNotSupported.id() => "Default XML namespace statement is not supported in HTML5 environment.".into(),
         WhackDiagnosticKind::ReturnNotAllowedInPackageInit.id() => "The return statement cannot be used in package initialization code.".into(),
         WhackDiagnosticKind::ReturnNotAllowedInGlobalInit.id() => "The return statement cannot be used in global initialization code.".into(),
         WhackDiagnosticKind::NamespaceConflictsWithConfigurationNs.id() => "Namespace conflicts with a configuration namespace.".into(),
@@ -118,6 +115,37 @@ lazy_static! {
         WhackDiagnosticKind::RecordClassMustHaveEmptyConstructor.id() => "Record class must have empty constructor.".into(),
         WhackDiagnosticKind::IllegalMapKey.id() => "Illegal map key.".into(),
         WhackDiagnosticKind::UnusedImport.id() => "Unused import.".into(),
+        WhackDiagnosticKind::NotAllCodePathsReturnAValue.id() => "Not all code paths return a value.".into(),
+        WhackDiagnosticKind::UnreachableCode.id() => "Unreachable code.".into(),
+        WhackDiagnosticKind::MxmlSourceOutsideSourcePath.id() => "MXML source must be inside a source path.".into(),
+        WhackDiagnosticKind::UnknownMxmlNamespace.id() => "Unknown MXML namespace: {1}.".into(),
+        WhackDiagnosticKind::UnknownMxmlAttribute.id() => "{1} is not a property, event or style of {2}.".into(),
+        WhackDiagnosticKind::MxmlValueNotConvertible.id() => "Cannot convert \"{1}\" to {2}.".into(),
+        WhackDiagnosticKind::UnsupportedMxmlFeature.id() => "Unsupported MXML feature: {1}.".into(),
+        WhackDiagnosticKind::MxmlChildrenNotAccepted.id() => "{1} does not accept child elements.".into(),
+        WhackDiagnosticKind::MxmlSourceNameIsNotAnIdentifier.id() => "MXML source path contains a name that is not an identifier: {1}.".into(),
+        WhackDiagnosticKind::VariableNotDefinitelyAssigned.id() => "Variable {1} is read before it is definitely assigned.".into(),
+        WhackDiagnosticKind::ConstantCondition.id() => "Condition is always {1}.".into(),
+        WhackDiagnosticKind::DuplicateLabel.id() => "Duplicate label: {1}.".into(),
+        WhackDiagnosticKind::VoidCondition.id() => "Condition is of type void and always evaluates to false.".into(),
+        WhackDiagnosticKind::NonBooleanConstantCondition.id() => "Condition is a constant that is not a Boolean.".into(),
+        WhackDiagnosticKind::UnawaitedPromiseCondition.id() => "Condition of type {1} always evaluates to true. Did you forget to await it?".into(),
+        WhackDiagnosticKind::UnrelatedSwitchTypeCase.id() => "Case of type {1} can never match a value of type {2}.".into(),
+        WhackDiagnosticKind::MalformedBindableMetadata.id() => "Malformed Bindable meta-data.".into(),
+        WhackDiagnosticKind::BindableNotAllowedHere.id() => "[Bindable] is only allowed on instance variables and accessors of a class.".into(),
+        WhackDiagnosticKind::BindableRequiresEventDispatcher.id() => "[Bindable] requires {1} to be an event dispatcher.".into(),
+        WhackDiagnosticKind::UnbindableDataBindingSource.id() => "Data binding will not be able to detect assignments to {1}.".into(),
+        WhackDiagnosticKind::MalformedEmbedMetadata.id() => "Malformed Embed meta-data.".into(),
+        WhackDiagnosticKind::EmbedNotAllowedHere.id() => "[Embed] is only allowed on static constants of a class.".into(),
+        WhackDiagnosticKind::EmbeddedAssetNotFound.id() => "Embedded asset not found: {1}.".into(),
+        WhackDiagnosticKind::UnsupportedEmbedMimeType.id() => "Unsupported MIME type for embedded asset: {1}.".into(),
+        WhackDiagnosticKind::EmbedMustBeClassOrByteArray.id() => "[Embed] constant must be of type Class or ByteArray.".into(),
+        WhackDiagnosticKind::UnknownEventName.id() => "Unknown event {1} for {2}.".into(),
+        WhackDiagnosticKind::IncompatibleEventListener.id() => "Listener parameter of type {1} is incompatible with event {2} of type {3}.".into(),
+        WhackDiagnosticKind::ReturnTypeDeclarationMustBeGenerator.id() => "Return type declaration must be Generator or Iterator.".into(),
+        WhackDiagnosticKind::DefaultXmlNamespaceMustBeNamespaceOrString.id() => "Default XML namespace must be a Namespace or String, found {1}.".into(),
+        WhackDiagnosticKind::EmbedConstantMustNotContainInitializer.id() => "[Embed] constant must not contain an initializer.".into(),
+        WhackDiagnosticKind::EmbedMustApplyToSingleBinding.id() => "[Embed] must apply to a single variable binding.".into(),
         // WhackDiagnosticKind::K.id() => ".".into(),
     };
 }
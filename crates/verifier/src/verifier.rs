@@ -29,4 +29,22 @@ mod statement;
 pub(crate) use statement::*;
 
 mod control_flow;
-pub(crate) use control_flow::*;
\ No newline at end of file
+pub(crate) use control_flow::*;
+
+mod mxml;
+pub(crate) use mxml::*;
+
+mod fixes;
+pub(crate) use fixes::*;
+
+mod flow;
+pub(crate) use flow::*;
+
+mod bindable;
+pub(crate) use bindable::*;
+
+mod embed;
+pub(crate) use embed::*;
+
+mod event_listener;
+pub(crate) use event_listener::*;
\ No newline at end of file
@@ -0,0 +1,90 @@
+use crate::ns::*;
+
+/// The event dispatched by a `[Bindable]` property when no event is specified.
+pub(crate) const DEFAULT_BINDABLE_EVENT: &str = "propertyChange";
+
+/// Verification of the `[Bindable]` meta-data.
+///
+/// `[Bindable]` and `[Bindable(event="name")]` are allowed on instance variables
+/// and accessors of a class that is an event dispatcher, that is, a class
+/// that defines or inherits a `dispatchEvent()` method.
+///
+/// The event name is recorded in the property slot (`bindable_event()`),
+/// which code generation uses to turn a variable into a getter/setter pair
+/// whose setter dispatches the change event, and which MXML data bindings
+/// use to determine whether a source property can be watched.
+pub(crate) struct BindableSubverifier;
+
+impl BindableSubverifier {
+    /// Verifies the `[Bindable]` meta-data, if any, applied to a variable
+    /// or virtual slot, and records its event name in the slot.
+    pub fn verify_bindable_metadata(verifier: &mut Subverifier, attributes: &[Attribute], slot: &Entity) -> Result<(), DeferError> {
+        let Some(metadata) = Attribute::find_metadata(attributes).into_iter().find(|m| m.name.0 == "Bindable") else {
+            return Ok(());
+        };
+        if slot.bindable_event().is_some() {
+            return Ok(());
+        }
+        let host = verifier.host.clone();
+
+        // Event name
+        let Some(event) = Self::event_name(&metadata) else {
+            verifier.add_verify_error(&metadata.location, WhackDiagnosticKind::MalformedBindableMetadata, diagarg![]);
+            return Ok(());
+        };
+
+        // Instance property of a class (a virtual slot has the parent of its accessors)
+        let parent = if slot.is::<VirtualSlot>() {
+            slot.getter(&host).or(slot.setter(&host)).and_then(|accessor| accessor.parent())
+        } else {
+            slot.parent()
+        };
+        let is_const = slot.is::<VariableSlot>() && slot.read_only(&host);
+        let Some(class) = parent.filter(|p| p.is::<ClassType>() && Attribute::find_static(attributes).is_none() && !is_const) else {
+            verifier.add_verify_error(&metadata.name.1, WhackDiagnosticKind::BindableNotAllowedHere, diagarg![]);
+            return Ok(());
+        };
+
+        // Event dispatcher
+        if !Self::is_event_dispatcher(&host, &class)? {
+            verifier.add_verify_error(&metadata.name.1, WhackDiagnosticKind::BindableRequiresEventDispatcher, diagarg![class.clone()]);
+            return Ok(());
+        }
+
+        slot.set_bindable_event(Some(event));
+        Ok(())
+    }
+
+    /// Returns the event name from either an unkeyed string or an `event="name"` entry.
+    fn event_name(metadata: &Rc<Metadata>) -> Option<String> {
+        let Some(entries) = metadata.entries.as_ref() else {
+            return Some(DEFAULT_BINDABLE_EVENT.to_owned());
+        };
+        let [entry] = entries.as_slice() else {
+            return None;
+        };
+        if entry.key.as_ref().map(|(k, _)| k != "event").unwrap_or(false) {
+            return None;
+        }
+        let event = match entry.value.as_ref() {
+            MetadataValue::String(v) => v.0.clone(),
+            MetadataValue::IdentifierString(v) => v.0.clone(),
+        };
+        if event.is_empty() { None } else { Some(event) }
+    }
+
+    /// Returns whether a class defines or inherits a `dispatchEvent()` method.
+    fn is_event_dispatcher(host: &Database, class: &Entity) -> Result<bool, DeferError> {
+        for class in class.descending_class_hierarchy(host) {
+            if class.is::<UnresolvedEntity>() {
+                return Err(DeferError(None));
+            }
+            if let Ok(Some(method)) = class.prototype(host).get_in_any_ns("dispatchEvent") {
+                if method.is::<MethodSlot>() {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
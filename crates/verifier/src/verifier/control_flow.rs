@@ -1,37 +1,538 @@
-#![allow(unused)]
-
 use crate::ns::*;
-use colored::Colorize;
 
-thread_local! {
-    static REPORTED_UNIMPLEMENTED: Cell<bool> = Cell::new(false);
+/// A `break` or `continue` target during control flow analysis.
+struct ControlFlowJumpTarget {
+    labels: Vec<String>,
+    /// Whether an unlabeled `break` may target this statement
+    /// (that is, a loop or `switch`).
+    breakable: bool,
+    break_block: usize,
+    /// Block to which a `continue` statement jumps, present for loops only.
+    continue_block: Option<usize>,
+}
+
+/// Builds the control flow graph of a function body.
+///
+/// Blocks are accumulated as directive lists identified by an index
+/// and are only contributed to the `ControlFlowGraph` at the end of the analysis.
+/// The block at index 0 is the entry and the block at index 1 is the exit,
+/// which is targeted by `return` and `throw` statements and by
+/// the end of the function body.
+pub(crate) struct ControlFlowAnalyser<'a> {
+    verifier: &'a mut Subverifier,
+    blocks: Vec<Vec<Rc<Directive>>>,
+    edges: Vec<(usize, usize)>,
+    /// Block being built, or `None` if the current point is unreachable.
+    current: Option<usize>,
+    /// Indicates that the current point became unreachable
+    /// due to a jump statement or a constant condition, in which case
+    /// the next statement is reported as unreachable code.
+    exited: bool,
+    jump_targets: Vec<ControlFlowJumpTarget>,
 }
 
-pub(crate) struct ControlFlowAnalysisIsUnimplemented;
+impl<'a> ControlFlowAnalyser<'a> {
+    const ENTRY: usize = 0;
+    const EXIT: usize = 1;
+
+    /// Analyses the control flow of a function body, contributing blocks and edges
+    /// to the given control flow graph and reporting unreachable code.
+    ///
+    /// Returns whether the end of the function body is reachable.
+    ///
+    /// A function body may be analysed more than once due to deferred
+    /// verification, in which case the control flow graph is rebuilt.
+    pub fn analyse_function_body(verifier: &mut Subverifier, list: &[Rc<Directive>], cfg: &ControlFlowGraph) -> bool {
+        cfg.blocks().clear();
+        cfg.edges().clear();
+
+        let mut analyser = ControlFlowAnalyser {
+            verifier,
+            blocks: vec![vec![], vec![]],
+            edges: vec![],
+            current: Some(Self::ENTRY),
+            exited: false,
+            jump_targets: vec![],
+        };
+        analyser.analyse_directives(list);
 
-impl ControlFlowAnalysisIsUnimplemented {
-    pub fn unimplemented() {
-        if !REPORTED_UNIMPLEMENTED.get() {
-            println!("{} Control flow analysis is unimplemented.\n", "Warning:".yellow());
-            REPORTED_UNIMPLEMENTED.set(true);
+        let end = analyser.current;
+        if let Some(end) = end {
+            analyser.edges.push((end, Self::EXIT));
         }
+        let end_reachable = end.map(|end| analyser.is_reachable(end)).unwrap_or(false);
+
+        // Contribute blocks and edges to the control flow graph.
+        let blocks: Vec<ControlFlowBlock> = analyser.blocks.into_iter().map(ControlFlowBlock::new).collect();
+        for block in blocks.iter() {
+            cfg.blocks().push(block.clone());
+        }
+        for (from, to) in analyser.edges.iter() {
+            cfg.edges().push(ControlFlowEdge {
+                from: blocks[*from].clone(),
+                to: blocks[*to].clone(),
+            });
+        }
+
+        end_reachable
     }
-}
 
-pub(crate) struct ControlFlowParent<'a> {
-    pub parent: ControlFlowBlock,
-    pub next_siblings: &'a [Rc<Directive>],
-}
+    fn new_block(&mut self) -> usize {
+        self.blocks.push(vec![]);
+        self.blocks.len() - 1
+    }
+
+    fn add_edge(&mut self, from: Option<usize>, to: usize) {
+        if let Some(from) = from {
+            self.edges.push((from, to));
+        }
+    }
+
+    fn has_predecessors(&self, block: usize) -> bool {
+        self.edges.iter().any(|(_, to)| *to == block)
+    }
+
+    fn is_reachable(&self, block: usize) -> bool {
+        let mut visited = vec![false; self.blocks.len()];
+        let mut pending = vec![Self::ENTRY];
+        while let Some(b) = pending.pop() {
+            if b == block {
+                return true;
+            }
+            if visited[b] {
+                continue;
+            }
+            visited[b] = true;
+            for (from, to) in self.edges.iter() {
+                if *from == b && !visited[*to] {
+                    pending.push(*to);
+                }
+            }
+        }
+        false
+    }
+
+    /// Moves to the given block, or marks the current point as unreachable
+    /// if the block has no predecessors.
+    fn continue_at(&mut self, block: usize) {
+        if self.has_predecessors(block) {
+            self.current = Some(block);
+        } else {
+            self.current = None;
+            self.exited = true;
+        }
+    }
+
+    /// Makes the current point unreachable after a jump.
+    fn jump(&mut self, to: usize, exited: bool) {
+        self.add_edge(self.current, to);
+        self.current = None;
+        self.exited = exited;
+    }
+
+    /// Returns the current block, starting an unreachable block if
+    /// the current point is unreachable.
+    fn current_block(&mut self, drtv: &Rc<Directive>) -> usize {
+        if let Some(current) = self.current {
+            return current;
+        }
+        if self.exited && !matches!(drtv.as_ref(), Directive::EmptyStatement(_)) {
+            self.verifier.add_warning(&drtv.location(), WhackDiagnosticKind::UnreachableCode, diagarg![]);
+        }
+        self.exited = false;
+        let block = self.new_block();
+        self.current = Some(block);
+        block
+    }
+
+    fn append_line(&mut self, drtv: &Rc<Directive>) -> usize {
+        let block = self.current_block(drtv);
+        self.blocks[block].push(drtv.clone());
+        block
+    }
+
+    fn analyse_directives(&mut self, list: &[Rc<Directive>]) {
+        for drtv in list.iter() {
+            self.analyse_directive(drtv, vec![]);
+        }
+    }
 
-pub(crate) struct ControlFlowAnalyser;
+    /// Analyses a directive. `labels` contains the labels
+    /// directly applied to the directive.
+    fn analyse_directive(&mut self, drtv: &Rc<Directive>, labels: Vec<String>) {
+        match drtv.as_ref() {
+            Directive::EmptyStatement(_) |
+            Directive::ExpressionStatement(_) |
+            Directive::SuperStatement(_) |
+            Directive::DefaultXmlNamespaceStatement(_) |
+            Directive::VariableDefinition(_) => {
+                self.append_line(drtv);
+            },
+            Directive::Block(block) => {
+                self.analyse_labeled_block(drtv, &block.directives, labels);
+            },
+            Directive::WithStatement(wstmt) => {
+                self.append_line(drtv);
+                self.analyse_labeled_substatement(&wstmt.body, labels);
+            },
+            Directive::LabeledStatement(labstmt) => {
+                let mut labels = labels;
+                labels.push(labstmt.label.0.clone());
+                self.analyse_directive(&labstmt.substatement, labels);
+            },
+            Directive::IfStatement(ifstmt) => {
+                self.append_line(drtv);
+                let after = self.new_block();
+                self.jump_targets.push(ControlFlowJumpTarget {
+                    labels, breakable: false, break_block: after, continue_block: None,
+                });
+                let test = self.current;
+                let constant = self.constant_condition(&ifstmt.test);
 
-impl ControlFlowAnalyser {
-    pub fn analyse_directives<'a>(
-        _list: &[Rc<Directive>],
-        _cfg: &ControlFlowGraph,
-        _building_block: &mut Vec<Rc<Directive>>,
-        _ascending_parents: &[ControlFlowParent<'a>]
-    ) {
-        ControlFlowAnalysisIsUnimplemented::unimplemented();
+                self.enter_branch(test, constant != Some(false));
+                self.analyse_directive(&ifstmt.consequent, vec![]);
+                let consequent_exited = self.current.is_none() && self.exited;
+                self.add_edge(self.current, after);
+
+                let mut alternative_exited = false;
+                if let Some(alt) = ifstmt.alternative.as_ref() {
+                    self.enter_branch(test, constant != Some(true));
+                    self.analyse_directive(alt, vec![]);
+                    alternative_exited = self.current.is_none() && self.exited;
+                    self.add_edge(self.current, after);
+                } else if constant != Some(true) {
+                    self.add_edge(test, after);
+                }
+
+                self.jump_targets.pop();
+                self.continue_at(after);
+                self.exited = self.current.is_none() && consequent_exited && alternative_exited;
+            },
+            Directive::SwitchStatement(swstmt) => {
+                self.append_line(drtv);
+                let discriminant = self.current;
+                let after = self.new_block();
+                self.jump_targets.push(ControlFlowJumpTarget {
+                    labels, breakable: true, break_block: after, continue_block: None,
+                });
+                let mut has_default = false;
+                for case in swstmt.cases.iter() {
+                    let case_block = self.new_block();
+                    if !case.labels.is_empty() {
+                        self.add_edge(discriminant, case_block);
+                    }
+                    has_default = has_default || case.labels.iter().any(|label| matches!(label, CaseLabel::Default(_)));
+
+                    // Fall through from the previous case.
+                    self.add_edge(self.current, case_block);
+                    self.current = Some(case_block);
+                    self.analyse_directives(&case.directives);
+                }
+                self.add_edge(self.current, after);
+                if !has_default {
+                    self.add_edge(discriminant, after);
+                }
+                self.jump_targets.pop();
+                self.continue_at(after);
+            },
+            Directive::SwitchTypeStatement(swstmt) => {
+                self.append_line(drtv);
+                let discriminant = self.current;
+                let after = self.new_block();
+                self.jump_targets.push(ControlFlowJumpTarget {
+                    labels, breakable: true, break_block: after, continue_block: None,
+                });
+                let mut has_default = false;
+                for case in swstmt.cases.iter() {
+                    let case_block = self.new_block();
+                    self.add_edge(discriminant, case_block);
+                    has_default = has_default || case.parameter.is_none();
+                    self.current = Some(case_block);
+                    self.analyse_directives(&case.block.directives);
+                    self.add_edge(self.current, after);
+                }
+                if !has_default {
+                    self.add_edge(discriminant, after);
+                }
+                self.jump_targets.pop();
+                self.continue_at(after);
+            },
+            Directive::WhileStatement(wstmt) => {
+                self.current_block(drtv);
+                let test = self.new_block();
+                self.add_edge(self.current, test);
+                self.blocks[test].push(drtv.clone());
+                let after = self.new_block();
+                let constant = self.constant_condition(&wstmt.test);
+                if constant != Some(true) {
+                    self.add_edge(Some(test), after);
+                }
+                self.analyse_loop_body(&wstmt.body, labels, test, test, after, constant != Some(false));
+                self.continue_at(after);
+            },
+            Directive::DoStatement(dostmt) => {
+                self.current_block(drtv);
+                let body = self.new_block();
+                self.add_edge(self.current, body);
+                let test = self.new_block();
+                self.blocks[test].push(drtv.clone());
+                let after = self.new_block();
+                self.jump_targets.push(ControlFlowJumpTarget {
+                    labels, breakable: true, break_block: after, continue_block: Some(test),
+                });
+                self.current = Some(body);
+                self.analyse_directive(&dostmt.body, vec![]);
+                self.add_edge(self.current, test);
+                self.jump_targets.pop();
+                self.add_edge(Some(test), body);
+                if self.constant_condition(&dostmt.test) != Some(true) {
+                    self.add_edge(Some(test), after);
+                }
+                self.continue_at(after);
+            },
+            Directive::ForStatement(forstmt) => {
+                // The initializer belongs to the current block.
+                self.append_line(drtv);
+                let test = self.new_block();
+                self.add_edge(self.current, test);
+                let update = self.new_block();
+                let after = self.new_block();
+                let constant = forstmt.test.as_ref().map(|test| self.constant_condition(test)).unwrap_or(Some(true));
+                if constant != Some(true) {
+                    self.add_edge(Some(test), after);
+                }
+                self.add_edge(Some(update), test);
+                self.analyse_loop_body(&forstmt.body, labels, test, update, after, constant != Some(false));
+                self.continue_at(after);
+            },
+            Directive::ForInStatement(forstmt) => {
+                self.append_line(drtv);
+                let next = self.new_block();
+                self.add_edge(self.current, next);
+                let after = self.new_block();
+                self.add_edge(Some(next), after);
+                self.analyse_loop_body(&forstmt.body, labels, next, next, after, true);
+                self.continue_at(after);
+            },
+            Directive::BreakStatement(brkstmt) => {
+                self.append_line(drtv);
+                let label = brkstmt.label.as_ref().map(|(label, _)| label.clone());
+                let target = self.jump_targets.iter().rev().find(|target| {
+                    if let Some(label) = label.as_ref() {
+                        target.labels.contains(label)
+                    } else {
+                        target.breakable
+                    }
+                }).map(|target| target.break_block);
+                if let Some(target) = target {
+                    self.jump(target, true);
+                } else {
+                    self.current = None;
+                    self.exited = false;
+                }
+            },
+            Directive::ContinueStatement(contstmt) => {
+                self.append_line(drtv);
+                let label = contstmt.label.as_ref().map(|(label, _)| label.clone());
+                let target = self.jump_targets.iter().rev().find(|target| {
+                    target.continue_block.is_some() && label.as_ref().map(|label| target.labels.contains(label)).unwrap_or(true)
+                }).and_then(|target| target.continue_block);
+                if let Some(target) = target {
+                    self.jump(target, true);
+                } else {
+                    self.current = None;
+                    self.exited = false;
+                }
+            },
+            Directive::ReturnStatement(_) |
+            Directive::ThrowStatement(_) => {
+                self.append_line(drtv);
+                self.jump(Self::EXIT, true);
+            },
+            Directive::TryStatement(trystmt) => {
+                self.analyse_try_stmt(drtv, trystmt, labels);
+            },
+            Directive::ConfigurationDirective(cfgdrtv) => {
+                let cval = self.verifier.host.node_mapping().get(drtv);
+                if cval.map(|cval| cval.is::<BooleanConstant>() && cval.boolean_value()).unwrap_or(false) {
+                    if let Directive::Block(block) = cfgdrtv.directive.as_ref() {
+                        self.analyse_labeled_block(drtv, &block.directives, labels);
+                    } else {
+                        self.analyse_directive(&cfgdrtv.directive, labels);
+                    }
+                }
+            },
+            Directive::IncludeDirective(incdrtv) => {
+                self.analyse_directives(&incdrtv.nested_directives);
+            },
+            Directive::DirectiveInjection(inj) => {
+                self.analyse_directives(inj.directives.borrow().as_ref());
+            },
+            // Definitions other than variables do not contribute
+            // to the control flow.
+            _ => {},
+        }
+    }
+
+    /// Analyses a directive list that may be labeled.
+    fn analyse_labeled_block(&mut self, drtv: &Rc<Directive>, list: &[Rc<Directive>], labels: Vec<String>) {
+        if labels.is_empty() {
+            self.analyse_directives(list);
+            return;
+        }
+        self.current_block(drtv);
+        let after = self.new_block();
+        self.jump_targets.push(ControlFlowJumpTarget {
+            labels, breakable: false, break_block: after, continue_block: None,
+        });
+        self.analyse_directives(list);
+        self.add_edge(self.current, after);
+        self.jump_targets.pop();
+        let exited = self.current.is_none() && self.exited;
+        self.continue_at(after);
+        self.exited = self.current.is_none() && exited;
+    }
+
+    /// Analyses a substatement that may be labeled.
+    fn analyse_labeled_substatement(&mut self, substatement: &Rc<Directive>, labels: Vec<String>) {
+        if labels.is_empty() {
+            self.analyse_directive(substatement, vec![]);
+            return;
+        }
+        let after = self.new_block();
+        self.jump_targets.push(ControlFlowJumpTarget {
+            labels, breakable: false, break_block: after, continue_block: None,
+        });
+        self.analyse_directive(substatement, vec![]);
+        self.add_edge(self.current, after);
+        self.jump_targets.pop();
+        let exited = self.current.is_none() && self.exited;
+        self.continue_at(after);
+        self.exited = self.current.is_none() && exited;
+    }
+
+    fn analyse_loop_body(&mut self, body: &Rc<Directive>, labels: Vec<String>, test: usize, continue_block: usize, after: usize, reachable: bool) {
+        self.jump_targets.push(ControlFlowJumpTarget {
+            labels, breakable: true, break_block: after, continue_block: Some(continue_block),
+        });
+        self.enter_branch(Some(test), reachable);
+        self.analyse_directive(body, vec![]);
+        self.add_edge(self.current, continue_block);
+        self.jump_targets.pop();
+    }
+
+    fn analyse_try_stmt(&mut self, drtv: &Rc<Directive>, trystmt: &TryStatement, labels: Vec<String>) {
+        self.current_block(drtv);
+        let after = self.new_block();
+        self.jump_targets.push(ControlFlowJumpTarget {
+            labels, breakable: false, break_block: after, continue_block: None,
+        });
+
+        // Exceptions may be thrown anywhere within the try block,
+        // therefore handlers are reachable from where the try block starts.
+        let start = self.current;
+        let try_block = self.new_block();
+        self.add_edge(start, try_block);
+        let edges_len = self.edges.len();
+        self.current = Some(try_block);
+        self.analyse_directives(&trystmt.block.directives);
+
+        let mut completions: Vec<usize> = vec![];
+        let mut exited = self.exited;
+        if let Some(end) = self.current {
+            completions.push(end);
+        }
+
+        for catch_clause in trystmt.catch_clauses.iter() {
+            let catch_block = self.new_block();
+            self.add_edge(start, catch_block);
+            self.current = Some(catch_block);
+            self.exited = false;
+            self.analyse_directives(&catch_clause.block.directives);
+            if let Some(end) = self.current {
+                completions.push(end);
+            } else {
+                exited = exited && self.exited;
+            }
+        }
+
+        if let Some(finally_clause) = trystmt.finally_clause.as_ref() {
+            // Jumps out of the try block and catch clauses, with which
+            // the finally clause proceeds.
+            let mut pending: Vec<usize> = self.edges[edges_len..].iter()
+                .filter(|(from, to)| *from >= try_block && *to < try_block)
+                .map(|(_, to)| *to)
+                .collect();
+            pending.sort();
+            pending.dedup();
+
+            let finally_block = self.new_block();
+            self.add_edge(start, finally_block);
+            for end in completions.iter() {
+                self.add_edge(Some(*end), finally_block);
+            }
+            self.current = Some(finally_block);
+            self.exited = false;
+            self.analyse_directives(&finally_clause.block.directives);
+
+            for target in pending.iter() {
+                self.add_edge(self.current, *target);
+            }
+            if completions.is_empty() {
+                self.current = None;
+                self.exited = exited;
+            } else {
+                self.add_edge(self.current, after);
+            }
+            exited = self.current.is_none() && (self.exited || exited);
+        } else {
+            for end in completions.iter() {
+                self.add_edge(Some(*end), after);
+            }
+        }
+
+        self.jump_targets.pop();
+        self.continue_at(after);
+        self.exited = self.current.is_none() && exited;
+    }
+
+    /// Enters a branch from the given block. If the branch is not reachable
+    /// due to a constant condition, the current point becomes unreachable
+    /// and the first statement of the branch is reported as unreachable code.
+    fn enter_branch(&mut self, from: Option<usize>, reachable: bool) {
+        if reachable {
+            let block = self.new_block();
+            self.add_edge(from, block);
+            self.current = Some(block);
+            self.exited = false;
+        } else {
+            self.current = None;
+            self.exited = true;
+        }
+    }
+
+    /// Returns the value of a condition folded to a constant, reporting
+    /// the condition unless it is a Boolean literal, such as in `while (true)`,
+    /// or a configuration constant, such as in `if (CONFIG::DEBUG)`.
+    fn constant_condition(&mut self, test: &Rc<Expression>) -> Option<bool> {
+        let val = self.verifier.host.node_mapping().get(test)?;
+        if !val.is::<BooleanConstant>() {
+            return None;
+        }
+        let value = val.boolean_value();
+        if !Self::is_literal_or_configuration_constant(test) {
+            self.verifier.add_warning(&test.location(), WhackDiagnosticKind::ConstantCondition, diagarg![value.to_string()]);
+        }
+        Some(value)
+    }
+
+    fn is_literal_or_configuration_constant(exp: &Rc<Expression>) -> bool {
+        match exp.as_ref() {
+            Expression::BooleanLiteral(_) => true,
+            Expression::QualifiedIdentifier(id) => id.qualifier.is_some(),
+            Expression::Paren(e) => Self::is_literal_or_configuration_constant(&e.expression),
+            Expression::Unary(e) => e.operator == Operator::LogicalNot && Self::is_literal_or_configuration_constant(&e.expression),
+            _ => false,
+        }
     }
 }
\ No newline at end of file
@@ -48,7 +48,7 @@ impl DestructuringDeclarationSubverifier {
             }
 
             // Ensure definition is not shadowing another definition
-            verifier.ensure_not_shadowing_definition(&pattern.location(), output, parent, &name);
+            verifier.ensure_not_shadowing_definition(&pattern.location(), output, parent, &name, vec![]);
 
             // Define variable property
             if let Some(prev) = output.get(&name) {
@@ -511,7 +511,7 @@ impl DestructuringDeclarationSubverifier {
         }
 
         // Ensure definition is not shadowing another definition
-        verifier.ensure_not_shadowing_definition(&shorthand.1, output, parent, &name);
+        verifier.ensure_not_shadowing_definition(&shorthand.1, output, parent, &name, vec![]);
 
         if let Some(prev) = output.get(&name) {
             verifier.handle_definition_conflict(&prev, &slot)
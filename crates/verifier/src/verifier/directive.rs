@@ -5,10 +5,13 @@ pub(crate) struct DirectiveSubverifier;
 impl DirectiveSubverifier {
     pub fn verify_directives(verifier: &mut Subverifier, list: &[Rc<Directive>]) -> Result<(), DeferError> {
         let mut any_defer = false;
-        for drtv in list {
+        let narrowings_len = verifier.flow_narrowings.len();
+        for (i, drtv) in list.iter().enumerate() {
             let r = Self::verify_directive(verifier, drtv).is_err();
             any_defer = any_defer || r;
+            FlowSubverifier::enter_early_exit_narrowings(verifier, list, i);
         }
+        FlowSubverifier::exit_narrowings(verifier, narrowings_len);
         if any_defer { Err(DeferError(None)) } else { Ok(()) }
     }
 
@@ -55,6 +58,15 @@ impl DirectiveSubverifier {
         if any_defer { Err(DeferError(None)) } else { Ok(()) }
     }
 
+    /// Verifies a directive executed only where `test` evaluates to `truthy`.
+    fn verify_guarded_directive(verifier: &mut Subverifier, test: &Rc<Expression>, truthy: bool, drtv: &Rc<Directive>) -> Result<(), DeferError> {
+        let narrowings = FlowSubverifier::narrowings(verifier, test, truthy);
+        let len = FlowSubverifier::enter_narrowings(verifier, narrowings, &AssignedNames::of_directive(drtv));
+        let r = Self::verify_directive(verifier, drtv);
+        FlowSubverifier::exit_narrowings(verifier, len);
+        r
+    }
+
     pub fn verify_directive(verifier: &mut Subverifier, drtv: &Rc<Directive>) -> Result<(), DeferError> {
         match drtv.as_ref() {
             Directive::VariableDefinition(defn) => {
@@ -101,9 +113,9 @@ impl DirectiveSubverifier {
                 Self::verify_directive(verifier, &lstmt.substatement)
             },
             Directive::IfStatement(ifstmt) => {
-                let mut any_defer = Self::verify_directive(verifier, &ifstmt.consequent).is_err();
+                let mut any_defer = Self::verify_guarded_directive(verifier, &ifstmt.test, true, &ifstmt.consequent).is_err();
                 if let Some(alt) = &ifstmt.alternative {
-                    let r = Self::verify_directive(verifier, alt).is_err();
+                    let r = Self::verify_guarded_directive(verifier, &ifstmt.test, false, alt).is_err();
                     any_defer = any_defer || r;
                 }
                 if any_defer { Err(DeferError(None)) } else { Ok(()) }
@@ -124,7 +136,7 @@ impl DirectiveSubverifier {
                 Self::verify_directive(verifier, &dostmt.body)
             },
             Directive::WhileStatement(whilestmt) => {
-                Self::verify_directive(verifier, &whilestmt.body)
+                Self::verify_guarded_directive(verifier, &whilestmt.test, true, &whilestmt.body)
             },
             Directive::ForStatement(forstmt) => {
                 Self::verify_for_stmt(verifier, drtv, forstmt)
@@ -141,6 +153,18 @@ impl DirectiveSubverifier {
             Directive::ImportDirective(impdrtv) => {
                 Self::verify_import_directive(verifier, drtv, impdrtv)
             },
+            // The default XML namespace is determined before
+            // variable initializers are verified.
+            Directive::DefaultXmlNamespaceStatement(dxns) => {
+                let phase = verifier.lazy_init_drtv_phase(drtv, VerifierPhase::Alpha);
+                if phase == VerifierPhase::Finished {
+                    return Ok(());
+                }
+                // Retry once the variables of the right-hand side are declared.
+                Self::verify_default_xml_namespace_stmt(verifier, dxns)?;
+                verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                Ok(())
+            },
             Directive::UseNamespaceDirective(usedrtv) => {
                 let phase = verifier.lazy_init_drtv_phase(drtv, VerifierPhase::Alpha);
                 if phase == VerifierPhase::Finished {
@@ -404,7 +428,7 @@ impl DirectiveSubverifier {
                         // If there is no type annotation and initialiser is unspecified,
                         // then report a warning
                         if binding.destructuring.type_annotation.is_none() && binding.initializer.is_none() {
-                            verifier.add_warning(&binding.destructuring.location, WhackDiagnosticKind::VariableHasNoTypeAnnotation, diagarg![]);
+                            verifier.add_warning_with_fixes(&binding.destructuring.location, WhackDiagnosticKind::VariableHasNoTypeAnnotation, diagarg![], DiagnosticFixes::declare_type(&binding.destructuring.location, &init.static_type(&verifier.host)));
                         }
 
                         // If variable is marked constant, is not `[Embed]` and does not contain an initializer,
@@ -563,7 +587,11 @@ impl DirectiveSubverifier {
 
                 let public_ns = class_parent_scope.search_system_ns_in_scope_chain(SystemNamespaceKind::Public).unwrap();
                 let name = verifier.host.factory().create_qname(&ns, defn.name.0.clone());
-                let mut class_entity = verifier.host.factory().create_class_type(name.clone(), &public_ns);
+
+                // Reuse a class placeholder declared ahead of time (as for MXML components).
+                let placeholder = verifier.host.node_mapping().get(drtv).filter(|c| class_out.get(&name).as_ref() == Some(c));
+
+                let mut class_entity = placeholder.clone().unwrap_or_else(|| verifier.host.factory().create_class_type(name.clone(), &public_ns));
                 class_entity.set_parent(Some(class_parent.clone()));
                 class_entity.set_asdoc(defn.asdoc.clone());
                 class_entity.set_location(Some(defn.name.1.clone()));
@@ -677,7 +705,9 @@ impl DirectiveSubverifier {
                 // Attempt to define the class partially;
                 // or fail if a conflict occurs, therefore ignoring
                 // this class definition.
-                if let Some(prev) = class_out.get(&name) {
+                if placeholder.is_some() {
+                    Unused(&verifier.host).add_nominal(&class_entity);
+                } else if let Some(prev) = class_out.get(&name) {
                     class_entity = verifier.handle_definition_conflict(&prev, &class_entity);
                 } else {
                     Unused(&verifier.host).add_nominal(&class_entity);
@@ -1856,7 +1886,7 @@ impl DirectiveSubverifier {
                 let (_, ns_alias_parent, ns_alias_out, ns) = defn_local.unwrap();
 
                 let name = verifier.host.factory().create_qname(&ns, defn.left.0.clone());
-                verifier.ensure_not_shadowing_definition(&defn.left.1, &ns_alias_out, &ns_alias_parent, &name);
+                verifier.ensure_not_shadowing_definition(&defn.left.1, &ns_alias_out, &ns_alias_parent, &name, vec![]);
 
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
@@ -1980,9 +2010,26 @@ impl DirectiveSubverifier {
             },
             // Eta
             VerifierPhase::Eta => {
-                // @todo
-                // - Handle the `[Bindable]` meta-data for simple identifier patterns
-                // - Handle the `[Embed]` meta-data for simple identifier patterns
+                // Handle the `[Bindable]` meta-data for simple identifier patterns
+                for binding in &defn.bindings {
+                    let is_simple_id = matches!(binding.destructuring.destructuring.as_ref(), Expression::QualifiedIdentifier(_));
+                    if !is_simple_id {
+                        continue;
+                    }
+                    let slot = verifier.node_mapping().get(&binding.destructuring.destructuring);
+                    if let Some(slot) = slot.filter(|slot| slot.is::<VariableSlot>()) {
+                        BindableSubverifier::verify_bindable_metadata(verifier, &defn.attributes, &slot)?;
+                    }
+                }
+
+                // Handle the `[Embed]` meta-data, which applies to a single binding
+                // without an initializer
+                let slot1 = verifier.node_mapping().get(&defn.bindings[0].destructuring.destructuring);
+                if let Some(slot1) = slot1.filter(|slot| slot.is::<VariableSlot>()) {
+                    if EmbedSubverifier::verify_embed_bindings(verifier, &defn.attributes, &defn.bindings) {
+                        EmbedSubverifier::verify_embed_metadata(verifier, &defn.attributes, &slot1)?;
+                    }
+                }
 
                 // Next phase
                 verifier.set_drtv_phase(drtv, VerifierPhase::Theta);
@@ -2056,7 +2103,7 @@ impl DirectiveSubverifier {
                     // If there is no type annotation and initialiser is unspecified,
                     // then report a warning
                     if binding.destructuring.type_annotation.is_none() && binding.initializer.is_none() {
-                        verifier.add_warning(&binding.destructuring.location, WhackDiagnosticKind::VariableHasNoTypeAnnotation, diagarg![]);
+                        verifier.add_warning_with_fixes(&binding.destructuring.location, WhackDiagnosticKind::VariableHasNoTypeAnnotation, diagarg![], DiagnosticFixes::declare_type(&binding.destructuring.location, &init.static_type(&verifier.host)));
                     }
 
                     // If variable is marked constant, is not `[Embed]` and does not contain an initializer,
@@ -2494,14 +2541,25 @@ impl DirectiveSubverifier {
                         }
                         partials.set_result_type(Some(result_type));
                     }
+                } else if partials.result_type().is_none() && Self::infers_fn_defn_result_type(&slot, &fn_parent, &ns, &common) {
+                    // The result type is inferred once the function body is verified.
+                    verifier.pending_result_types.insert(slot.clone(), common.clone());
                 } else if partials.result_type().is_none() {
                     verifier.add_warning(&loc, WhackDiagnosticKind::ReturnValueHasNoTypeDeclaration, diagarg![]);
-                    partials.set_result_type(Some(if common.contains_await { host.promise_type_of_any()? } else { host.any_type() }));
+                    partials.set_result_type(Some(if common.contains_await {
+                        host.promise_type_of_any()?
+                    } else if common.contains_yield {
+                        host.generator_type_of_any()?
+                    } else {
+                        host.any_type()
+                    }));
                 }
 
                 // Set signature
                 let signature;
-                if partials.signature().is_none() {
+                if partials.result_type().is_none() {
+                    signature = host.unresolved_entity();
+                } else if partials.signature().is_none() {
                     let mut result_type = partials.result_type().unwrap(); 
 
                     if common.contains_await && !result_type.promise_result_type(&host)?.is_some() {
@@ -2509,6 +2567,11 @@ impl DirectiveSubverifier {
                         result_type = host.promise_type().defer()?.apply_type(&host, &host.promise_type().defer()?.type_params().unwrap(), &shared_array![host.invalidation_entity()])
                     }
 
+                    if common.contains_yield && result_type.iterator_element_type(&host).is_none() && !result_type.is::<InvalidationEntity>() {
+                        verifier.add_verify_error(&loc, WhackDiagnosticKind::ReturnTypeDeclarationMustBeGenerator, diagarg![]);
+                        result_type = host.generator_type_of_any()?;
+                    }
+
                     let signature1 = host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), result_type);
                     partials.set_signature(Some(signature1.clone()));
                     signature = signature1;
@@ -2523,7 +2586,7 @@ impl DirectiveSubverifier {
                 // Do not allow shadowing properties in base classes if not marked "override".
                 if !marked_override {
                     let name = verifier.host.factory().create_qname(&ns, name.0.clone());
-                    verifier.ensure_not_shadowing_definition(&loc, &fn_out, &fn_parent, &name);
+                    verifier.ensure_not_shadowing_definition(&loc, &fn_out, &fn_parent, &name, DiagnosticFixes::add_override(defn));
                 }
 
                 // Restore scope
@@ -2554,7 +2617,7 @@ impl DirectiveSubverifier {
                             verifier.add_verify_error(&loc, WhackDiagnosticKind::IncompatibleOverride, diagarg![expected_signature.clone(), actual_signature.clone()]);
                         },
                         Err(MethodOverrideError::MustOverrideAMethod) => {
-                            verifier.add_verify_error(&loc, WhackDiagnosticKind::MustOverrideAMethod, diagarg![]);
+                            verifier.add_verify_error_with_fixes(&loc, WhackDiagnosticKind::MustOverrideAMethod, diagarg![], DiagnosticFixes::remove_override(defn));
                         },
                         Err(MethodOverrideError::OverridingFinalMethod) => {
                             verifier.add_verify_error(&loc, WhackDiagnosticKind::OverridingFinalMethod, diagarg![]);
@@ -2575,6 +2638,20 @@ impl DirectiveSubverifier {
         }
     }
 
+    /// Indicates whether the result type of a function definition without
+    /// a result type annotation is inferred from its body, which is the case
+    /// for package-level and top-level functions and for static or private
+    /// methods, as these are never overridden nor implement an interface.
+    fn infers_fn_defn_result_type(slot: &Entity, fn_parent: &Entity, ns: &Entity, common: &Rc<FunctionCommon>) -> bool {
+        if common.body.is_none() || slot.is_native() || slot.is_abstract() || slot.is_external() || slot.is_overriding() {
+            return false;
+        }
+        if fn_parent.is::<ClassType>() || fn_parent.is::<EnumType>() {
+            return slot.is_static() || ns.is_private_ns();
+        }
+        fn_parent.is::<Package>() || (fn_parent.is::<Activation>() && fn_parent.is_global_initialization())
+    }
+
     fn verify_constructor_fn_defn(verifier: &mut Subverifier, drtv: &Rc<Directive>, defn: &FunctionDefinition, name: &(String, Location)) -> Result<(), DeferError> {
         let phase = verifier.lazy_init_drtv_phase(drtv, VerifierPhase::Alpha);
         if phase == VerifierPhase::Finished {
@@ -3208,7 +3285,7 @@ impl DirectiveSubverifier {
                 // Do not allow shadowing properties in base classes if not marked "override".
                 if !marked_override {
                     let name = verifier.host.factory().create_qname(&ns, name.0.clone());
-                    verifier.ensure_not_shadowing_definition(&loc, &fn_out, &fn_parent, &name);
+                    verifier.ensure_not_shadowing_definition(&loc, &fn_out, &fn_parent, &name, DiagnosticFixes::add_override(defn));
                 }
 
                 // Restore scope
@@ -3247,7 +3324,7 @@ impl DirectiveSubverifier {
                             verifier.add_verify_error(&loc, WhackDiagnosticKind::IncompatibleOverride, diagarg![expected_signature.clone(), actual_signature.clone()]);
                         },
                         Err(MethodOverrideError::MustOverrideAMethod) => {
-                            verifier.add_verify_error(&loc, WhackDiagnosticKind::MustOverrideAMethod, diagarg![]);
+                            verifier.add_verify_error_with_fixes(&loc, WhackDiagnosticKind::MustOverrideAMethod, diagarg![], DiagnosticFixes::remove_override(defn));
                         },
                         Err(MethodOverrideError::OverridingFinalMethod) => {
                             verifier.add_verify_error(&loc, WhackDiagnosticKind::OverridingFinalMethod, diagarg![]);
@@ -3260,6 +3337,11 @@ impl DirectiveSubverifier {
                 Err(DeferError(None))
             },
             VerifierPhase::Omega => {
+                // Handle the `[Bindable]` meta-data
+                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let virtual_var = slot.of_virtual_slot(&verifier.host).unwrap();
+                BindableSubverifier::verify_bindable_metadata(verifier, &defn.attributes, &virtual_var)?;
+
                 // Finish
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
@@ -3591,7 +3673,7 @@ impl DirectiveSubverifier {
                 // Do not allow shadowing properties in base classes if not marked "override".
                 if !marked_override {
                     let name = verifier.host.factory().create_qname(&ns, name.0.clone());
-                    verifier.ensure_not_shadowing_definition(&loc, &fn_out, &fn_parent, &name);
+                    verifier.ensure_not_shadowing_definition(&loc, &fn_out, &fn_parent, &name, DiagnosticFixes::add_override(defn));
                 }
 
                 // Restore scope
@@ -3630,7 +3712,7 @@ impl DirectiveSubverifier {
                             verifier.add_verify_error(&loc, WhackDiagnosticKind::IncompatibleOverride, diagarg![expected_signature.clone(), actual_signature.clone()]);
                         },
                         Err(MethodOverrideError::MustOverrideAMethod) => {
-                            verifier.add_verify_error(&loc, WhackDiagnosticKind::MustOverrideAMethod, diagarg![]);
+                            verifier.add_verify_error_with_fixes(&loc, WhackDiagnosticKind::MustOverrideAMethod, diagarg![], DiagnosticFixes::remove_override(defn));
                         },
                         Err(MethodOverrideError::OverridingFinalMethod) => {
                             verifier.add_verify_error(&loc, WhackDiagnosticKind::OverridingFinalMethod, diagarg![]);
@@ -3643,6 +3725,11 @@ impl DirectiveSubverifier {
                 Err(DeferError(None))
             },
             VerifierPhase::Omega => {
+                // Handle the `[Bindable]` meta-data
+                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let virtual_var = slot.of_virtual_slot(&verifier.host).unwrap();
+                BindableSubverifier::verify_bindable_metadata(verifier, &defn.attributes, &virtual_var)?;
+
                 // Finish
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
@@ -3844,6 +3931,24 @@ impl DirectiveSubverifier {
         Ok(())
     }
 
+    /// Verifies a `default xml namespace` statement, whose right-hand side
+    /// becomes the default XML namespace of the enclosing activation.
+    fn verify_default_xml_namespace_stmt(verifier: &mut Subverifier, dxns: &DefaultXmlNamespaceStatement) -> Result<(), DeferError> {
+        let host = verifier.host.clone();
+        let Some(ns) = verifier.verify_expression(&dxns.right, &Default::default())? else {
+            return Ok(());
+        };
+        let ns_t = ns.static_type(&host).escape_of_nullable_or_non_nullable();
+        if ![host.any_type(), host.namespace_type(), host.string_type()].contains(&ns_t) {
+            verifier.add_verify_error(&dxns.right.location(), WhackDiagnosticKind::DefaultXmlNamespaceMustBeNamespaceOrString, diagarg![ns_t]);
+            return Ok(());
+        }
+        if let Some(act) = verifier.scope().search_activation() {
+            act.set_default_xml_namespace(Some(ns));
+        }
+        Ok(())
+    }
+
     fn verify_import_directive(verifier: &mut Subverifier, drtv: &Rc<Directive>, impdrtv: &ImportDirective) -> Result<(), DeferError> {
         let phase = verifier.lazy_init_drtv_phase(drtv, VerifierPhase::Alpha);
         if phase == VerifierPhase::Finished {
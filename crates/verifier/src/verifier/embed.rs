@@ -0,0 +1,151 @@
+use crate::ns::*;
+
+/// MIME types of embedded assets, by file extension.
+const EMBED_MIME_TYPES: [(&str, &str); 14] = [
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("svg", "image/svg+xml"),
+    ("webp", "image/webp"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("ttf", "font/ttf"),
+    ("otf", "font/otf"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("json", "application/json"),
+];
+
+/// MIME type of an embedded asset whose file extension is not recognized.
+const DEFAULT_EMBED_MIME_TYPE: &str = "application/octet-stream";
+
+/// Verification of the `[Embed]` meta-data.
+///
+/// `[Embed(source="path")]` and `[Embed(source="path", mimeType="type")]`
+/// are allowed on static constants of a class typed `Class` or `ByteArray`,
+/// declared with a single binding and no initializer.
+/// The source path is resolved relative to the directory of the
+/// source file, and the MIME type is otherwise determined from
+/// the file extension.
+///
+/// The resolved asset is recorded in the variable slot (`embedded_asset()`),
+/// which code generation uses to initialize the constant and
+/// to include the asset in the output.
+pub(crate) struct EmbedSubverifier;
+
+impl EmbedSubverifier {
+    /// Ensures that a variable definition with the `[Embed]` meta-data
+    /// consists of a single binding without an initializer,
+    /// whose value would otherwise be silently dropped.
+    ///
+    /// Returns `false` if an error has been reported.
+    pub fn verify_embed_bindings(verifier: &mut Subverifier, attributes: &[Attribute], bindings: &[Rc<VariableBinding>]) -> bool {
+        if !Attribute::find_metadata(attributes).iter().any(|m| m.name.0 == "Embed") {
+            return true;
+        }
+        let mut valid = true;
+        for binding in bindings.iter().skip(1) {
+            verifier.add_verify_error(&binding.destructuring.location, WhackDiagnosticKind::EmbedMustApplyToSingleBinding, diagarg![]);
+            valid = false;
+        }
+        if let Some(init) = bindings[0].initializer.as_ref() {
+            verifier.add_verify_error(&init.location(), WhackDiagnosticKind::EmbedConstantMustNotContainInitializer, diagarg![]);
+            valid = false;
+        }
+        valid
+    }
+
+    /// Verifies the `[Embed]` meta-data, if any, applied to a variable,
+    /// and records the embedded asset in the variable slot.
+    pub fn verify_embed_metadata(verifier: &mut Subverifier, attributes: &[Attribute], slot: &Entity) -> Result<(), DeferError> {
+        let Some(metadata) = Attribute::find_metadata(attributes).into_iter().find(|m| m.name.0 == "Embed") else {
+            return Ok(());
+        };
+        if slot.embedded_asset().is_some() {
+            return Ok(());
+        }
+        let host = verifier.host.clone();
+        let class_type = host.class_type().defer()?;
+
+        // Static constant of a class
+        let is_class_static = slot.parent().map(|p| p.is::<ClassType>()).unwrap_or(false) && Attribute::find_static(attributes).is_some();
+        if !(is_class_static && slot.read_only(&host)) {
+            verifier.add_verify_error(&metadata.name.1, WhackDiagnosticKind::EmbedNotAllowedHere, diagarg![]);
+            return Ok(());
+        }
+
+        // Source and MIME type
+        let Some(((source, source_loc), mime_type)) = Self::source_and_mime_type(&metadata) else {
+            verifier.add_verify_error(&metadata.name.1, WhackDiagnosticKind::MalformedEmbedMetadata, diagarg![]);
+            return Ok(());
+        };
+
+        // Data type
+        let byte_array_type = host.byte_array_type();
+        let t = slot.static_type(&host).escape_of_nullable_or_non_nullable();
+        if t != class_type && t != byte_array_type {
+            verifier.add_verify_error(&metadata.name.1, WhackDiagnosticKind::EmbedMustBeClassOrByteArray, diagarg![]);
+            return Ok(());
+        }
+
+        // Resolve the source relative to the source file
+        let Some(path) = Self::resolve_source(&source_loc, &source) else {
+            verifier.add_verify_error(&source_loc, WhackDiagnosticKind::EmbeddedAssetNotFound, diagarg![source]);
+            return Ok(());
+        };
+
+        // MIME type
+        let mime_type = match mime_type {
+            Some(mime_type) => {
+                if mime_type != DEFAULT_EMBED_MIME_TYPE && !EMBED_MIME_TYPES.iter().any(|(_, m)| *m == mime_type) {
+                    verifier.add_verify_error(&metadata.name.1, WhackDiagnosticKind::UnsupportedEmbedMimeType, diagarg![mime_type]);
+                    return Ok(());
+                }
+                mime_type
+            },
+            None => Self::mime_type_of(&path),
+        };
+
+        slot.set_embedded_asset(Some(Rc::new(EmbeddedAsset {
+            path: path.to_string_lossy().into_owned(),
+            mime_type,
+        })));
+        Ok(())
+    }
+
+    /// Returns the source (from either an unkeyed string or a `source="path"` entry)
+    /// and the MIME type (from a `mimeType="type"` entry).
+    fn source_and_mime_type(metadata: &Rc<Metadata>) -> Option<((String, Location), Option<String>)> {
+        let mut source: Option<(String, Location)> = None;
+        let mut mime_type: Option<String> = None;
+        for entry in metadata.entries.as_ref()?.iter() {
+            let value = match entry.value.as_ref() {
+                MetadataValue::String(v) => v.clone(),
+                MetadataValue::IdentifierString(v) => v.clone(),
+            };
+            match entry.key.as_ref().map(|(k, _)| k.as_str()) {
+                None | Some("source") => source = Some(value),
+                Some("mimeType") => mime_type = Some(value.0),
+                _ => {},
+            }
+        }
+        Some((source.filter(|(s, _)| !s.is_empty())?, mime_type))
+    }
+
+    fn resolve_source(location: &Location, source: &str) -> Option<std::path::PathBuf> {
+        let file_path = std::path::PathBuf::from(location.compilation_unit().file_path()?);
+        let path = file_path.parent()?.join(source);
+        let path = path.canonicalize().ok()?;
+        if path.is_file() { Some(path) } else { None }
+    }
+
+    fn mime_type_of(path: &std::path::Path) -> String {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        EMBED_MIME_TYPES.iter()
+            .find(|(e, _)| *e == extension)
+            .map(|(_, m)| (*m).to_owned())
+            .unwrap_or(DEFAULT_EMBED_MIME_TYPE.to_owned())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::ns::*;
+
+/// Checking of event listener calls against `[Event]` meta-data.
+///
+/// A call to `addEventListener()` or `removeEventListener()` whose first
+/// argument is a string constant is checked when the type of the base object,
+/// its base classes or its interfaces declare events: an unknown event name
+/// is reported, and the first parameter of the listener must accept
+/// the declared event type.
+pub(crate) struct EventListenerSubverifier;
+
+impl EventListenerSubverifier {
+    pub fn verify_listener_call(verifier: &mut Subverifier, base: &Entity, exp: &CallExpression) -> Result<(), DeferError> {
+        if !base.is::<InstanceReferenceValue>() {
+            return Ok(());
+        }
+        let method_name = base.property().name().local_name();
+        if method_name != "addEventListener" && method_name != "removeEventListener" {
+            return Ok(());
+        }
+        let Some(name_exp) = exp.arguments.first() else {
+            return Ok(());
+        };
+        let Some(name) = verifier.node_mapping().get(name_exp).filter(|k| k.is::<StringConstant>()) else {
+            return Ok(());
+        };
+        let name = name.string_value();
+        let host = verifier.host.clone();
+
+        // Events of the object type and its ascending types
+        let object_type = base.base().static_type(&host).escape_of_nullable_or_non_nullable();
+        let mut declares_events = false;
+        let mut event: Option<Event> = None;
+        for t in [object_type.clone()].into_iter().chain(object_type.all_ascending_types(&host)) {
+            if !(t.is_class_type_possibly_after_sub() || t.is_interface_type_possibly_after_sub()) {
+                continue;
+            }
+            let events = t.events();
+            declares_events = declares_events || events.length() != 0;
+            if let Some(e) = events.get(&name) {
+                event = Some(e);
+                break;
+            }
+        }
+        if !declares_events {
+            return Ok(());
+        }
+        let Some(event) = event else {
+            verifier.add_warning(&name_exp.location(), WhackDiagnosticKind::UnknownEventName, diagarg![name, object_type]);
+            return Ok(());
+        };
+
+        // Listener
+        let Some(listener_exp) = exp.arguments.get(1) else {
+            return Ok(());
+        };
+        let Some(mut listener) = verifier.node_mapping().get(listener_exp) else {
+            return Ok(());
+        };
+        // The listener is possibly converted to `Function`.
+        while listener.is::<ConversionValue>() {
+            listener = listener.base();
+        }
+        let listener_type = Self::listener_signature(&host, &listener);
+        if !listener_type.is::<FunctionType>() || event.data_type.is::<UnresolvedEntity>() {
+            return Ok(());
+        }
+        let Some(param) = listener_type.params().get(0) else {
+            return Ok(());
+        };
+        let param_type = param.static_type.escape_of_nullable_or_non_nullable();
+        if !event.data_type.is_equals_or_subtype_of(&param_type, &host)? {
+            verifier.add_verify_error(&listener_exp.location(), WhackDiagnosticKind::IncompatibleEventListener, diagarg![param.static_type.clone(), name, event.data_type.clone()]);
+        }
+        Ok(())
+    }
+
+    /// Returns the signature of a function expression or method,
+    /// or otherwise the static type of a listener.
+    fn listener_signature(host: &Database, listener: &Entity) -> Entity {
+        if listener.is::<LambdaObject>() {
+            if let Some(act) = listener.activation() {
+                return act.of_method().signature(host);
+            }
+        }
+        if listener.is::<FixtureReferenceValue>() && listener.property().is::<MethodSlot>() {
+            return listener.property().signature(host);
+        }
+        listener.static_type(host).escape_of_nullable_or_non_nullable()
+    }
+}
@@ -85,7 +85,11 @@ impl ExpSubverifier {
         }
         let r = r.unwrap();
         if r.is_none() {
-            verifier.add_verify_error(&id.location, WhackDiagnosticKind::UndefinedProperty, diagarg![key.local_name().unwrap()]);
+            let mut fixes = vec![];
+            if let (None, QualifiedIdentifierIdentifier::Id((name, name_loc))) = (&id.qualifier, &id.id) {
+                fixes = DiagnosticFixes::did_you_mean(verifier, name, name_loc);
+            }
+            verifier.add_verify_error_with_fixes(&id.location, WhackDiagnosticKind::UndefinedProperty, diagarg![key.local_name().unwrap()], fixes);
             return Ok(None);
         }
         let r = r.unwrap();
@@ -93,6 +97,13 @@ impl ExpSubverifier {
         // Mark local capture
         verifier.detect_local_capture(&r);
 
+        // Definite assignment and narrowing
+        if context.mode == VerifyMode::Read {
+            FlowSubverifier::verify_read(verifier, &r, &id.location);
+            let r = FlowSubverifier::narrow_reference(verifier, &r)?;
+            return verifier.reference_post_processing(r, context);
+        }
+
         // Post-processing
         verifier.reference_post_processing(r, context)
     }
@@ -468,7 +479,14 @@ impl ExpSubverifier {
             verifier.add_verify_error(&id.location, WhackDiagnosticKind::UndefinedPropertyWithStaticType, diagarg![key.local_name().unwrap(), base.static_type(&verifier.host)]);
             return Ok(None);
         }
-        let r = r.unwrap();
+        let mut r = r.unwrap();
+
+        // Unqualified XML element names resolve in the default XML namespace.
+        if r.is::<XmlReferenceValue>() && r.qualifier().is_none() {
+            if let Some(ns) = verifier.default_xml_namespace() {
+                r = verifier.host.factory().create_xml_reference_value(&r.base(), Some(ns), &r.key());
+            }
+        }
 
         // No need to mark local capture for the property operator.
         // verifier.detect_local_capture(&r);
@@ -822,7 +840,9 @@ impl ExpSubverifier {
         if base.is::<FixtureReferenceValue>() && base.property().is::<MethodSlot>() {
             let sig = base.property().signature(&verifier.host).defer()?;
             match ArgumentsSubverifier::verify(verifier, &exp.arguments, &sig) {
-                Ok(_) => {},
+                Ok(_) => {
+                    EventListenerSubverifier::verify_listener_call(verifier, &base, exp)?;
+                },
                 Err(VerifierArgumentsError::Defer) => {
                     return Err(DeferError(None));
                 },
@@ -950,6 +970,26 @@ impl ExpSubverifier {
             return Ok(Some(verifier.host.factory().create_value(&result_type)));
         }
 
+        if exp.operator == Operator::Yield {
+            let host = verifier.host.clone();
+            let act = verifier.scope().search_activation().filter(|act| !(act.is_package_initialization() || act.is_global_initialization()));
+            let sig = act.as_ref().filter(|act| !verifier.infers_result_type(&act.of_method())).map(|act| act.of_method().signature(&host));
+
+            // Coerce the operand to the yielded type, or
+            // record its value if the result type is being inferred.
+            if let Some(sig) = sig {
+                let yield_type = sig.result_type().iterator_element_type(&host).unwrap_or(host.any_type());
+                verifier.imp_coerce_exp(&exp.expression, &yield_type)?;
+            } else if let Some(val) = verifier.verify_expression(&exp.expression, &default())? {
+                if let Some(act) = act {
+                    verifier.yielded_values.entry(act).or_default().push((exp.expression.location(), val));
+                }
+            }
+
+            // The value sent to the generator
+            return Ok(Some(host.factory().create_value(&host.any_type())));
+        }
+
         let update_ops = [Operator::PreIncrement, Operator::PreDecrement, Operator::PostIncrement, Operator::PostDecrement];
         let rw_mode = if exp.operator == Operator::Delete {
             VerifyMode::Delete
@@ -999,10 +1039,6 @@ impl ExpSubverifier {
             Operator::Typeof => {
                 Ok(Some(verifier.host.factory().create_value(&verifier.host.string_type().defer()?)))
             },
-            Operator::Yield => {
-                verifier.add_verify_error(&exp.location, WhackDiagnosticKind::YieldIsNotSupported, diagarg![]);
-                Ok(None)
-            },
             Operator::Positive => {
                 let val_st_esc = val_st.escape_of_non_nullable();
                 if !([verifier.host.any_type(), verifier.host.object_type().defer()?, verifier.host.jsval_type().defer()?].contains(&val_st_esc) || verifier.host.numeric_types()?.contains(&val_st)) {
@@ -1474,7 +1510,7 @@ impl ExpSubverifier {
                 Ok(Some(verifier.host.factory().create_value(&verifier.host.factory().create_value(&verifier.host.any_type()))))
             },
             Operator::LogicalAnd => {
-                let Some(right) = verifier.verify_expression(&exp.right, &default())? else {
+                let Some(right) = FlowSubverifier::verify_guarded_exp(verifier, &exp.left, true, &exp.right, &default())? else {
                     return Ok(None);
                 };
                 let right_st = right.static_type(&verifier.host);
@@ -1501,7 +1537,7 @@ impl ExpSubverifier {
                 Ok(Some(verifier.host.factory().create_value(&verifier.host.any_type())))
             },
             Operator::LogicalOr => {
-                let Some(right) = verifier.verify_expression(&exp.right, &default())? else {
+                let Some(right) = FlowSubverifier::verify_guarded_exp(verifier, &exp.left, false, &exp.right, &default())? else {
                     return Ok(None);
                 };
                 let right_st = right.static_type(&verifier.host);
@@ -1544,8 +1580,8 @@ impl ExpSubverifier {
             context_type: context.context_type.clone(),
             ..default()
         };
-        let Some(conseq) = verifier.verify_expression(&exp.consequent, &ctx1)? else {
-            verifier.verify_expression(&exp.alternative, &ctx1)?;
+        let Some(conseq) = FlowSubverifier::verify_guarded_exp(verifier, &exp.test, true, &exp.consequent, &ctx1)? else {
+            FlowSubverifier::verify_guarded_exp(verifier, &exp.test, false, &exp.alternative, &ctx1)?;
             return Ok(None);
         };
 
@@ -1555,7 +1591,7 @@ impl ExpSubverifier {
             context_type: ctx1.context_type.or(Some(conseq_st.clone())),
             ..default()
         };
-        let Some(alt) = verifier.verify_expression(&exp.alternative, &ctx2)? else {
+        let Some(alt) = FlowSubverifier::verify_guarded_exp(verifier, &exp.test, false, &exp.alternative, &ctx2)? else {
             return Ok(None);
         };
 
@@ -1695,6 +1731,7 @@ impl ExpSubverifier {
             };
 
             AssignmentDestructuringSubverifier::verify_pattern(verifier, &exp.left, &right)?;
+            FlowSubverifier::assign_pattern(verifier, &exp.left);
 
             Ok(Some(verifier.host.factory().create_value(&right.static_type(&verifier.host))))
         } else {
@@ -1738,6 +1775,8 @@ impl ExpSubverifier {
 
                     _ => panic!(),
                 }
+            } else {
+                FlowSubverifier::assign(verifier, &exp.left);
             }
 
             if right.is_none() {
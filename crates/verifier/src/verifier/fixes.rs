@@ -0,0 +1,106 @@
+use crate::ns::*;
+
+/// Builds fix-it suggestions attached to diagnostics.
+pub(crate) struct DiagnosticFixes;
+
+impl DiagnosticFixes {
+    /// Removes the `override` attribute from a function definition.
+    pub fn remove_override(defn: &FunctionDefinition) -> Vec<DiagnosticFix> {
+        let Some(loc) = Attribute::find_override(&defn.attributes) else {
+            return vec![];
+        };
+        let cu = loc.compilation_unit();
+        let text = cu.text();
+        let rest = &text[loc.last_offset()..];
+        let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        let loc = Location::with_offsets(&cu, loc.first_offset(), loc.last_offset() + spaces);
+        vec![DiagnosticFix::new("Remove 'override'", vec![TextEdit::delete(&loc)])]
+    }
+
+    /// Marks a function definition as `override`, inserting the attribute
+    /// before the `function` keyword, which follows the last attribute.
+    pub fn add_override(defn: &FunctionDefinition) -> Vec<DiagnosticFix> {
+        let cu = defn.location.compilation_unit();
+        let text = cu.text();
+        let start = defn.attributes.last().map(|a| a.location().last_offset()).unwrap_or(defn.location.first_offset());
+        let rest = &text[start..];
+        let offset = start + (rest.len() - rest.trim_start().len());
+        if !text[offset..].starts_with("function") {
+            return vec![];
+        }
+        vec![DiagnosticFix::new_machine_applicable("Add 'override'", vec![TextEdit::insert(&cu, offset, "override ")])]
+    }
+
+    /// Declares the inferred type of a variable binding lacking a type annotation.
+    pub fn declare_type(binding_loc: &Location, inferred_type: &Entity) -> Vec<DiagnosticFix> {
+        let cu = binding_loc.compilation_unit();
+        let type_name = inferred_type.to_string();
+        vec![DiagnosticFix::new(&format!("Declare type '{type_name}'"), vec![TextEdit::insert(&cu, binding_loc.last_offset(), &format!(":{type_name}"))])]
+    }
+
+    /// Suggests the name closest to an undefined lexical name among the names
+    /// available through the scope chain.
+    pub fn did_you_mean(verifier: &Subverifier, name: &str, location: &Location) -> Vec<DiagnosticFix> {
+        let host = verifier.host.clone();
+        let scope = verifier.scope();
+        let open_ns_set = scope.concat_open_ns_set_of_scope_chain();
+
+        let mut candidates: Vec<String> = vec![];
+        let mut scope1 = Some(scope);
+        while let Some(s) = scope1 {
+            for name in s.properties(&host).borrow().keys() {
+                if name.accessible_from_ns_set(&host, &open_ns_set) {
+                    candidates.push(name.local_name());
+                }
+            }
+            let mut definitions = vec![];
+            if s.is::<ClassScope>() || s.is::<EnumScope>() {
+                definitions.extend(s.class().available_static_definitions(&host, &open_ns_set).unwrap_or_default());
+                definitions.extend(s.class().available_prototype_definitions(&host, &open_ns_set).unwrap_or_default());
+            } else if s.is::<InterfaceScope>() {
+                definitions.extend(s.interface().available_prototype_definitions(&host, &open_ns_set).unwrap_or_default());
+            } else if s.is::<PackageScope>() {
+                definitions.extend(s.package().available_definitions_in_package(&host, &open_ns_set).unwrap_or_default());
+            }
+            for import in s.import_list().iter() {
+                if import.is::<PackagePropertyImport>() {
+                    definitions.push(import.property());
+                } else if import.is::<PackageWildcardImport>() {
+                    definitions.extend(import.package().available_definitions_in_package(&host, &open_ns_set).unwrap_or_default());
+                }
+            }
+            // Unresolved imports have no name.
+            candidates.extend(definitions.iter()
+                .filter(|d| !(d.is::<UnresolvedEntity>() || d.is::<InvalidationEntity>()))
+                .map(|d| d.name().local_name()));
+            scope1 = s.parent();
+        }
+
+        let threshold = (name.chars().count() / 3).max(1);
+        let closest = candidates.iter()
+            .filter(|candidate| candidate.as_str() != name)
+            .map(|candidate| (Self::edit_distance(name, candidate), candidate))
+            .filter(|(distance, _)| *distance <= threshold)
+            .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+        let Some((_, closest)) = closest else {
+            return vec![];
+        };
+        vec![DiagnosticFix::new(&format!("Change to '{closest}'"), vec![TextEdit::replace(location, closest)])]
+    }
+
+    /// Levenshtein distance between two strings.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, cb) in b.iter().enumerate() {
+                let above = row[j + 1];
+                row[j + 1] = if ca == *cb { diagonal } else { 1 + diagonal.min(above).min(row[j]) };
+                diagonal = above;
+            }
+        }
+        row[b.len()]
+    }
+}
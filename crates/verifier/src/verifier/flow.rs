@@ -0,0 +1,684 @@
+use crate::ns::*;
+
+/// Narrowing of a local variable within a region guarded by a condition.
+#[derive(Clone)]
+pub(crate) enum FlowNarrowing {
+    /// The variable is not `null` nor `undefined`, as after `x != null`.
+    NonNull,
+    /// The variable is of the given type, as after `x is T`.
+    Type(Entity),
+}
+
+/// A statement targeted by `break` statements, tracked while
+/// statements are verified.
+pub(crate) struct FlowBreakTarget {
+    pub labels: Vec<String>,
+    /// Whether an unlabeled `break` may target this statement
+    /// (that is, a loop or `switch`).
+    pub breakable: bool,
+    /// Whether `continue` may target this statement (that is, a loop).
+    pub continuable: bool,
+    /// Variables not definitely assigned at any `break` targeting this statement.
+    pub unassigned_vars: Vec<Entity>,
+    /// Variables not definitely assigned at any `continue` targeting this statement.
+    pub continue_unassigned_vars: Vec<Entity>,
+}
+
+/// Flow-sensitive checking of local variables.
+///
+/// # Narrowing
+///
+/// Within a region guarded by a condition such as `x != null` or `x is T`,
+/// reading the local variable `x` results in a non-nullable value or
+/// a value of type `T`. Regions are the consequent or alternative of an `if`
+/// statement, the body of a `while` statement, the right operand of
+/// a logical operator, the branches of a conditional expression and,
+/// after an `if` statement whose consequent does not complete normally,
+/// the remaining statements of the enclosing list.
+///
+/// A variable is not narrowed within a region that assigns to it.
+///
+/// # Definite assignment
+///
+/// A local variable declared without an initializer whose type has no default
+/// value (for example, a non-nullable type) must be definitely assigned before
+/// it is read. Variables are tracked while statements are verified.
+pub(crate) struct FlowSubverifier;
+
+impl FlowSubverifier {
+    /// Returns the narrowings that hold where `test` evaluates to `truthy`.
+    pub fn narrowings(verifier: &Subverifier, test: &Rc<Expression>, truthy: bool) -> Vec<(Entity, FlowNarrowing)> {
+        match test.as_ref() {
+            Expression::Paren(e) => Self::narrowings(verifier, &e.expression, truthy),
+            Expression::Unary(e) if e.operator == Operator::LogicalNot => {
+                Self::narrowings(verifier, &e.expression, !truthy)
+            },
+            Expression::QualifiedIdentifier(_) if truthy => {
+                Self::local_variable(verifier, test).map(|slot| vec![(slot, FlowNarrowing::NonNull)]).unwrap_or_default()
+            },
+            Expression::Binary(e) => {
+                match e.operator {
+                    Operator::LogicalAnd if truthy => {
+                        let mut r = Self::narrowings(verifier, &e.left, true);
+                        r.extend(Self::narrowings(verifier, &e.right, true));
+                        r
+                    },
+                    Operator::LogicalOr if !truthy => {
+                        let mut r = Self::narrowings(verifier, &e.left, false);
+                        r.extend(Self::narrowings(verifier, &e.right, false));
+                        r
+                    },
+                    Operator::Equals | Operator::StrictEquals |
+                    Operator::NotEquals | Operator::StrictNotEquals => {
+                        let non_null = matches!(e.operator, Operator::NotEquals | Operator::StrictNotEquals) == truthy;
+                        if !non_null {
+                            return vec![];
+                        }
+                        let variable = if Self::is_null_or_undefined(&e.right) {
+                            &e.left
+                        } else if Self::is_null_or_undefined(&e.left) {
+                            &e.right
+                        } else {
+                            return vec![];
+                        };
+                        Self::local_variable(verifier, variable).map(|slot| vec![(slot, FlowNarrowing::NonNull)]).unwrap_or_default()
+                    },
+                    Operator::Is | Operator::IsNot if (e.operator == Operator::Is) == truthy => {
+                        let Some(slot) = Self::local_variable(verifier, &e.left) else {
+                            return vec![];
+                        };
+                        let Some(t) = Self::type_reference(verifier, &e.right) else {
+                            return vec![];
+                        };
+                        vec![(slot, FlowNarrowing::Type(t))]
+                    },
+                    _ => vec![],
+                }
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Enters a region with the given narrowings, excluding variables assigned
+    /// within `region`. Returns the value to pass to `exit_narrowings()`.
+    pub fn enter_narrowings(verifier: &mut Subverifier, narrowings: Vec<(Entity, FlowNarrowing)>, region: &AssignedNames) -> usize {
+        let len = verifier.flow_narrowings.len();
+        if narrowings.is_empty() {
+            return len;
+        }
+        let Some(activation) = verifier.scope().search_activation() else {
+            return len;
+        };
+        for (slot, narrowing) in narrowings {
+            if !region.contains(&slot.name().local_name()) {
+                verifier.flow_narrowings.push((slot, narrowing, activation.clone()));
+            }
+        }
+        len
+    }
+
+    pub fn exit_narrowings(verifier: &mut Subverifier, len: usize) {
+        verifier.flow_narrowings.truncate(len);
+    }
+
+    /// Enters the narrowings that hold for the statements following
+    /// `list[i]` if it is an `if` statement without alternative
+    /// whose consequent does not complete normally, as in
+    /// `if (x == null) return;`.
+    pub fn enter_early_exit_narrowings(verifier: &mut Subverifier, list: &[Rc<Directive>], i: usize) {
+        let Directive::IfStatement(ifstmt) = list[i].as_ref() else {
+            return;
+        };
+        if ifstmt.alternative.is_some() || Self::completes_normally(&ifstmt.consequent) {
+            return;
+        }
+        let narrowings = Self::narrowings(verifier, &ifstmt.test, false);
+        Self::enter_narrowings(verifier, narrowings, &AssignedNames::of_directives(&list[(i + 1)..]));
+    }
+
+    /// Verifies an operand evaluated only where `test` evaluates to `truthy`,
+    /// such as the right operand of a logical operator.
+    pub fn verify_guarded_exp(verifier: &mut Subverifier, test: &Rc<Expression>, truthy: bool, exp: &Rc<Expression>, context: &VerifierExpressionContext) -> Result<Option<Entity>, DeferError> {
+        let narrowings = Self::narrowings(verifier, test, truthy);
+        let len = Self::enter_narrowings(verifier, narrowings, &AssignedNames::of_exp(exp));
+        let unassigned_vars = verifier.unassigned_vars.clone();
+        let r = verifier.verify_expression(exp, context);
+        Self::merge_unassigned_vars(verifier, &unassigned_vars);
+        Self::exit_narrowings(verifier, len);
+        r
+    }
+
+    /// Narrows a reference read from a local variable.
+    pub fn narrow_reference(verifier: &mut Subverifier, r: &Entity) -> Result<Entity, DeferError> {
+        if verifier.flow_narrowings.is_empty() || !r.is::<ScopeReferenceValue>() {
+            return Ok(r.clone());
+        }
+        let slot = r.property();
+        let activation = verifier.scope().search_activation();
+        let narrowing = verifier.flow_narrowings.iter().rev()
+            .find(|(slot1, _, activation1)| *slot1 == slot && Some(activation1) == activation.as_ref())
+            .map(|(_, narrowing, _)| narrowing.clone());
+        let host = verifier.host.clone();
+        match narrowing {
+            Some(FlowNarrowing::NonNull) => host.factory().create_non_null_value(r),
+            Some(FlowNarrowing::Type(t)) => {
+                let st = r.static_type(&host).escape_of_nullable_or_non_nullable();
+                if t != st && (st == host.any_type() || t.is_subtype_of(&st, &host)?) {
+                    host.factory().create_conversion_value(r, ConversionKind::ToContravariant, false, &t)
+                } else {
+                    Ok(r.clone())
+                }
+            },
+            None => Ok(r.clone()),
+        }
+    }
+
+    /// Returns the variable slot of a local variable referenced by
+    /// a lexical identifier.
+    fn local_variable(verifier: &Subverifier, exp: &Rc<Expression>) -> Option<Entity> {
+        let Expression::QualifiedIdentifier(id) = exp.as_ref() else {
+            return None;
+        };
+        if id.qualifier.is_some() || id.attribute {
+            return None;
+        }
+        let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id else {
+            return None;
+        };
+        let r = verifier.scope().lookup_in_scope_chain(&verifier.host, None, &PropertyLookupKey::LocalName(name.clone())).ok()??;
+        if r.is::<ScopeReferenceValue>() && r.property().is::<VariableSlot>() && r.base().search_activation().is_some() {
+            Some(r.property())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the type referenced by a lexical identifier.
+    fn type_reference(verifier: &Subverifier, exp: &Rc<Expression>) -> Option<Entity> {
+        let Expression::QualifiedIdentifier(id) = exp.as_ref() else {
+            return None;
+        };
+        if id.qualifier.is_some() || id.attribute {
+            return None;
+        }
+        let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id else {
+            return None;
+        };
+        let r = verifier.scope().lookup_in_scope_chain(&verifier.host, None, &PropertyLookupKey::LocalName(name.clone())).ok()??;
+        if r.is::<FixtureReferenceValue>() && r.property().is::<Type>() {
+            Some(r.property())
+        } else {
+            None
+        }
+    }
+
+    fn is_null_or_undefined(exp: &Rc<Expression>) -> bool {
+        match exp.as_ref() {
+            Expression::NullLiteral(_) => true,
+            Expression::QualifiedIdentifier(id) => {
+                id.qualifier.is_none() && matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "undefined")
+            },
+            _ => false,
+        }
+    }
+
+    /// Indicates whether a statement may complete normally. Statements
+    /// ending in `return`, `throw`, `break` or `continue` do not.
+    pub fn completes_normally(stmt: &Rc<Directive>) -> bool {
+        match stmt.as_ref() {
+            Directive::ReturnStatement(_) |
+            Directive::ThrowStatement(_) |
+            Directive::BreakStatement(_) |
+            Directive::ContinueStatement(_) => false,
+            Directive::Block(block) => block.directives.iter().all(Self::completes_normally),
+            Directive::IfStatement(ifstmt) => {
+                Self::completes_normally(&ifstmt.consequent)
+                    || ifstmt.alternative.as_ref().map(Self::completes_normally).unwrap_or(true)
+            },
+            _ => true,
+        }
+    }
+
+    /// Starts tracking the variables of a definition that are declared
+    /// without an initializer and whose type has no default value.
+    pub fn declare_unassigned_vars(verifier: &mut Subverifier, bindings: &[Rc<VariableBinding>]) {
+        let host = verifier.host.clone();
+        for binding in bindings.iter() {
+            if binding.initializer.is_some() || !matches!(binding.destructuring.destructuring.as_ref(), Expression::QualifiedIdentifier(_)) {
+                continue;
+            }
+            let Some(slot) = host.node_mapping().get(&binding.destructuring.destructuring) else {
+                continue;
+            };
+            if !slot.is::<VariableSlot>() {
+                continue;
+            }
+            let st = slot.static_type(&host);
+            if st.is::<UnresolvedEntity>() || st.is::<InvalidationEntity>() {
+                continue;
+            }
+            if let Ok(None) = st.type_default_value(&host) {
+                if !verifier.unassigned_vars.contains(&slot) {
+                    verifier.unassigned_vars.push(slot);
+                }
+            }
+        }
+    }
+
+    /// Marks the variable referenced by an assignment target as assigned.
+    pub fn assign(verifier: &mut Subverifier, target: &Rc<Expression>) {
+        if verifier.unassigned_vars.is_empty() {
+            return;
+        }
+        let target = match target.as_ref() {
+            Expression::Paren(e) => &e.expression,
+            _ => target,
+        };
+        if let Some(r) = verifier.host.node_mapping().get(target) {
+            if r.is::<ScopeReferenceValue>() {
+                let slot = r.property();
+                verifier.unassigned_vars.retain(|slot1| *slot1 != slot);
+            }
+        }
+    }
+
+    /// Marks the variables assigned by a destructuring pattern as assigned.
+    pub fn assign_pattern(verifier: &mut Subverifier, pattern: &Rc<Expression>) {
+        if verifier.unassigned_vars.is_empty() {
+            return;
+        }
+        let mut names = AssignedNames::new();
+        names.pattern(pattern);
+        verifier.unassigned_vars.retain(|slot| !names.contains(&slot.name().local_name()));
+    }
+
+    /// Reports reading a variable that is not definitely assigned.
+    pub fn verify_read(verifier: &mut Subverifier, r: &Entity, location: &Location) {
+        if verifier.unassigned_vars.is_empty() || !r.is::<ScopeReferenceValue>() {
+            return;
+        }
+        let slot = r.property();
+        if !verifier.unassigned_vars.contains(&slot) {
+            return;
+        }
+        // Variables may be assigned before a nested function is called.
+        if slot.parent().and_then(|p| p.search_activation()) != verifier.scope().search_activation() {
+            return;
+        }
+        verifier.add_verify_error(location, WhackDiagnosticKind::VariableNotDefinitelyAssigned, diagarg![slot.name().local_name()]);
+    }
+
+    /// Merges the unassigned variables of alternative paths.
+    pub fn merge_unassigned_vars(verifier: &mut Subverifier, other: &[Entity]) {
+        for slot in other.iter() {
+            if !verifier.unassigned_vars.contains(slot) {
+                verifier.unassigned_vars.push(slot.clone());
+            }
+        }
+    }
+
+    /// Records the unassigned variables at a `break` statement
+    /// and makes the current point unreachable.
+    pub fn break_to(verifier: &mut Subverifier, label: Option<&String>) {
+        let unassigned_vars = std::mem::take(&mut verifier.unassigned_vars);
+        let target = verifier.flow_break_targets.iter_mut().rev().find(|target| {
+            if let Some(label) = label {
+                target.labels.contains(label)
+            } else {
+                target.breakable
+            }
+        });
+        if let Some(target) = target {
+            for slot in unassigned_vars {
+                if !target.unassigned_vars.contains(&slot) {
+                    target.unassigned_vars.push(slot);
+                }
+            }
+        }
+    }
+
+    /// Records the unassigned variables at a `continue` statement
+    /// and makes the current point unreachable. A labeled `continue`
+    /// targets the loop that is the substatement of the labeled statement.
+    pub fn continue_to(verifier: &mut Subverifier, label: Option<&String>) {
+        let unassigned_vars = std::mem::take(&mut verifier.unassigned_vars);
+        let targets = &mut verifier.flow_break_targets;
+        let start = match label {
+            Some(label) => {
+                let Some(i) = targets.iter().rposition(|target| target.labels.contains(label)) else {
+                    return;
+                };
+                i + 1
+            },
+            None => 0,
+        };
+        let mut candidates = targets[start..].iter_mut().filter(|target| target.continuable);
+        let target = if label.is_some() { candidates.next() } else { candidates.last() };
+        if let Some(target) = target {
+            for slot in unassigned_vars {
+                if !target.continue_unassigned_vars.contains(&slot) {
+                    target.continue_unassigned_vars.push(slot);
+                }
+            }
+        }
+    }
+
+    /// Merges the unassigned variables at `continue` statements targeting
+    /// the innermost loop into the current point, which is the end
+    /// of the loop body.
+    pub fn merge_continue_states(verifier: &mut Subverifier) {
+        let unassigned_vars = std::mem::take(&mut verifier.flow_break_targets.last_mut().unwrap().continue_unassigned_vars);
+        Self::merge_unassigned_vars(verifier, &unassigned_vars);
+    }
+
+    pub fn enter_break_target(verifier: &mut Subverifier, labels: Vec<String>, breakable: bool) {
+        verifier.flow_break_targets.push(FlowBreakTarget {
+            labels,
+            breakable,
+            continuable: false,
+            unassigned_vars: vec![],
+            continue_unassigned_vars: vec![],
+        });
+    }
+
+    pub fn enter_loop(verifier: &mut Subverifier) {
+        verifier.flow_break_targets.push(FlowBreakTarget {
+            labels: vec![],
+            breakable: true,
+            continuable: true,
+            unassigned_vars: vec![],
+            continue_unassigned_vars: vec![],
+        });
+    }
+
+    /// Exits a `break` target, merging the unassigned variables
+    /// at `break` statements into the current point.
+    pub fn exit_break_target(verifier: &mut Subverifier) {
+        let target = verifier.flow_break_targets.pop().unwrap();
+        Self::merge_unassigned_vars(verifier, &target.unassigned_vars);
+    }
+}
+
+/// Local names assigned within a region, collected syntactically,
+/// including within nested functions.
+pub(crate) struct AssignedNames {
+    names: Vec<String>,
+}
+
+impl AssignedNames {
+    pub fn new() -> Self {
+        Self { names: vec![] }
+    }
+
+    pub fn of_directive(drtv: &Rc<Directive>) -> Self {
+        let mut r = Self::new();
+        r.directive(drtv);
+        r
+    }
+
+    pub fn of_directives(list: &[Rc<Directive>]) -> Self {
+        let mut r = Self::new();
+        r.directives(list);
+        r
+    }
+
+    pub fn of_exp(exp: &Rc<Expression>) -> Self {
+        let mut r = Self::new();
+        r.exp(exp);
+        r
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|name1| name1 == name)
+    }
+
+    fn directives(&mut self, list: &[Rc<Directive>]) {
+        for drtv in list.iter() {
+            self.directive(drtv);
+        }
+    }
+
+    fn directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ExpressionStatement(d) => self.exp(&d.expression),
+            Directive::SuperStatement(d) => self.exp_list(&d.arguments),
+            Directive::Block(d) => self.directives(&d.directives),
+            Directive::LabeledStatement(d) => self.directive(&d.substatement),
+            Directive::IfStatement(d) => {
+                self.exp(&d.test);
+                self.directive(&d.consequent);
+                if let Some(alternative) = d.alternative.as_ref() {
+                    self.directive(alternative);
+                }
+            },
+            Directive::SwitchStatement(d) => {
+                self.exp(&d.discriminant);
+                for case in d.cases.iter() {
+                    for label in case.labels.iter() {
+                        if let CaseLabel::Case((exp, _)) = label {
+                            self.exp(exp);
+                        }
+                    }
+                    self.directives(&case.directives);
+                }
+            },
+            Directive::SwitchTypeStatement(d) => {
+                self.exp(&d.discriminant);
+                for case in d.cases.iter() {
+                    self.directives(&case.block.directives);
+                }
+            },
+            Directive::DoStatement(d) => {
+                self.directive(&d.body);
+                self.exp(&d.test);
+            },
+            Directive::WhileStatement(d) => {
+                self.exp(&d.test);
+                self.directive(&d.body);
+            },
+            Directive::ForStatement(d) => {
+                match d.init.as_ref() {
+                    Some(ForInitializer::Expression(exp)) => self.exp(exp),
+                    Some(ForInitializer::VariableDefinition(defn)) => self.variable_bindings(&defn.bindings),
+                    None => {},
+                }
+                if let Some(test) = d.test.as_ref() {
+                    self.exp(test);
+                }
+                if let Some(update) = d.update.as_ref() {
+                    self.exp(update);
+                }
+                self.directive(&d.body);
+            },
+            Directive::ForInStatement(d) => {
+                match &d.left {
+                    ForInBinding::Expression(exp) => self.pattern(exp),
+                    ForInBinding::VariableDefinition(defn) => self.variable_bindings(&defn.bindings),
+                }
+                self.exp(&d.right);
+                self.directive(&d.body);
+            },
+            Directive::WithStatement(d) => {
+                self.exp(&d.object);
+                self.directive(&d.body);
+            },
+            Directive::ReturnStatement(d) => {
+                if let Some(exp) = d.expression.as_ref() {
+                    self.exp(exp);
+                }
+            },
+            Directive::ThrowStatement(d) => self.exp(&d.expression),
+            Directive::TryStatement(d) => {
+                self.directives(&d.block.directives);
+                for catch_clause in d.catch_clauses.iter() {
+                    self.directives(&catch_clause.block.directives);
+                }
+                if let Some(finally_clause) = d.finally_clause.as_ref() {
+                    self.directives(&finally_clause.block.directives);
+                }
+            },
+            Directive::ConfigurationDirective(d) => self.directive(&d.directive),
+            Directive::IncludeDirective(d) => self.directives(&d.nested_directives),
+            Directive::DirectiveInjection(d) => self.directives(&d.directives.borrow()),
+            Directive::VariableDefinition(d) => self.variable_bindings(&d.bindings),
+            Directive::FunctionDefinition(d) => self.function_common(&d.common),
+            _ => {},
+        }
+    }
+
+    fn variable_bindings(&mut self, list: &[Rc<VariableBinding>]) {
+        for binding in list.iter() {
+            if let Some(init) = binding.initializer.as_ref() {
+                self.exp(init);
+            }
+        }
+    }
+
+    fn function_common(&mut self, common: &Rc<FunctionCommon>) {
+        match common.body.as_ref() {
+            Some(FunctionBody::Block(block)) => self.directives(&block.directives),
+            Some(FunctionBody::Expression(exp)) => self.exp(exp),
+            None => {},
+        }
+    }
+
+    fn exp_list(&mut self, list: &[Rc<Expression>]) {
+        for exp in list.iter() {
+            self.exp(exp);
+        }
+    }
+
+    /// Collects the names assigned by an assignment target.
+    pub fn pattern(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(id) => {
+                if let (None, QualifiedIdentifierIdentifier::Id((name, _))) = (&id.qualifier, &id.id) {
+                    self.names.push(name.clone());
+                }
+            },
+            Expression::Paren(e) => self.pattern(&e.expression),
+            Expression::Unary(e) if e.operator == Operator::NonNull => self.pattern(&e.expression),
+            Expression::ArrayLiteral(e) => {
+                for element in e.elements.iter() {
+                    match element {
+                        Element::Expression(exp) => self.pattern(exp),
+                        Element::Rest((exp, _)) => self.pattern(exp),
+                        Element::Elision => {},
+                    }
+                }
+            },
+            Expression::ObjectInitializer(e) => {
+                for field in e.fields.iter() {
+                    match field.as_ref() {
+                        InitializerField::Field { name, value, .. } => {
+                            if let Some(value) = value.as_ref() {
+                                self.pattern(value);
+                            } else if let FieldName::Identifier(id) = &name.0 {
+                                if let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id {
+                                    self.names.push(name.clone());
+                                }
+                            }
+                        },
+                        InitializerField::Rest((exp, _)) => self.pattern(exp),
+                    }
+                }
+            },
+            _ => self.exp(exp),
+        }
+    }
+
+    fn exp(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Paren(e) => self.exp(&e.expression),
+            Expression::ArrayLiteral(e) => {
+                for element in e.elements.iter() {
+                    match element {
+                        Element::Expression(exp) => self.exp(exp),
+                        Element::Rest((exp, _)) => self.exp(exp),
+                        Element::Elision => {},
+                    }
+                }
+            },
+            Expression::VectorLiteral(e) => {
+                for element in e.elements.iter() {
+                    match element {
+                        Element::Expression(exp) => self.exp(exp),
+                        Element::Rest((exp, _)) => self.exp(exp),
+                        Element::Elision => {},
+                    }
+                }
+            },
+            Expression::ObjectInitializer(e) => {
+                for field in e.fields.iter() {
+                    match field.as_ref() {
+                        InitializerField::Field { name, value, .. } => {
+                            if let FieldName::Brackets(exp) = &name.0 {
+                                self.exp(exp);
+                            }
+                            if let Some(value) = value.as_ref() {
+                                self.exp(value);
+                            }
+                        },
+                        InitializerField::Rest((exp, _)) => self.exp(exp),
+                    }
+                }
+            },
+            Expression::Function(e) => self.function_common(&e.common),
+            Expression::New(e) => {
+                self.exp(&e.base);
+                if let Some(arguments) = e.arguments.as_ref() {
+                    self.exp_list(arguments);
+                }
+            },
+            Expression::Member(e) => self.exp(&e.base),
+            Expression::ComputedMember(e) => {
+                self.exp(&e.base);
+                self.exp(&e.key);
+            },
+            Expression::Descendants(e) => self.exp(&e.base),
+            Expression::Filter(e) => {
+                self.exp(&e.base);
+                self.exp(&e.test);
+            },
+            Expression::Super(e) => {
+                if let Some(object) = e.object.as_ref() {
+                    self.exp_list(object);
+                }
+            },
+            Expression::Call(e) => {
+                self.exp(&e.base);
+                self.exp_list(&e.arguments);
+            },
+            Expression::WithTypeArguments(e) => self.exp(&e.base),
+            Expression::Unary(e) => {
+                if [Operator::PreIncrement, Operator::PreDecrement, Operator::PostIncrement, Operator::PostDecrement].contains(&e.operator) {
+                    self.pattern(&e.expression);
+                } else {
+                    self.exp(&e.expression);
+                }
+            },
+            Expression::OptionalChaining(e) => {
+                self.exp(&e.base);
+                self.exp(&e.expression);
+            },
+            Expression::Binary(e) => {
+                self.exp(&e.left);
+                self.exp(&e.right);
+            },
+            Expression::Conditional(e) => {
+                self.exp(&e.test);
+                self.exp(&e.consequent);
+                self.exp(&e.alternative);
+            },
+            Expression::Assignment(e) => {
+                self.pattern(&e.left);
+                self.exp(&e.right);
+            },
+            Expression::Sequence(e) => {
+                self.exp(&e.left);
+                self.exp(&e.right);
+            },
+            _ => {},
+        }
+    }
+}
@@ -76,6 +76,11 @@ impl FunctionCommonSubverifier {
                 result_type = host.promise_type().defer()?.apply_type(&host, &host.promise_type().defer()?.type_params().unwrap(), &shared_array![host.invalidation_entity()])
             }
 
+            if common.contains_yield && result_type.iterator_element_type(&host).is_none() && !result_type.is::<InvalidationEntity>() {
+                verifier.add_verify_error(&name_span, WhackDiagnosticKind::ReturnTypeDeclarationMustBeGenerator, diagarg![]);
+                result_type = host.generator_type_of_any()?;
+            }
+
             let signature1 = host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), result_type);
             partials.set_signature(Some(signature1.clone()));
             signature = Some(signature1);
@@ -86,10 +91,6 @@ impl FunctionCommonSubverifier {
             method.set_signature(&signature);
         }
 
-        if signature.is_none() {
-            return Err(DeferError(None));
-        }
-
         // Resolve directives and then statements, or just the expression body.
         match &common.body {
             Some(FunctionBody::Block(block)) => {
@@ -98,7 +99,7 @@ impl FunctionCommonSubverifier {
                 });
                 verifier.inherit_and_enter_scope(&block_scope);
                 DirectiveSubverifier::verify_directives(verifier, &block.directives)?;
-                StatementSubverifier::verify_statements(verifier, &block.directives);
+                StatementSubverifier::verify_function_body_statements(verifier, &block.directives);
                 verifier.exit_scope();
             },
             Some(FunctionBody::Expression(exp)) => {
@@ -112,45 +113,134 @@ impl FunctionCommonSubverifier {
         }
 
         // Analyse the control flow (for block only).
+        let mut end_reachable = false;
         if let Some(FunctionBody::Block(block)) = &common.body {
-            ControlFlowAnalyser::analyse_directives(&block.directives, &activation.control_flow_graph(), &mut vec![], &[]);
+            end_reachable = ControlFlowAnalyser::analyse_function_body(verifier, &block.directives, &activation.control_flow_graph());
         }
 
-        // If the signature is fully resolved, ensure all code paths return a value.
-        // Result types that do not require a return value are
-        // `*`, `void`, `Promise.<*>`, and `Promise.<void>`.
-        if let Some(_signature) = partials.signature() {
-            ControlFlowAnalysisIsUnimplemented::unimplemented();
         // If the signature is not fully resolved due to unknown result type,
-        // collect the result value types returned from all code paths,
-        // ensure the result of all code paths implicitly coerce to the first code path's
-        // result's type, and construct the signature into the signature local.
-        //
-        // If the result type does not match a Promise for an asynchronous method,
-        // change it to Promise.<INVALIDATED> and report an error.
+        // infer the result type from the values returned from all code paths
+        // and construct the signature.
+        let signature = if let Some(signature) = signature {
+            signature
         } else {
-            let promise_type = host.promise_type().defer()?;
+            let signature = Self::infer_signature(verifier, common, partials)?;
+            partials.set_signature(Some(signature.clone()));
+            method.set_signature(&signature);
+            signature
+        };
+
+        // Ensure all code paths return a value.
+        // Result types that do not require a return value are
+        // `*`, `void`, `Promise.<*>`, and `Promise.<void>`.
+        if end_reachable {
+            Self::verify_all_code_paths_return(verifier, common, &method, &signature, &name_span);
+        }
 
-            // let mut result_type = Self::deduce_result_type(verifier, None);
-            verifier.add_warning(&name_span, WhackDiagnosticKind::ReturnTypeInferenceIsNotImplemented, diagarg![]);
-            let mut result_type = if common.contains_await { host.promise_type_of_any()? } else { host.any_type() };
+        Ok(())
+    }
 
+    /// Constructs the signature of a function whose result type is inferred
+    /// from its verified body.
+    ///
+    /// For an asynchronous method, the result type is wrapped into a Promise.
+    /// For a generator, the result type is a Generator of the yielded values.
+    fn infer_signature(verifier: &mut Subverifier, common: &Rc<FunctionCommon>, partials: &VerifierFunctionPartials) -> Result<Entity, DeferError> {
+        let host = verifier.host.clone();
+        let activation = partials.activation();
+        let result_type = if common.contains_yield {
+            let element_type = Self::infer_yield_type(verifier, &activation)?;
+            host.factory().create_type_after_substitution(&host.generator_type().defer()?, &shared_array![element_type])
+        } else {
+            let mut result_type = Self::infer_result_type(verifier, common, &activation)?;
             if common.contains_await {
-                if result_type.is::<InvalidationEntity>() {
-                    result_type = promise_type.apply_type(&host, &promise_type.type_params().unwrap(), &shared_array![host.invalidation_entity()]);
-                } else if result_type.promise_result_type(&host)?.is_none() {
-                    verifier.add_verify_error(&name_span, WhackDiagnosticKind::ReturnTypeDeclarationMustBePromise, diagarg![]);
-                    result_type = promise_type.apply_type(&host, &promise_type.type_params().unwrap(), &shared_array![host.invalidation_entity()]);
-                }
+                result_type = host.factory().create_type_after_substitution(&host.promise_type().defer()?, &shared_array![result_type]);
             }
+            result_type
+        };
+        Ok(host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), result_type))
+    }
 
-            signature = Some(host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), result_type));
+    /// Infers the result type of a function from the values returned
+    /// from all code paths, in the order they appear in the source text.
+    ///
+    /// The first returned value determines the result type; each subsequent value
+    /// must implicitly coerce to it, or else the result type is widened to
+    /// that value's type if the previous result type implicitly coerces to it.
+    /// `null` and `undefined` constants do not determine the result type.
+    ///
+    /// For an asynchronous method, the returned type is that of the Promise's result.
+    fn infer_result_type(verifier: &mut Subverifier, common: &Rc<FunctionCommon>, activation: &Entity) -> Result<Entity, DeferError> {
+        let host = verifier.host.clone();
+
+        let mut values: Vec<(Location, Option<Entity>)> = vec![];
+        match &common.body {
+            Some(FunctionBody::Block(_)) => {
+                for block in activation.control_flow_graph().blocks().iter() {
+                    for line in block.lines().iter() {
+                        if let Directive::ReturnStatement(retstmt) = line.as_ref() {
+                            let value = retstmt.expression.as_ref().map(|exp| host.node_mapping().get(exp).unwrap_or(host.invalidation_entity()));
+                            values.push((retstmt.location.clone(), value));
+                        }
+                    }
+                }
+                values.sort_by_key(|(loc, _)| loc.first_offset());
+            },
+            Some(FunctionBody::Expression(exp)) => {
+                values.push((exp.location(), Some(host.node_mapping().get(exp).unwrap_or(host.invalidation_entity()))));
+            },
+            None => {},
+        }
+
+        if values.iter().all(|(_, value)| value.is_none()) {
+            return Ok(host.void_type());
         }
 
-        // Set the activation method's signature to the last obtained signature. 
-        method.set_signature(&signature.unwrap());
+        Self::widen_value_types(verifier, &values)
+    }
 
-        Ok(())
+    /// Infers the type of the values yielded by a generator,
+    /// in the same way as result types are inferred.
+    fn infer_yield_type(verifier: &mut Subverifier, activation: &Entity) -> Result<Entity, DeferError> {
+        let mut values = verifier.yielded_values.remove(activation).unwrap_or_default().into_iter()
+            .map(|(loc, value)| (loc, Some(value)))
+            .collect::<Vec<_>>();
+        values.sort_by_key(|(loc, _)| loc.first_offset());
+        Self::widen_value_types(verifier, &values)
+    }
+
+    /// Determines the type of a sequence of values, where the first value determines
+    /// the type and subsequent values either coerce to it or widen it.
+    fn widen_value_types(verifier: &mut Subverifier, values: &[(Location, Option<Entity>)]) -> Result<Entity, DeferError> {
+        let host = verifier.host.clone();
+        let mut result_type: Option<Entity> = None;
+        for (loc, value) in values.iter() {
+            let Some(value) = value else {
+                verifier.add_verify_error(loc, WhackDiagnosticKind::ReturnValueMustBeSpecified, diagarg![]);
+                continue;
+            };
+            if value.is::<InvalidationEntity>() {
+                return Ok(host.invalidation_entity());
+            }
+            if value.is::<NullConstant>() || value.is::<UndefinedConstant>() {
+                continue;
+            }
+            let value_type = value.static_type(&host);
+            let Some(result_type_1) = result_type.clone() else {
+                result_type = Some(value_type);
+                continue;
+            };
+            if ConversionMethods(&host).implicit(value, &result_type_1, false)?.is_some() {
+                continue;
+            }
+            if ConversionMethods(&host).implicit(&host.factory().create_value(&result_type_1), &value_type, false)?.is_some() {
+                result_type = Some(value_type);
+            } else {
+                verifier.add_verify_error(loc, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![value_type, result_type_1]);
+            }
+        }
+
+        Ok(result_type.unwrap_or(host.any_type()))
     }
 
     
@@ -188,7 +278,7 @@ impl FunctionCommonSubverifier {
                     host.factory().create_scope()
                 });
                 verifier.inherit_and_enter_scope(&block_scope);
-                StatementSubverifier::verify_statements(verifier, &block.directives);
+                StatementSubverifier::verify_function_body_statements(verifier, &block.directives);
                 verifier.exit_scope();
             },
             Some(FunctionBody::Expression(exp)) => {
@@ -202,15 +292,125 @@ impl FunctionCommonSubverifier {
         }
 
         // Analyse the control flow (for block only).
+        let mut end_reachable = false;
         if let Some(FunctionBody::Block(block)) = &common.body {
-            ControlFlowAnalyser::analyse_directives(&block.directives, &activation.control_flow_graph(), &mut vec![], &[]);
+            end_reachable = ControlFlowAnalyser::analyse_function_body(verifier, &block.directives, &activation.control_flow_graph());
+        }
+
+        // Infer the result type if it was left to the function body.
+        let method = activation.of_method();
+        let mut signature = partials.signature();
+        if signature.is_none() && verifier.pending_result_types.contains_key(&method) {
+            let signature1 = Self::infer_signature(verifier, common, partials).unwrap_or_else(|_| {
+                verifier.add_verify_error(&partials.name_span(), WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
+                host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), host.invalidation_entity())
+            });
+            partials.set_signature(Some(signature1.clone()));
+            method.set_signature(&signature1);
+            verifier.pending_result_types.remove(&method);
+            signature = Some(signature1);
         }
 
         // Ensure all code paths return a value.
         // Result types that do not require a return value are
         // `*`, `void`, `Promise.<*>`, and `Promise.<void>`.
-        if let Some(_signature) = partials.signature() {
-            ControlFlowAnalysisIsUnimplemented::unimplemented();
+        if let Some(signature) = signature {
+            if end_reachable {
+                Self::verify_all_code_paths_return(verifier, common, &method, &signature, &partials.name_span());
+            }
+        }
+    }
+
+    /// Infers the result type of a referenced function definition pending
+    /// inference by verifying its body ahead of the other function bodies,
+    /// so that calls to it have a resolved signature.
+    ///
+    /// While directives are verified, function bodies may not be verified yet,
+    /// therefore the definition is resolved to the `*` result type instead.
+    pub fn resolve_pending_result_type(verifier: &mut Subverifier, reference: &Entity) -> Result<(), DeferError> {
+        if !reference.is::<FixtureReferenceValue>() {
+            return Ok(());
+        }
+        let method = reference.property();
+        if !verifier.pending_result_types.contains_key(&method) || verifier.inferring_result_types.contains(&method) {
+            return Ok(());
+        }
+        if verifier.function_bodies_ready {
+            Self::infer_pending_result_type(verifier, &method)
+        } else {
+            Self::resolve_pending_result_type_to_any(verifier, &method)
+        }
+    }
+
+    /// Verifies the body of a function definition pending result
+    /// type inference and infers its result type. If the body cannot
+    /// be verified, the result type is invalidated.
+    pub fn infer_pending_result_type(verifier: &mut Subverifier, method: &Entity) -> Result<(), DeferError> {
+        let host = verifier.host.clone();
+        let common = verifier.pending_result_types[method].clone();
+        let partials = verifier.function_definition_partials.get(&NodeAsKey(common.clone())).unwrap();
+        let kscope = verifier.scope.clone();
+
+        // Recursive calls see the `*` result type until the body is verified.
+        method.set_signature(&host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), host.any_type()));
+        verifier.inferring_result_types.push(method.clone());
+
+        let mut r = Err(DeferError(None));
+        for _ in 0..Verifier::MAX_CYCLES {
+            r = Self::verify_function_definition_common(verifier, &common, &partials);
+            if r.is_ok() {
+                break;
+            }
+        }
+        if r.is_ok() {
+            Self::verify_function_definition_common_statements_only(verifier, &common, &partials);
+            verifier.function_definition_partials.remove(&NodeAsKey(common.clone()));
+        } else {
+            method.set_signature(&host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), host.invalidation_entity()));
+            verifier.pending_result_types.remove(method);
+        }
+
+        verifier.inferring_result_types.pop();
+        verifier.scope = kscope;
+        r
+    }
+
+    fn resolve_pending_result_type_to_any(verifier: &mut Subverifier, method: &Entity) -> Result<(), DeferError> {
+        let host = verifier.host.clone();
+        let common = verifier.pending_result_types[method].clone();
+        let partials = verifier.function_definition_partials.get(&NodeAsKey(common.clone())).unwrap();
+        let result_type = if common.contains_await {
+            host.promise_type_of_any()?
+        } else if common.contains_yield {
+            host.generator_type_of_any()?
+        } else {
+            host.any_type()
+        };
+        verifier.add_warning(&partials.name_span(), WhackDiagnosticKind::ReturnValueHasNoTypeDeclaration, diagarg![]);
+        let signature = host.factory().create_function_type(partials.params().as_ref().unwrap().clone(), result_type.clone());
+        partials.set_result_type(Some(result_type));
+        partials.set_signature(Some(signature.clone()));
+        method.set_signature(&signature);
+        verifier.pending_result_types.remove(method);
+        Ok(())
+    }
+
+    /// Reports that not all code paths return a value, given that
+    /// the end of the function body is reachable.
+    fn verify_all_code_paths_return(verifier: &mut Subverifier, common: &Rc<FunctionCommon>, method: &Entity, signature: &Entity, name_span: &Location) {
+        let host = verifier.host.clone();
+        if common.contains_yield {
+            return;
+        }
+        let mut result_type = signature.result_type();
+        if method.is_async() {
+            if let Ok(Some(prom_result_type)) = result_type.promise_result_type(&host) {
+                result_type = prom_result_type;
+            }
+        }
+        if result_type.is::<InvalidationEntity>() || [host.any_type(), host.void_type()].contains(&result_type) {
+            return;
         }
+        verifier.add_verify_error(name_span, WhackDiagnosticKind::NotAllCodePathsReturnAValue, diagarg![]);
     }
 }
\ No newline at end of file
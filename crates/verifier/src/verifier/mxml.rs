@@ -0,0 +1,827 @@
+use crate::ns::*;
+
+/// The MXML 2009 language namespace.
+pub(crate) const MXML_2009_NS: &str = "http://ns.adobe.com/mxml/2009";
+
+/// A MXML document desugared into a class definition.
+pub(crate) struct MxmlDocument {
+    pub package_name: Vec<String>,
+    pub class_name: String,
+    pub root: Rc<MxmlElement>,
+    pub class_drtv: Rc<Directive>,
+    pub class_block: Rc<Block>,
+    /// Type expressions of component instances, including these
+    /// contributing a variable to the class.
+    pub type_expressions: RefCell<Vec<(Rc<MxmlElement>, Rc<Expression>)>>,
+}
+
+pub(crate) struct MxmlSubverifier;
+
+impl MxmlSubverifier {
+    /// Desugars a MXML document into a program consisting of a package
+    /// that defines the MXML component class.
+    ///
+    /// The package is determined from the path of the MXML source relative to
+    /// one of the `source_path` directories from the compiler options, and
+    /// the class is named after the MXML source file.
+    ///
+    /// The resulting class extends the class of the root tag, declares
+    /// a public variable for every `id` attribute, and contains the
+    /// directives from `<fx:Script>` tags and the meta-data from `<fx:Metadata>` tags.
+    pub fn desugar_document(verifier: &mut Subverifier, mxml: &Rc<Mxml>) -> Option<(Rc<Program>, MxmlDocument)> {
+        // Root element (the parser already reports documents without exactly one root element)
+        let root = mxml.content.iter().find_map(|content| {
+            if let MxmlContent::Element(e) = content.as_ref() { Some(e.clone()) } else { None }
+        })?;
+
+        let cu = mxml.location.compilation_unit();
+        let root_loc = root.name.location.clone();
+
+        // Package and class names
+        let (package_name, class_name) = match Self::qualified_name_of(&cu) {
+            Some(names) => names,
+            None => {
+                verifier.add_verify_error(&root_loc, WhackDiagnosticKind::MxmlSourceOutsideSourcePath, diagarg![]);
+                return None;
+            },
+        };
+        for name in package_name.iter().chain([&class_name]) {
+            if !Self::is_identifier(name) {
+                verifier.add_verify_error(&root_loc, WhackDiagnosticKind::MxmlSourceNameIsNotAnIdentifier, diagarg![name.clone()]);
+                return None;
+            }
+        }
+
+        let mut package_directives: Vec<Rc<Directive>> = vec![];
+        let mut class_directives: Vec<Rc<Directive>> = vec![];
+        let mut attributes: Vec<Attribute> = vec![Attribute::Public(root_loc.clone())];
+        let mut implements_clause: Option<Vec<Rc<Expression>>> = None;
+        let type_expressions: RefCell<Vec<(Rc<MxmlElement>, Rc<Expression>)>> = RefCell::new(vec![]);
+
+        // Import the packages used by tag names
+        let mut imported_packages: Vec<Vec<String>> = vec![];
+        Self::import_tag_packages(&root, &mut imported_packages, &mut package_directives);
+
+        // Extended class
+        let extends_clause = Self::tag_type_expression(verifier, &root);
+
+        // Implemented interfaces
+        for attr in root.attributes.iter() {
+            if !attr.xmlns && attr.name.prefix.is_none() && attr.name.name == "implements" {
+                let list = Self::attribute_value_segments(attr, ',').into_iter().map(|(first, last)| {
+                    ParserFacade(&cu, ParserOptions {
+                        byte_range: Some((first, last)),
+                        ..default()
+                    }).parse_type_expression()
+                }).collect::<Vec<_>>();
+                implements_clause = Some(list);
+            }
+        }
+
+        // Language tags directly under the root element
+        for child in Self::child_elements(&root) {
+            let Ok((uri, localname)) = child.name.resolve_name(&child.namespace) else {
+                continue;
+            };
+            if uri != MXML_2009_NS {
+                continue;
+            }
+            match localname.as_str() {
+                "Script" => {
+                    for (first, last) in Self::text_ranges(&child) {
+                        class_directives.extend(ParserFacade(&cu, ParserOptions {
+                            byte_range: Some((first, last)),
+                            ..default()
+                        }).parse_directives(ParserDirectiveContext::ClassBlock {
+                            name: class_name.clone(),
+                        }));
+                    }
+                },
+                "Metadata" => {
+                    for (first, last) in Self::text_ranges(&child) {
+                        let (metadata, _) = ParserFacade(&cu, ParserOptions {
+                            byte_range: Some((first, last)),
+                            ..default()
+                        }).parse_metadata();
+                        attributes.extend(metadata);
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        // Declare a public variable for every identified component instance
+        let mut identified: Vec<(Rc<MxmlElement>, Rc<MxmlAttribute>)> = vec![];
+        Self::collect_identified_elements(&root, &mut identified);
+        for (element, id_attr) in identified.iter() {
+            let Some(type_annotation) = Self::tag_type_expression(verifier, element) else {
+                continue;
+            };
+            type_expressions.borrow_mut().push((element.clone(), type_annotation.clone()));
+
+            let id_loc = Self::attribute_value_location(id_attr);
+            let id = Rc::new(Expression::QualifiedIdentifier(QualifiedIdentifier {
+                location: id_loc.clone(),
+                attribute: false,
+                qualifier: None,
+                id: QualifiedIdentifierIdentifier::Id((id_attr.value.0.clone(), id_loc.clone())),
+            }));
+            class_directives.push(Rc::new(Directive::VariableDefinition(VariableDefinition {
+                location: id_attr.location.clone(),
+                asdoc: None,
+                attributes: vec![Attribute::Public(id_attr.location.clone())],
+                kind: (VariableDefinitionKind::Var, id_attr.location.clone()),
+                bindings: vec![Rc::new(VariableBinding {
+                    destructuring: TypedDestructuring {
+                        location: id_loc.clone(),
+                        destructuring: id,
+                        type_annotation: Some(type_annotation),
+                    },
+                    initializer: None,
+                })],
+            })));
+        }
+
+        let class_block = Rc::new(Block {
+            location: root.location.clone(),
+            directives: class_directives,
+        });
+
+        let class_drtv = Rc::new(Directive::ClassDefinition(ClassDefinition {
+            location: root.location.clone(),
+            asdoc: None,
+            attributes,
+            name: (class_name.clone(), root_loc.clone()),
+            type_parameters: None,
+            extends_clause,
+            implements_clause,
+            block: class_block.clone(),
+        }));
+        package_directives.push(class_drtv.clone());
+
+        let program = Rc::new(Program {
+            location: mxml.location.clone(),
+            packages: vec![Rc::new(PackageDefinition {
+                location: root.location.clone(),
+                asdoc: None,
+                name: package_name.iter().map(|name| (name.clone(), root_loc.clone())).collect(),
+                block: Rc::new(Block {
+                    location: root.location.clone(),
+                    directives: package_directives,
+                }),
+            })],
+            directives: vec![],
+        });
+
+        Some((program, MxmlDocument {
+            package_name,
+            class_name,
+            root,
+            class_drtv,
+            class_block,
+            type_expressions,
+        }))
+    }
+
+    /// Returns the package name and class name of a MXML source
+    /// based in its path relative to the matching source path.
+    pub fn qualified_name_of(cu: &Rc<CompilationUnit>) -> Option<(Vec<String>, String)> {
+        let file_path = std::path::PathBuf::from(cu.file_path()?);
+        let file_path = file_path.canonicalize().unwrap_or(file_path);
+        for source_path in CompilerOptions::of(cu).source_path.iter() {
+            let Ok(relative) = file_path.strip_prefix(source_path) else {
+                continue;
+            };
+            let class_name = relative.file_stem()?.to_str()?.to_owned();
+            let mut package_name: Vec<String> = vec![];
+            if let Some(parent) = relative.parent() {
+                for component in parent.components() {
+                    package_name.push(component.as_os_str().to_str()?.to_owned());
+                }
+            }
+            return Some((package_name, class_name));
+        }
+        None
+    }
+
+    /// Declares the package of a MXML component and registers a placeholder
+    /// for its class, allowing ActionScript 3 code to import and reference
+    /// the component by name before the class definition is verified.
+    pub fn declare_class_placeholder(verifier: &mut Subverifier, document: &MxmlDocument) {
+        let host = verifier.host.clone();
+        let pckg = host.factory().create_package(document.package_name.iter().map(|name| name.as_str()));
+        let public_ns = pckg.public_ns().unwrap();
+        let name = host.factory().create_qname(&public_ns, document.class_name.clone());
+
+        // A conflicting definition is reported by the class definition.
+        if pckg.properties(&host).get(&name).is_some() {
+            return;
+        }
+
+        let class_entity = host.factory().create_class_type(name.clone(), &public_ns);
+        class_entity.set_parent(Some(pckg.clone()));
+        class_entity.set_location(Some(document.root.name.location.clone()));
+        class_entity.set_extends_class(Some(host.unresolved_entity()));
+        pckg.properties(&host).set(name, class_entity.clone());
+        host.node_mapping().set(&document.class_drtv, Some(class_entity));
+    }
+
+    /// Verifies attributes and child elements of a desugared MXML document.
+    /// This is done after the class definition has been verified.
+    pub fn verify_document(verifier: &mut Subverifier, document: &MxmlDocument) {
+        let host = verifier.host.clone();
+        let Some(class_entity) = host.node_mapping().get(&document.class_drtv) else {
+            return;
+        };
+        let Some(block_scope) = host.node_mapping().get(&document.class_block) else {
+            return;
+        };
+
+        // Save scope
+        let kscope = verifier.scope.clone();
+
+        // Enter an activation whose "this" is the MXML component.
+        let method = host.factory().create_method_slot(&host.empty_empty_qname(), &host.factory().create_function_type(vec![], host.void_type()));
+        let activation = host.factory().create_activation(&method);
+        activation.set_this(Some(host.factory().create_this_object(&class_entity)));
+        verifier.set_scope(&block_scope);
+        verifier.inherit_and_enter_scope(&activation);
+
+        Self::verify_instance(verifier, document, &document.root, &class_entity, true);
+
+        // Restore scope
+        verifier.scope = kscope;
+    }
+
+    /// Verifies a component instance element, returning its type.
+    fn verify_instance_element(verifier: &mut Subverifier, document: &MxmlDocument, element: &Rc<MxmlElement>) -> Option<Entity> {
+        let existing = document.type_expressions.borrow().iter().find(|(e, _)| Rc::ptr_eq(e, element)).map(|(_, t)| t.clone());
+        let type_exp = existing.or_else(|| Self::tag_type_expression(verifier, element))?;
+        let instance_type = match verifier.verify_type_expression(&type_exp) {
+            Ok(Some(t)) => t,
+            Ok(None) => return None,
+            Err(_) => {
+                verifier.add_verify_error(&element.name.location, WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
+                return None;
+            },
+        };
+        Self::verify_instance(verifier, document, element, &instance_type, false);
+        Some(instance_type)
+    }
+
+    fn verify_instance(verifier: &mut Subverifier, document: &MxmlDocument, element: &Rc<MxmlElement>, instance_type: &Entity, is_root: bool) {
+        let host = verifier.host.clone();
+
+        // Attributes
+        for attr in element.attributes.iter() {
+            if attr.xmlns {
+                continue;
+            }
+            if attr.name.prefix.is_some() {
+                verifier.add_verify_error(&attr.name.location, WhackDiagnosticKind::UnknownMxmlAttribute, diagarg![attr.name.to_string(&element.namespace), instance_type.clone()]);
+                continue;
+            }
+            let name = attr.name.name.clone();
+            if (is_root && name == "implements") || (!is_root && name == "id") {
+                continue;
+            }
+
+            // Property
+            if let Some(prop) = Self::lookup_property(verifier, instance_type, &name, &attr.name.location) {
+                if prop.is::<InvalidationEntity>() {
+                    continue;
+                }
+                if prop.read_only(&host) {
+                    verifier.add_verify_error(&attr.name.location, WhackDiagnosticKind::EntityIsReadOnly, diagarg![]);
+                    continue;
+                }
+                let prop_type = prop.static_type(&host);
+                Self::verify_attribute_value(verifier, document, attr, &prop_type);
+                continue;
+            }
+
+            // Event
+            if let Some(event) = Self::find_event(&host, instance_type, &name) {
+                let event_type = if event.data_type.is::<UnresolvedEntity>() { host.any_type() } else { event.data_type.clone() };
+                Self::verify_event_handler(verifier, attr, &event_type);
+                continue;
+            }
+
+            // Style
+            if Self::find_metadata_named(&host, instance_type, "Style", &name) {
+                continue;
+            }
+
+            verifier.add_verify_error(&attr.name.location, WhackDiagnosticKind::UnknownMxmlAttribute, diagarg![name, instance_type.clone()]);
+        }
+
+        // Child elements and text
+        let Some(content) = element.content.as_ref() else {
+            return;
+        };
+        let uri = element.name.resolve_prefix(&element.namespace).ok();
+        let mut default_children: Vec<Rc<MxmlElement>> = vec![];
+        let mut default_text: Option<(String, Location)> = None;
+        for c in content.iter() {
+            match c.as_ref() {
+                MxmlContent::Element(child) => {
+                    let Ok((child_uri, localname)) = child.name.resolve_name(&child.namespace) else {
+                        continue;
+                    };
+
+                    // Language tags
+                    if child_uri == MXML_2009_NS {
+                        match localname.as_str() {
+                            "Script" | "Metadata" | "Style" if is_root => {},
+                            "Declarations" if is_root => {
+                                for decl in Self::child_elements(child) {
+                                    Self::verify_instance_element(verifier, document, &decl);
+                                }
+                            },
+                            _ => {
+                                verifier.add_verify_error(&child.name.location, WhackDiagnosticKind::UnsupportedMxmlFeature, diagarg![format!("<{}>", localname)]);
+                            },
+                        }
+                        continue;
+                    }
+
+                    // Property element
+                    if Some(&child_uri) == uri.as_ref() {
+                        if let Some(prop) = Self::lookup_property(verifier, instance_type, &localname, &child.name.location) {
+                            if prop.is::<InvalidationEntity>() {
+                                continue;
+                            }
+                            if prop.read_only(&host) {
+                                verifier.add_verify_error(&child.name.location, WhackDiagnosticKind::EntityIsReadOnly, diagarg![]);
+                                continue;
+                            }
+                            let prop_type = prop.static_type(&host);
+                            let values = Self::child_elements(child);
+                            if values.is_empty() {
+                                let text = child.inner_text();
+                                let loc = child.content.as_ref().and_then(|c| c.first()).map(|c| c.location()).unwrap_or(child.name.location.clone());
+                                Self::verify_literal_value(verifier, &text, &loc, &prop_type);
+                            } else {
+                                Self::verify_value_elements(verifier, document, &values, &prop_type);
+                            }
+                            continue;
+                        }
+                    }
+
+                    default_children.push(child.clone());
+                },
+                MxmlContent::Characters((text, loc)) => {
+                    if !text.trim().is_empty() {
+                        default_text = Some((text.clone(), loc.clone()));
+                    }
+                },
+                MxmlContent::CData((_, loc)) => {
+                    default_text = Some((c.inner_text(), loc.clone()));
+                },
+                _ => {},
+            }
+        }
+
+        if default_children.is_empty() && default_text.is_none() {
+            return;
+        }
+
+        // Child elements and text are assigned to the [DefaultProperty]
+        let first_loc = default_children.first().map(|c| c.name.location.clone()).unwrap_or_else(|| default_text.as_ref().unwrap().1.clone());
+        let default_prop = Self::find_default_property(&host, instance_type)
+            .and_then(|name| Self::lookup_property(verifier, instance_type, &name, &first_loc));
+        let Some(default_prop) = default_prop else {
+            verifier.add_verify_error(&first_loc, WhackDiagnosticKind::MxmlChildrenNotAccepted, diagarg![instance_type.clone()]);
+            for child in default_children.iter() {
+                Self::verify_instance_element(verifier, document, child);
+            }
+            return;
+        };
+        if default_prop.is::<InvalidationEntity>() {
+            return;
+        }
+        let prop_type = default_prop.static_type(&host);
+        if default_children.is_empty() {
+            let (text, loc) = default_text.unwrap();
+            Self::verify_literal_value(verifier, &text, &loc, &prop_type);
+        } else {
+            Self::verify_value_elements(verifier, document, &default_children, &prop_type);
+        }
+    }
+
+    /// Verifies component instances assigned to a property. A single instance
+    /// must be implicitly convertible to the property type; several instances
+    /// require an `Array`, `Vector` or untyped property.
+    fn verify_value_elements(verifier: &mut Subverifier, document: &MxmlDocument, elements: &[Rc<MxmlElement>], target_type: &Entity) {
+        let host = verifier.host.clone();
+        let target_esc_type = target_type.escape_of_nullable_or_non_nullable();
+        let element_type = if elements.len() > 1 {
+            let array_element_type = target_esc_type.array_element_type(&host).ok().flatten();
+            let vector_element_type = target_esc_type.vector_element_type(&host).ok().flatten();
+            array_element_type.or(vector_element_type)
+        } else {
+            None
+        };
+        for element in elements.iter() {
+            let Some(instance_type) = Self::verify_instance_element(verifier, document, element) else {
+                continue;
+            };
+            let value_target_type = element_type.clone().unwrap_or(target_type.clone());
+            if elements.len() > 1 && element_type.is_none()
+            && target_esc_type != host.any_type() && target_esc_type != host.object_type() {
+                verifier.add_verify_error(&element.name.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![instance_type, target_type.clone()]);
+                continue;
+            }
+            let value = host.factory().create_value(&instance_type);
+            if let Ok(None) = ConversionMethods(&host).implicit(&value, &value_target_type, false) {
+                verifier.add_verify_error(&element.name.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![instance_type, value_target_type]);
+            }
+        }
+    }
+
+    /// Verifies an attribute value assigned to a property, which is either
+    /// a data binding (`{expression}`) or a literal.
+    fn verify_attribute_value(verifier: &mut Subverifier, document: &MxmlDocument, attr: &Rc<MxmlAttribute>, target_type: &Entity) {
+        let loc = Self::attribute_value_location(attr);
+        let cu = loc.compilation_unit();
+        let raw = &cu.text()[loc.first_offset()..loc.last_offset()];
+        let trimmed = raw.trim();
+        if trimmed.starts_with('{') && trimmed.ends_with('}') {
+            let first = loc.first_offset() + raw.find('{').unwrap() + 1;
+            let last = loc.first_offset() + raw.rfind('}').unwrap();
+            let exp = ParserFacade(&cu, ParserOptions {
+                byte_range: Some((first, last)),
+                ..default()
+            }).parse_expression();
+            if verifier.imp_coerce_exp_or_max_cycles_error(&exp, target_type).is_some() {
+                Self::verify_data_binding_sources(verifier, document, &exp);
+            }
+        } else {
+            Self::verify_literal_value(verifier, &attr.value.0, &loc, target_type);
+        }
+    }
+
+    /// Warns for properties referenced by a data binding expression
+    /// whose assignments cannot be detected, that is, writable properties
+    /// that are not `[Bindable]`. Variables of identified component
+    /// instances are assigned once and are not reported.
+    fn verify_data_binding_sources(verifier: &mut Subverifier, document: &MxmlDocument, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Paren(e) => {
+                Self::verify_data_binding_sources(verifier, document, &e.expression);
+                return;
+            },
+            Expression::Member(e) => {
+                Self::verify_data_binding_sources(verifier, document, &e.base);
+            },
+            Expression::QualifiedIdentifier(_) => {},
+            Expression::Call(e) => {
+                for arg in e.arguments.iter() {
+                    Self::verify_data_binding_sources(verifier, document, arg);
+                }
+                return;
+            },
+            Expression::Unary(e) => {
+                Self::verify_data_binding_sources(verifier, document, &e.expression);
+                return;
+            },
+            Expression::Binary(e) => {
+                Self::verify_data_binding_sources(verifier, document, &e.left);
+                Self::verify_data_binding_sources(verifier, document, &e.right);
+                return;
+            },
+            Expression::Conditional(e) => {
+                Self::verify_data_binding_sources(verifier, document, &e.test);
+                Self::verify_data_binding_sources(verifier, document, &e.consequent);
+                Self::verify_data_binding_sources(verifier, document, &e.alternative);
+                return;
+            },
+            _ => {
+                return;
+            },
+        }
+
+        let host = verifier.host.clone();
+        let Some(val) = verifier.node_mapping().get(exp) else {
+            return;
+        };
+        if !val.is::<FixtureReferenceValue>() {
+            return;
+        }
+        let prop = val.property();
+        let writable = (prop.is::<VariableSlot>() && !prop.read_only(&host))
+            || (prop.is::<VirtualSlot>() && prop.setter(&host).is_some());
+        if writable && prop.bindable_event().is_none() && !Self::is_component_variable(document, &prop) {
+            verifier.add_warning(&exp.location(), WhackDiagnosticKind::UnbindableDataBindingSource, diagarg![prop.name().local_name()]);
+        }
+    }
+
+    /// Indicates whether a slot is the variable declared
+    /// for an identified component instance.
+    fn is_component_variable(document: &MxmlDocument, slot: &Entity) -> bool {
+        let Some(loc) = slot.location() else {
+            return false;
+        };
+        document.type_expressions.borrow().iter().any(|(element, _)| {
+            element.attributes.iter().any(|attr| !attr.xmlns && attr.name.prefix.is_none() && attr.name.name == "id" && Self::attribute_value_location(attr) == loc)
+        })
+    }
+
+    /// Verifies a literal value against a `String`, `Boolean`, numeric or untyped target.
+    fn verify_literal_value(verifier: &mut Subverifier, value: &str, location: &Location, target_type: &Entity) {
+        let host = verifier.host.clone();
+        let t = target_type.escape_of_nullable_or_non_nullable();
+        if t.is::<InvalidationEntity>() || t == host.any_type() || t == host.object_type() || t == host.string_type() {
+            return;
+        }
+        let value = value.trim();
+        let valid = if t == host.boolean_type() {
+            value == "true" || value == "false"
+        } else if t == host.int_type() || t == host.uint_type() {
+            Self::parse_numeric_literal(value).map(|n| n.fract() == 0.0 && (t != host.uint_type() || n >= 0.0)).unwrap_or(false)
+        } else if t == host.number_type() || t == host.float_type() {
+            Self::parse_numeric_literal(value).is_some()
+        } else {
+            false
+        };
+        if !valid {
+            verifier.add_verify_error(location, WhackDiagnosticKind::MxmlValueNotConvertible, diagarg![value.to_owned(), target_type.clone()]);
+        }
+    }
+
+    /// Parses a numeric MXML literal, including hexadecimal
+    /// (`0xFF`) and color (`#RRGGBB`) forms.
+    fn parse_numeric_literal(value: &str) -> Option<f64> {
+        use std::str::FromStr;
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(v) => (true, v),
+            None => (false, value),
+        };
+        let n = if let Some(hex) = value.strip_prefix("0x").or(value.strip_prefix("0X")).or(value.strip_prefix('#')) {
+            u64::from_str_radix(hex, 16).ok()? as f64
+        } else if value == "NaN" || value.is_empty() || value.contains(|ch: char| ch.is_ascii_alphabetic() && ch != 'e' && ch != 'E') {
+            return if value == "NaN" { Some(f64::NAN) } else { None };
+        } else {
+            f64::from_str(value).ok()?
+        };
+        Some(if negative { -n } else { n })
+    }
+
+    /// Verifies an event handler attribute. The attribute value consists of
+    /// directives evaluated with an `event` local of the event's type.
+    fn verify_event_handler(verifier: &mut Subverifier, attr: &Rc<MxmlAttribute>, event_type: &Entity) {
+        let host = verifier.host.clone();
+        let loc = Self::attribute_value_location(attr);
+        let cu = loc.compilation_unit();
+        let directives = ParserFacade(&cu, ParserOptions {
+            byte_range: Some((loc.first_offset(), loc.last_offset())),
+            ..default()
+        }).parse_directives(ParserDirectiveContext::Default);
+
+        let this = verifier.scope().search_activation().and_then(|act| act.this());
+        let internal_ns = verifier.scope().search_system_ns_in_scope_chain(SystemNamespaceKind::Internal).unwrap();
+
+        // Handler activation
+        let params = vec![Rc::new(SemanticFunctionTypeParameter {
+            kind: ParameterKind::Required,
+            static_type: event_type.clone(),
+        })];
+        let method = host.factory().create_method_slot(&host.empty_empty_qname(), &host.factory().create_function_type(params, host.void_type()));
+        let activation = host.factory().create_activation(&method);
+        activation.set_this(this);
+        let event_name = host.factory().create_qname(&internal_ns, "event".to_owned());
+        let event_slot = host.factory().create_variable_slot(&event_name, false, event_type);
+        activation.properties(&host).set(event_name, event_slot);
+
+        // Save scope
+        let kscope = verifier.scope();
+        verifier.set_scope(&kscope.parent().unwrap_or(kscope.clone()));
+        verifier.inherit_and_enter_scope(&activation);
+
+        let mut any_defer = false;
+        for _ in 0..Verifier::MAX_CYCLES {
+            any_defer = DirectiveSubverifier::verify_directives(verifier, &directives).is_err();
+            if !any_defer {
+                break;
+            }
+        }
+        if any_defer {
+            verifier.add_verify_error(&loc, WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
+        }
+        StatementSubverifier::verify_statements(verifier, &directives);
+
+        // Restore scope
+        verifier.set_scope(&kscope);
+    }
+
+    fn lookup_property(verifier: &mut Subverifier, instance_type: &Entity, name: &str, location: &Location) -> Option<Entity> {
+        let host = verifier.host.clone();
+        let base = host.factory().create_value(instance_type);
+        let open_ns_set = verifier.scope().concat_open_ns_set_of_scope_chain();
+        match PropertyLookup(&host).lookup_in_object(&base, &open_ns_set, None, &PropertyLookupKey::LocalName(name.to_owned()), false) {
+            Ok(Some(r)) => {
+                if r.is::<InstanceReferenceValue>() && r.property().is::<MethodSlot>() {
+                    return None;
+                }
+                Some(r)
+            },
+            Ok(None) => None,
+            Err(PropertyLookupError::AmbiguousReference(name)) => {
+                verifier.add_verify_error(location, WhackDiagnosticKind::AmbiguousReference, diagarg![name.clone()]);
+                Some(host.invalidation_entity())
+            },
+            Err(_) => None,
+        }
+    }
+
+    fn find_event(host: &Database, instance_type: &Entity, name: &str) -> Option<Event> {
+        for class in instance_type.escape_of_nullable_or_non_nullable().descending_class_hierarchy(host) {
+            if !class.is::<ClassType>() {
+                continue;
+            }
+            if let Some(event) = class.events().get(&name.to_owned()) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Finds the value of the `[DefaultProperty("name")]` meta-data
+    /// across the class hierarchy.
+    fn find_default_property(host: &Database, instance_type: &Entity) -> Option<String> {
+        for class in instance_type.escape_of_nullable_or_non_nullable().descending_class_hierarchy(host) {
+            if !class.is::<ClassType>() {
+                continue;
+            }
+            for m in class.metadata().iter() {
+                if m.name.0 != "DefaultProperty" {
+                    continue;
+                }
+                if let Some(entry) = m.entries.as_ref().and_then(|entries| entries.first()) {
+                    if entry.key.is_none() {
+                        return Some(Self::metadata_value(&entry.value));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Determines whether a meta-data such as `[Style(name="color")]` with
+    /// a given `name` entry exists across the class hierarchy.
+    fn find_metadata_named(host: &Database, instance_type: &Entity, metadata_name: &str, name: &str) -> bool {
+        for class in instance_type.escape_of_nullable_or_non_nullable().descending_class_hierarchy(host) {
+            if !class.is::<ClassType>() {
+                continue;
+            }
+            for m in class.metadata().iter() {
+                if m.name.0 != metadata_name {
+                    continue;
+                }
+                for entry in m.entries.iter().flatten() {
+                    if entry.key.as_ref().map(|k| k.0 == "name").unwrap_or(false) && Self::metadata_value(&entry.value) == name {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn metadata_value(value: &MetadataValue) -> String {
+        match value {
+            MetadataValue::String(v) => v.0.clone(),
+            MetadataValue::IdentifierString(v) => v.0.clone(),
+        }
+    }
+
+    /// Builds a type expression for a tag name. The namespace URI of
+    /// the tag is of the form `"*"` for the top-level package or
+    /// `"q.b.*"` for any other package.
+    fn tag_type_expression(verifier: &mut Subverifier, element: &Rc<MxmlElement>) -> Option<Rc<Expression>> {
+        let loc = element.name.location.clone();
+        let uri = element.name.resolve_prefix(&element.namespace).ok()?;
+        let Some(package_name) = Self::package_name_of_uri(&uri) else {
+            verifier.add_verify_error(&loc, WhackDiagnosticKind::UnknownMxmlNamespace, diagarg![uri]);
+            return None;
+        };
+        let mut names = package_name;
+        names.push(element.name.name.clone());
+        let id = |name: &String| QualifiedIdentifier {
+            location: loc.clone(),
+            attribute: false,
+            qualifier: None,
+            id: QualifiedIdentifierIdentifier::Id((name.clone(), loc.clone())),
+        };
+        let mut exp = Rc::new(Expression::QualifiedIdentifier(id(&names[0])));
+        for name in names[1..].iter() {
+            exp = Rc::new(Expression::Member(MemberExpression {
+                location: loc.clone(),
+                base: exp,
+                identifier: id(name),
+            }));
+        }
+        Some(exp)
+    }
+
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars.next().map(|ch| ch.is_alphabetic() || ch == '_' || ch == '$').unwrap_or(false)
+            && chars.all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+    }
+
+    fn package_name_of_uri(uri: &str) -> Option<Vec<String>> {
+        if uri == "*" {
+            return Some(vec![]);
+        }
+        let name = uri.strip_suffix(".*")?;
+        Some(name.split('.').map(|s| s.to_owned()).collect())
+    }
+
+    /// Contributes wildcard imports for the packages used by tag names,
+    /// so that their fully qualified names resolve.
+    fn import_tag_packages(element: &Rc<MxmlElement>, imported: &mut Vec<Vec<String>>, out: &mut Vec<Rc<Directive>>) {
+        if let Ok(uri) = element.name.resolve_prefix(&element.namespace) {
+            if let Some(package_name) = Self::package_name_of_uri(&uri) {
+                if !package_name.is_empty() && !imported.contains(&package_name) {
+                    let loc = element.name.location.clone();
+                    out.push(Rc::new(Directive::ImportDirective(ImportDirective {
+                        location: loc.clone(),
+                        alias: None,
+                        package_name: package_name.iter().map(|name| (name.clone(), loc.clone())).collect(),
+                        import_specifier: ImportSpecifier::Wildcard(loc.clone()),
+                    })));
+                    imported.push(package_name);
+                }
+            }
+        }
+        for child in Self::child_elements(element) {
+            Self::import_tag_packages(&child, imported, out);
+        }
+    }
+
+    fn collect_identified_elements(element: &Rc<MxmlElement>, out: &mut Vec<(Rc<MxmlElement>, Rc<MxmlAttribute>)>) {
+        for child in Self::child_elements(element) {
+            if child.name.resolve_prefix(&child.namespace).map(|uri| uri == MXML_2009_NS).unwrap_or(false) {
+                // Only <fx:Declarations> contains component instances
+                if child.name.name == "Declarations" {
+                    Self::collect_identified_elements(&child, out);
+                }
+                continue;
+            }
+            let id_attr = child.attributes.iter().find(|attr| !attr.xmlns && attr.name.prefix.is_none() && attr.name.name == "id");
+            if let Some(id_attr) = id_attr {
+                out.push((child.clone(), id_attr.clone()));
+            }
+            Self::collect_identified_elements(&child, out);
+        }
+    }
+
+    fn child_elements(element: &Rc<MxmlElement>) -> Vec<Rc<MxmlElement>> {
+        element.content.iter().flatten().filter_map(|c| {
+            if let MxmlContent::Element(e) = c.as_ref() { Some(e.clone()) } else { None }
+        }).collect()
+    }
+
+    /// Returns the byte ranges of the character and CDATA content of an element.
+    fn text_ranges(element: &Rc<MxmlElement>) -> Vec<(usize, usize)> {
+        let mut r = vec![];
+        for c in element.content.iter().flatten() {
+            match c.as_ref() {
+                MxmlContent::Characters((_, loc)) => {
+                    r.push((loc.first_offset(), loc.last_offset()));
+                },
+                MxmlContent::CData((_, loc)) => {
+                    r.push((loc.first_offset() + "<![CDATA[".len(), loc.last_offset() - "]]>".len()));
+                },
+                _ => {},
+            }
+        }
+        r
+    }
+
+    /// Returns the location of an attribute value excluding the quotes.
+    fn attribute_value_location(attr: &Rc<MxmlAttribute>) -> Location {
+        let loc = &attr.value.1;
+        Location::with_offsets(&loc.compilation_unit(), loc.first_offset() + 1, loc.last_offset() - 1)
+    }
+
+    /// Returns the byte ranges of the trimmed segments of an attribute value
+    /// delimited by a character.
+    fn attribute_value_segments(attr: &Rc<MxmlAttribute>, delimiter: char) -> Vec<(usize, usize)> {
+        let loc = Self::attribute_value_location(attr);
+        let cu = loc.compilation_unit();
+        let raw = &cu.text()[loc.first_offset()..loc.last_offset()];
+        let mut r = vec![];
+        let mut i = loc.first_offset();
+        for segment in raw.split(delimiter) {
+            let leading = segment.len() - segment.trim_start().len();
+            let trimmed = segment.trim();
+            if !trimmed.is_empty() {
+                r.push((i + leading, i + leading + trimmed.len()));
+            }
+            i += segment.len() + delimiter.len_utf8();
+        }
+        r
+    }
+}
@@ -4,9 +4,30 @@ pub(crate) struct StatementSubverifier;
 
 impl StatementSubverifier {
     pub fn verify_statements(verifier: &mut Subverifier, list: &[Rc<Directive>]) {
-        for stmt in list.iter() {
+        let narrowings_len = verifier.flow_narrowings.len();
+        for (i, stmt) in list.iter().enumerate() {
             Self::verify_statement(verifier, stmt);
+            FlowSubverifier::enter_early_exit_narrowings(verifier, list, i);
         }
+        FlowSubverifier::exit_narrowings(verifier, narrowings_len);
+    }
+
+    /// Verifies the statements of a function body, which may be nested
+    /// in a statement of another function.
+    pub fn verify_function_body_statements(verifier: &mut Subverifier, list: &[Rc<Directive>]) {
+        let unassigned_vars = std::mem::take(&mut verifier.unassigned_vars);
+        let break_targets = std::mem::take(&mut verifier.flow_break_targets);
+        Self::verify_statements(verifier, list);
+        verifier.unassigned_vars = unassigned_vars;
+        verifier.flow_break_targets = break_targets;
+    }
+
+    /// Verifies a statement executed only where `test` evaluates to `truthy`.
+    fn verify_guarded_statement(verifier: &mut Subverifier, test: &Rc<Expression>, truthy: bool, stmt: &Rc<Directive>) {
+        let narrowings = FlowSubverifier::narrowings(verifier, test, truthy);
+        let len = FlowSubverifier::enter_narrowings(verifier, narrowings, &AssignedNames::of_directive(stmt));
+        Self::verify_statement(verifier, stmt);
+        FlowSubverifier::exit_narrowings(verifier, len);
     }
 
     pub fn verify_statement(verifier: &mut Subverifier, stmt: &Rc<Directive>) {
@@ -24,19 +45,35 @@ impl StatementSubverifier {
                 verifier.exit_scope();
             },
             Directive::LabeledStatement(labstmt) => {
+                // Labels must be unique within enclosing labeled statements. Undefined labels
+                // and illegal break and continue statements are reported by the parser.
+                let (label, label_loc) = &labstmt.label;
+                if verifier.flow_break_targets.iter().any(|target| target.labels.contains(label)) {
+                    verifier.add_verify_error(label_loc, WhackDiagnosticKind::DuplicateLabel, diagarg![label.clone()]);
+                }
+                FlowSubverifier::enter_break_target(verifier, vec![labstmt.label.0.clone()], false);
                 Self::verify_statement(verifier, &labstmt.substatement);
+                FlowSubverifier::exit_break_target(verifier);
             },
             Directive::IfStatement(ifstmt) => {
-                verifier.verify_expression_or_max_cycles_error(&ifstmt.test, &Default::default());
-                Self::verify_statement(verifier, &ifstmt.consequent);
+                Self::verify_condition(verifier, &ifstmt.test);
+                let unassigned_vars = verifier.unassigned_vars.clone();
+                Self::verify_guarded_statement(verifier, &ifstmt.test, true, &ifstmt.consequent);
                 if let Some(alt) = ifstmt.alternative.as_ref() {
-                    Self::verify_statement(verifier, alt);
+                    let conseq_unassigned_vars = std::mem::replace(&mut verifier.unassigned_vars, unassigned_vars);
+                    Self::verify_guarded_statement(verifier, &ifstmt.test, false, alt);
+                    FlowSubverifier::merge_unassigned_vars(verifier, &conseq_unassigned_vars);
+                } else {
+                    FlowSubverifier::merge_unassigned_vars(verifier, &unassigned_vars);
                 }
             },
             Directive::SwitchStatement(swstmt) => {
                 let host = verifier.host.clone();
                 let discriminant = verifier.verify_expression_or_max_cycles_error(&swstmt.discriminant, &Default::default());
+                let unassigned_vars = verifier.unassigned_vars.clone();
+                FlowSubverifier::enter_break_target(verifier, vec![], true);
                 for case in swstmt.cases.iter() {
+                    FlowSubverifier::merge_unassigned_vars(verifier, &unassigned_vars);
                     for label in case.labels.iter() {
                         match label {
                             CaseLabel::Case((exp, _)) => {
@@ -51,35 +88,81 @@ impl StatementSubverifier {
                     }
                     Self::verify_statements(verifier, &case.directives);
                 }
+                if !swstmt.cases.iter().any(|case| case.labels.iter().any(|label| matches!(label, CaseLabel::Default(_)))) {
+                    FlowSubverifier::merge_unassigned_vars(verifier, &unassigned_vars);
+                }
+                FlowSubverifier::exit_break_target(verifier);
             },
             Directive::SwitchTypeStatement(swstmt) => {
-                verifier.verify_expression_or_max_cycles_error(&swstmt.discriminant, &Default::default());
+                let discriminant = verifier.verify_expression_or_max_cycles_error(&swstmt.discriminant, &Default::default());
+                let unassigned_vars = verifier.unassigned_vars.clone();
+                let mut cases_unassigned_vars = vec![];
+                FlowSubverifier::enter_break_target(verifier, vec![], true);
                 for case in swstmt.cases.iter() {
+                    if let (Some(discriminant), Some(parameter)) = (discriminant.as_ref(), case.parameter.as_ref()) {
+                        Self::verify_switch_type_case(verifier, discriminant, parameter);
+                    }
+                    verifier.unassigned_vars = unassigned_vars.clone();
                     Self::verify_block(verifier, &case.block);
+                    cases_unassigned_vars.extend(std::mem::take(&mut verifier.unassigned_vars));
                 }
+                FlowSubverifier::merge_unassigned_vars(verifier, &cases_unassigned_vars);
+                if !swstmt.cases.iter().any(|case| case.parameter.is_none()) {
+                    FlowSubverifier::merge_unassigned_vars(verifier, &unassigned_vars);
+                }
+                FlowSubverifier::exit_break_target(verifier);
             },
             Directive::DoStatement(dostmt) => {
+                FlowSubverifier::enter_loop(verifier);
                 Self::verify_statement(verifier, &dostmt.body);
-                verifier.verify_expression_or_max_cycles_error(&dostmt.test, &Default::default());
+                FlowSubverifier::merge_continue_states(verifier);
+                Self::verify_condition(verifier, &dostmt.test);
+                // A loop whose test is always true exits only through `break`.
+                if Self::is_constant_true(verifier, &dostmt.test) {
+                    verifier.unassigned_vars.clear();
+                }
+                FlowSubverifier::exit_break_target(verifier);
             },
             Directive::WhileStatement(wstmt) => {
-                verifier.verify_expression_or_max_cycles_error(&wstmt.test, &Default::default());
-                Self::verify_statement(verifier, &wstmt.body);
+                Self::verify_condition(verifier, &wstmt.test);
+                let unassigned_vars = verifier.unassigned_vars.clone();
+                FlowSubverifier::enter_loop(verifier);
+                Self::verify_guarded_statement(verifier, &wstmt.test, true, &wstmt.body);
+                verifier.unassigned_vars = if Self::is_constant_true(verifier, &wstmt.test) { vec![] } else { unassigned_vars };
+                FlowSubverifier::exit_break_target(verifier);
             },
             Directive::ForStatement(forstmt) => {
                 let host = verifier.host.clone();
                 let scope = host.node_mapping().get(&stmt).unwrap();
                 verifier.inherit_and_enter_scope(&scope);
-                if let Some(ForInitializer::Expression(init)) = forstmt.init.as_ref() {
-                    verifier.verify_expression_or_max_cycles_error(&init, &Default::default());
+                match forstmt.init.as_ref() {
+                    Some(ForInitializer::Expression(init)) => {
+                        verifier.verify_expression_or_max_cycles_error(&init, &Default::default());
+                    },
+                    Some(ForInitializer::VariableDefinition(defn)) => {
+                        FlowSubverifier::declare_unassigned_vars(verifier, &defn.bindings);
+                    },
+                    None => {},
                 }
                 if let Some(test) = forstmt.test.as_ref() {
-                    verifier.verify_expression_or_max_cycles_error(&test, &Default::default());
+                    Self::verify_condition(verifier, test);
                 }
+                let unassigned_vars = verifier.unassigned_vars.clone();
+                FlowSubverifier::enter_loop(verifier);
+                if let Some(test) = forstmt.test.as_ref() {
+                    Self::verify_guarded_statement(verifier, test, true, &forstmt.body);
+                } else {
+                    Self::verify_statement(verifier, &forstmt.body);
+                }
+                FlowSubverifier::merge_continue_states(verifier);
                 if let Some(update) = forstmt.update.as_ref() {
                     verifier.verify_expression_or_max_cycles_error(&update, &Default::default());
                 }
-                Self::verify_statement(verifier, &forstmt.body);
+                // A loop without a test, or whose test is always true,
+                // exits only through `break`.
+                let exits_through_test = forstmt.test.as_ref().map(|test| !Self::is_constant_true(verifier, test)).unwrap_or(false);
+                verifier.unassigned_vars = if exits_through_test { unassigned_vars } else { vec![] };
+                FlowSubverifier::exit_break_target(verifier);
                 verifier.exit_scope();
             },
             Directive::ForInStatement(forstmt) => {
@@ -101,22 +184,36 @@ impl StatementSubverifier {
             },
             Directive::ReturnStatement(retstmt) => {
                 Self::verify_return_stmt(verifier, stmt, retstmt);
+                verifier.unassigned_vars.clear();
             },
             Directive::ThrowStatement(tstmt) => {
                 verifier.verify_expression_or_max_cycles_error(&tstmt.expression, &Default::default());
+                verifier.unassigned_vars.clear();
+            },
+            Directive::BreakStatement(brkstmt) => {
+                FlowSubverifier::break_to(verifier, brkstmt.label.as_ref().map(|label| &label.0));
             },
-            Directive::DefaultXmlNamespaceStatement(dxns) => {
-                verifier.add_verify_error(&dxns.location, WhackDiagnosticKind::DxnsStatementIsNotSupported, diagarg![]);
-                verifier.verify_expression_or_max_cycles_error(&dxns.right, &Default::default());
+            Directive::ContinueStatement(contstmt) => {
+                FlowSubverifier::continue_to(verifier, contstmt.label.as_ref().map(|label| &label.0));
             },
             Directive::TryStatement(trystmt) => {
+                let unassigned_vars = verifier.unassigned_vars.clone();
                 Self::verify_block(verifier, &trystmt.block);
+                let mut result_unassigned_vars = std::mem::take(&mut verifier.unassigned_vars);
                 for catch_clause in trystmt.catch_clauses.iter() {
+                    verifier.unassigned_vars = unassigned_vars.clone();
                     Self::verify_block(verifier, &catch_clause.block);
+                    result_unassigned_vars.extend(std::mem::take(&mut verifier.unassigned_vars));
                 }
                 if let Some(finally_clause) = trystmt.finally_clause.as_ref() {
+                    // Variables assigned by the finally block are assigned
+                    // after the try statement.
+                    verifier.unassigned_vars = unassigned_vars;
                     Self::verify_block(verifier, &finally_clause.block);
+                    let finally_unassigned_vars = std::mem::take(&mut verifier.unassigned_vars);
+                    result_unassigned_vars.retain(|slot| finally_unassigned_vars.contains(slot));
                 }
+                FlowSubverifier::merge_unassigned_vars(verifier, &result_unassigned_vars);
             },
             Directive::ConfigurationDirective(cfgdrtv) => {
                 let cval = verifier.host.node_mapping().get(stmt).unwrap();
@@ -142,10 +239,64 @@ impl StatementSubverifier {
             Directive::EnumDefinition(defn) => {
                 Self::verify_block(verifier, &defn.block);
             },
+            Directive::VariableDefinition(defn) => {
+                FlowSubverifier::declare_unassigned_vars(verifier, &defn.bindings);
+            },
             _ => {},
         }
     }
 
+    /// Verifies the test of an `if` statement or loop.
+    fn verify_condition(verifier: &mut Subverifier, test: &Rc<Expression>) {
+        let host = verifier.host.clone();
+        let Some(val) = verifier.verify_expression_or_max_cycles_error(test, &Default::default()) else {
+            return;
+        };
+        let st = val.static_type(&host).escape_of_nullable_or_non_nullable();
+        if st == host.void_type() {
+            verifier.add_warning(&test.location(), WhackDiagnosticKind::VoidCondition, diagarg![]);
+        } else if val.is::<Constant>() && !val.is::<BooleanConstant>() {
+            // Constant Boolean conditions are reported by the control flow analysis.
+            verifier.add_warning(&test.location(), WhackDiagnosticKind::NonBooleanConstantCondition, diagarg![]);
+        } else if let Ok(Some(_)) = st.promise_result_type(&host) {
+            verifier.add_warning(&test.location(), WhackDiagnosticKind::UnawaitedPromiseCondition, diagarg![st]);
+        }
+    }
+
+    /// Whether a verified condition is the constant `true`.
+    fn is_constant_true(verifier: &Subverifier, test: &Rc<Expression>) -> bool {
+        verifier.host.node_mapping().get(test)
+            .map(|val| val.is::<BooleanConstant>() && val.boolean_value())
+            .unwrap_or(false)
+    }
+
+    /// Ensures a `switch type` case may match the discriminant.
+    fn verify_switch_type_case(verifier: &mut Subverifier, discriminant: &Entity, parameter: &TypedDestructuring) {
+        let host = verifier.host.clone();
+        let Some(type_annot) = parameter.type_annotation.as_ref() else {
+            return;
+        };
+        let Some(t) = host.node_mapping().get(type_annot).and_then(|t| t.as_type()) else {
+            return;
+        };
+        let st = discriminant.static_type(&host);
+
+        // A value of a class type may implement any interface through a subclass.
+        if t.escape_of_nullable_or_non_nullable().is::<InterfaceType>() || st.escape_of_nullable_or_non_nullable().is::<InterfaceType>() {
+            return;
+        }
+
+        match st.is_comparison_between_unrelated_types(&t, &host) {
+            Ok(true) => {
+                verifier.add_warning(&type_annot.location(), WhackDiagnosticKind::UnrelatedSwitchTypeCase, diagarg![t, st]);
+            },
+            Ok(false) => {},
+            Err(_) => {
+                verifier.add_verify_error(&type_annot.location(), WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
+            },
+        }
+    }
+
     pub fn verify_block(verifier: &mut Subverifier, block: &Rc<Block>) {
         let scope = verifier.host.lazy_node_mapping(block, || {
             verifier.host.factory().create_scope()
@@ -173,7 +324,7 @@ impl StatementSubverifier {
         }
         let sig = act.of_method().signature(&host);
 
-        if sig.is::<UnresolvedEntity>() {
+        if verifier.infers_result_type(&act.of_method()) {
             if let Some(exp) = retstmt.expression.as_ref() {
                 verifier.verify_expression_or_max_cycles_error(&exp, &Default::default());
             }
@@ -195,6 +346,14 @@ impl StatementSubverifier {
             }
         }
 
+        // A generator optionally returns a final value of the yielded type.
+        if act.of_method().is_generator() {
+            if retstmt.expression.is_none() {
+                return;
+            }
+            r_t = r_t.iterator_element_type(&host).unwrap_or(host.any_type());
+        }
+
         if let Some(exp) = retstmt.expression.as_ref() {
             verifier.imp_coerce_exp_or_max_cycles_error(exp, &r_t);
         } else if ![host.any_type(), host.void_type()].contains(&r_t) {
@@ -243,6 +402,7 @@ impl StatementSubverifier {
     fn verify_for_in_stmt(verifier: &mut Subverifier, stmt: &Rc<Directive>, forstmt: &ForInStatement) {
         let host = verifier.host.clone();
         let scope = host.node_mapping().get(&stmt).unwrap();
+        let unassigned_vars = verifier.unassigned_vars.clone();
 
         if let ForInBinding::Expression(dest_exp) = &forstmt.left {
             // Resolve object key-values
@@ -268,6 +428,7 @@ impl StatementSubverifier {
                 mode: VerifyMode::Write,
                 ..default()
             });
+            FlowSubverifier::assign(verifier, dest_exp);
             if let Some(dest) = dest {
                 let dest_t = dest.static_type(&host);
 
@@ -314,7 +475,10 @@ impl StatementSubverifier {
             }
         }
         verifier.inherit_and_enter_scope(&scope);
+        FlowSubverifier::enter_loop(verifier);
         Self::verify_statement(verifier, &forstmt.body);
+        verifier.unassigned_vars = unassigned_vars;
+        FlowSubverifier::exit_break_target(verifier);
         verifier.exit_scope();
     }
 
@@ -356,6 +520,10 @@ impl StatementSubverifier {
         if t == host.xml_type().defer()? || t == host.xml_list_type().defer()? {
             return Ok(Some((host.number_type().defer()?, host.xml_type())));
         }
+        // Generator.<T> or Iterator.<T>
+        if let Some(elem_t) = t.iterator_element_type(host) {
+            return Ok(Some((host.number_type().defer()?, elem_t)));
+        }
 
         Ok(None)
     }
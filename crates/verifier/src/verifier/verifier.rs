@@ -41,6 +41,8 @@ use crate::ns::*;
 /// ```
 pub struct Verifier {
     verifier: Subverifier,
+    /// Programs that MXML documents were desugared into.
+    mxml_programs: HashMap<NodeAsKey<Rc<Mxml>>, Rc<Program>>,
 }
 
 impl Verifier {
@@ -59,11 +61,20 @@ impl Verifier {
                 definition_conflicts: SharedArray::new(),
                 class_defn_guard: HashMap::new(),
                 itrfc_defn_guard: HashMap::new(),
+                allowed_warnings: vec![],
+                flow_narrowings: vec![],
+                unassigned_vars: vec![],
+                flow_break_targets: vec![],
+                yielded_values: HashMap::new(),
+                pending_result_types: HashMap::new(),
+                inferring_result_types: vec![],
+                function_bodies_ready: false,
                 invalidated: false,
                 external: false,
                 // deferred_counter: 0,
                 scope: None,
             },
+            mxml_programs: HashMap::new(),
         }
     }
 
@@ -72,16 +83,41 @@ impl Verifier {
         self.verifier.invalidated
     }
 
+    /// Returns the program that an MXML document was desugared into
+    /// by a previous `verify_programs()` call.
+    pub fn mxml_program(&self, mxml: &Rc<Mxml>) -> Option<Rc<Program>> {
+        self.mxml_programs.get(&NodeAsKey(mxml.clone())).cloned()
+    }
+
     /// # Panics
     ///
     /// Panics if the verifier is already invalidated before verifying.
-    pub fn verify_programs(&mut self, _compiler_options: &Rc<CompilerOptions>, programs: Vec<Rc<Program>>, _mxml_list: Vec<Rc<Mxml>>) {
+    pub fn verify_programs(&mut self, _compiler_options: &Rc<CompilerOptions>, programs: Vec<Rc<Program>>, mxml_list: Vec<Rc<Mxml>>) {
         if self.verifier.invalidated {
             panic!("Verifier already invalidated.");
         }
 
         let host = self.verifier.host.clone();
 
+        // Desugar every MXML document into a program defining a class.
+        let mut programs = programs;
+        let mut mxml_documents: Vec<MxmlDocument> = vec![];
+        for mxml in mxml_list.iter() {
+            if let Some((program, document)) = MxmlSubverifier::desugar_document(&mut self.verifier, mxml) {
+                self.mxml_programs.insert(NodeAsKey(mxml.clone()), program.clone());
+                programs.push(program);
+                mxml_documents.push(document);
+            }
+        }
+
+        // Collect warnings suppressed by [Allow] meta-data.
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                Self::collect_allowed_warnings(&mut self.verifier, &pckgdef.block.directives);
+            }
+            Self::collect_allowed_warnings(&mut self.verifier, &program.directives);
+        }
+
         // Topmost activation before a package
         let top_m = host.factory().create_method_slot(&host.empty_empty_qname(), &host.factory().create_function_type(vec![], host.void_type()));
         let top_act = host.factory().create_activation(&top_m);
@@ -113,7 +149,11 @@ impl Verifier {
             host.node_mapping().set(&pckgdef.block, Some(scope));
         }
 
-        // @todo Declare packages based in MXML source tree.
+        // Declare packages based in MXML source tree and
+        // register placeholders for MXML component classes.
+        for document in mxml_documents.iter() {
+            MxmlSubverifier::declare_class_placeholder(&mut self.verifier, document);
+        }
 
         // Verify namespace definitions across packages a first time, as they may be used
         // as attributes.
@@ -182,6 +222,10 @@ impl Verifier {
                 rem_pckg_list.remove(i);
             }
         }
+        // Function bodies may be verified ahead of time to infer
+        // result types from now on.
+        self.verifier.function_bodies_ready = true;
+
         if rem_pckg_list.is_empty() {
             // Verify statements across packages
             for pckg in packages.iter() {
@@ -220,6 +264,7 @@ impl Verifier {
             // Enter scope
             self.inherit_and_enter_scope(&top_act);
 
+            self.verifier.function_bodies_ready = false;
             let mut any_defer = false;
             for _ in 0..Verifier::MAX_CYCLES {
                 any_defer = DirectiveSubverifier::verify_directives(&mut self.verifier, &program.directives).is_err();
@@ -230,22 +275,38 @@ impl Verifier {
             if any_defer {
                 self.verifier.add_verify_error(&program.location, WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
             }
+            self.verifier.function_bodies_ready = true;
             StatementSubverifier::verify_statements(&mut self.verifier, &program.directives);
 
             self.exit_scope();
         }
 
+        // Verify attributes and child elements of MXML documents.
+        for document in mxml_documents.iter() {
+            MxmlSubverifier::verify_document(&mut self.verifier, document);
+        }
+
         // Handle deferred function commons for function definitions.
         for _ in 0..Verifier::MAX_CYCLES {
             let mut any_defer = false;
             let mut eliminated_commons: Vec<Rc<FunctionCommon>> = vec![];
-            for (common, partials) in self.verifier.function_definition_partials.clone().borrow().iter() {
-                let common = (**common).clone();
-                let r = FunctionCommonSubverifier::verify_function_definition_common(&mut self.verifier, &common, partials).is_err();
-                if !r {
-                    FunctionCommonSubverifier::verify_function_definition_common_statements_only(&mut self.verifier, &common, &partials);
-                    eliminated_commons.push(common);
+            let list = self.verifier.function_definition_partials.borrow().iter().map(|(common, partials)| ((**common).clone(), partials.clone())).collect::<Vec<_>>();
+            for (common, partials) in list.iter() {
+                // Skip function bodies verified ahead of time to infer their result type.
+                if !self.verifier.function_definition_partials.has(&NodeAsKey(common.clone())) {
+                    continue;
                 }
+                let method = partials.activation().of_method();
+                let r = if self.verifier.pending_result_types.contains_key(&method) {
+                    FunctionCommonSubverifier::infer_pending_result_type(&mut self.verifier, &method).is_err()
+                } else {
+                    let r = FunctionCommonSubverifier::verify_function_definition_common(&mut self.verifier, common, partials).is_err();
+                    if !r {
+                        FunctionCommonSubverifier::verify_function_definition_common_statements_only(&mut self.verifier, common, partials);
+                        eliminated_commons.push(common.clone());
+                    }
+                    r
+                };
                 any_defer = any_defer || r;
             }
             // Cleanup the VerifierFunctionPartials cache from Subverifier.
@@ -382,6 +443,10 @@ impl Verifier {
         self.verifier.add_warning(location, kind, arguments);
     }
 
+    pub fn add_warning_with_fixes(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>, fixes: Vec<DiagnosticFix>) {
+        self.verifier.add_warning_with_fixes(location, kind, arguments, fixes);
+    }
+
     fn collect_package_definitions(program: &Rc<Program>) -> Vec<Rc<PackageDefinition>> {
         let mut r = program.packages.clone();
         for drtv in &program.directives {
@@ -402,6 +467,77 @@ impl Verifier {
         r
     }
 
+    /// Collects the warnings suppressed by `[Allow]` meta-data
+    /// across definitions. Function bodies are not visited.
+    fn collect_allowed_warnings(verifier: &mut Subverifier, directives: &[Rc<Directive>]) {
+        for drtv in directives {
+            match drtv.as_ref() {
+                Directive::ClassDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                    Self::collect_allowed_warnings(verifier, &defn.block.directives);
+                },
+                Directive::EnumDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                    Self::collect_allowed_warnings(verifier, &defn.block.directives);
+                },
+                Directive::InterfaceDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                    Self::collect_allowed_warnings(verifier, &defn.block.directives);
+                },
+                Directive::FunctionDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                },
+                Directive::VariableDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                },
+                Directive::TypeDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                },
+                Directive::NamespaceDefinition(defn) => {
+                    Self::allow_warnings(verifier, &defn.location, &defn.attributes);
+                },
+                Directive::Block(block) => {
+                    Self::collect_allowed_warnings(verifier, &block.directives);
+                },
+                Directive::ConfigurationDirective(cfgdrtv) => {
+                    Self::collect_allowed_warnings(verifier, std::slice::from_ref(&cfgdrtv.directive));
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    for pckgdef in incdrtv.nested_packages.iter() {
+                        Self::collect_allowed_warnings(verifier, &pckgdef.block.directives);
+                    }
+                    Self::collect_allowed_warnings(verifier, &incdrtv.nested_directives);
+                },
+                _ => {},
+            }
+        }
+    }
+
+    /// Handles `[Allow("Name1", "Name2")]` meta-data, where each entry
+    /// is either the name or ID of a diagnostic.
+    fn allow_warnings(verifier: &mut Subverifier, location: &Location, attributes: &[Attribute]) {
+        let mut ids: Vec<i32> = vec![];
+        for m in Attribute::find_metadata(attributes).iter() {
+            if m.name.0 != "Allow" {
+                continue;
+            }
+            for entry in m.entries.iter().flatten() {
+                let val = match entry.value.as_ref() {
+                    MetadataValue::String(val) => val.0.clone(),
+                    MetadataValue::IdentifierString(val) => val.0.clone(),
+                };
+                let kind = WhackDiagnosticKind::from_name(&val)
+                    .or_else(|| val.parse::<i32>().ok().and_then(WhackDiagnosticKind::from_id));
+                if let Some(kind) = kind {
+                    ids.push(kind.id());
+                }
+            }
+        }
+        if !ids.is_empty() {
+            verifier.allowed_warnings.push((location.clone(), ids));
+        }
+    }
+
     #[inline(always)]
     pub fn set_scope(&mut self, scope: &Entity) {
         self.verifier.set_scope(scope);
@@ -442,6 +578,31 @@ pub(crate) struct Subverifier {
     pub class_defn_guard: HashMap<NodeAsKey<Rc<Directive>>, Rc<ClassDefnGuard>>,
     pub itrfc_defn_guard: HashMap<NodeAsKey<Rc<Directive>>, Rc<InterfaceDefnGuard>>,
 
+    /// Ranges of definitions annotated with `[Allow]` meta-data
+    /// and the IDs of the warnings they suppress.
+    pub allowed_warnings: Vec<(Location, Vec<i32>)>,
+
+    /// Narrowings of local variables within the guarded regions being verified,
+    /// as (variable slot, narrowing, activation) entries.
+    pub flow_narrowings: Vec<(Entity, FlowNarrowing, Entity)>,
+    /// Local variables that are not definitely assigned at the
+    /// statement being verified.
+    pub unassigned_vars: Vec<Entity>,
+    pub flow_break_targets: Vec<FlowBreakTarget>,
+
+    /// Values yielded by generators whose result type is
+    /// being inferred, by activation.
+    pub yielded_values: HashMap<Entity, Vec<(Location, Entity)>>,
+
+    /// Function definitions whose result type is inferred once
+    /// their body is verified, by method slot.
+    pub pending_result_types: HashMap<Entity, Rc<FunctionCommon>>,
+    /// Methods whose body is being verified to infer their result type.
+    pub inferring_result_types: Vec<Entity>,
+    /// Whether function bodies may be verified when their definition
+    /// is referenced, which is not the case while directives are verified.
+    pub function_bodies_ready: bool,
+
     invalidated: bool,
     // pub deferred_counter: usize,
     pub scope: Option<Entity>,
@@ -463,6 +624,10 @@ impl Subverifier {
         self.function_definition_partials.clear();
         self.class_defn_guard.clear();
         self.itrfc_defn_guard.clear();
+        self.yielded_values.clear();
+        self.pending_result_types.clear();
+        self.inferring_result_types.clear();
+        self.function_bodies_ready = false;
     }
 
     pub fn lazy_init_drtv_phase(&mut self, drtv: &Rc<Directive>, initial_phase: VerifierPhase) -> VerifierPhase {
@@ -514,29 +679,76 @@ impl Subverifier {
     }
 
     pub fn add_syntax_error(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        self.add_syntax_error_with_fixes(location, kind, arguments, vec![]);
+    }
+
+    pub fn add_syntax_error_with_fixes(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>, fixes: Vec<DiagnosticFix>) {
         let cu = location.compilation_unit();
         if cu.prevent_equal_offset_error(location) {
             return;
         }
-        cu.add_diagnostic(WhackDiagnostic::new_syntax_error(location, kind, arguments));
+        let diagnostic = WhackDiagnostic::new_syntax_error(location, kind, arguments);
+        WhackDiagnostic(&diagnostic).set_fixes(fixes);
+        cu.add_diagnostic(diagnostic);
         self.invalidated = true;
     }
 
     pub fn add_verify_error(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        self.add_verify_error_with_fixes(location, kind, arguments, vec![]);
+    }
+
+    pub fn add_verify_error_with_fixes(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>, fixes: Vec<DiagnosticFix>) {
         let cu = location.compilation_unit();
         if cu.prevent_equal_offset_error(location) {
             return;
         }
-        cu.add_diagnostic(WhackDiagnostic::new_verify_error(location, kind, arguments));
+        let diagnostic = WhackDiagnostic::new_verify_error(location, kind, arguments);
+        WhackDiagnostic(&diagnostic).set_fixes(fixes);
+        cu.add_diagnostic(diagnostic);
         self.invalidated = true;
     }
 
+    /// Reports a warning according to its level. A denied warning
+    /// is reported as a verify error.
     pub fn add_warning(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        self.add_warning_with_fixes(location, kind, arguments, vec![]);
+    }
+
+    pub fn add_warning_with_fixes(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>, fixes: Vec<DiagnosticFix>) {
+        match self.warning_level(location, kind) {
+            WarningLevel::Allow => {},
+            WarningLevel::Warn => {
+                let cu = location.compilation_unit();
+                if cu.prevent_equal_offset_warning(location) {
+                    return;
+                }
+                let diagnostic = WhackDiagnostic::new_warning(location, kind, arguments);
+                WhackDiagnostic(&diagnostic).set_fixes(fixes);
+                cu.add_diagnostic(diagnostic);
+            },
+            WarningLevel::Deny => {
+                self.add_verify_error_with_fixes(location, kind, arguments, fixes);
+            },
+        }
+    }
+
+    /// Returns the level of a warning at a location, considering
+    /// `[Allow]` meta-data and then the compiler options.
+    pub fn warning_level(&self, location: &Location, kind: WhackDiagnosticKind) -> WarningLevel {
         let cu = location.compilation_unit();
-        if cu.prevent_equal_offset_warning(location) {
-            return;
+        let allowed = self.allowed_warnings.iter().any(|(range, ids)| {
+            Rc::ptr_eq(&range.compilation_unit(), &cu)
+                && range.first_offset() <= location.first_offset()
+                && location.last_offset() <= range.last_offset()
+                && ids.contains(&kind.id())
+        });
+        if allowed {
+            return WarningLevel::Allow;
         }
-        cu.add_diagnostic(WhackDiagnostic::new_warning(location, kind, arguments));
+        cu.compiler_options()
+            .and_then(|options| Rc::downcast::<CompilerOptions>(options).ok())
+            .map(|options| options.warnings.level_of(kind))
+            .unwrap_or(WarningLevel::Warn)
     }
 
     pub fn set_scope(&mut self, scope: &Entity) {
@@ -559,6 +771,18 @@ impl Subverifier {
         self.scope.as_ref().unwrap().clone()
     }
 
+    /// Indicates whether the result type of a method is being inferred
+    /// from its body, in which case returned and yielded values are
+    /// recorded rather than coerced.
+    pub fn infers_result_type(&self, method: &Entity) -> bool {
+        method.signature(&self.host).is::<UnresolvedEntity>() || self.inferring_result_types.contains(method)
+    }
+
+    /// Returns the default XML namespace of the current activation, if any.
+    pub fn default_xml_namespace(&self) -> Option<Entity> {
+        self.scope().search_activation().and_then(|act| act.default_xml_namespace())
+    }
+
     pub fn verify_expression_or_max_cycles_error(&mut self, exp: &Rc<Expression>, context: &VerifierExpressionContext) -> Option<Entity> {
         let val = self.verify_expression(exp, context);
         if let Ok(val) = val {
@@ -580,9 +804,15 @@ impl Subverifier {
         match exp.as_ref() {
             Expression::QualifiedIdentifier(id) => {
                 result = ExpSubverifier::verify_qualified_identifier_as_exp(self, id, context)?;
+                if let Some(r) = result.as_ref() {
+                    FunctionCommonSubverifier::resolve_pending_result_type(self, r)?;
+                }
             },
             Expression::Member(e) => {
                 result = ExpSubverifier::verify_member_exp(self, exp, e, context)?;
+                if let Some(r) = result.as_ref() {
+                    FunctionCommonSubverifier::resolve_pending_result_type(self, r)?;
+                }
             },
             Expression::ComputedMember(e) => {
                 result = ExpSubverifier::verify_computed_member_exp(self, e, context)?;
@@ -830,7 +1060,7 @@ impl Subverifier {
                 for _ in 0..p.type_params().unwrap().length() {
                     subst.push(self.host.any_type());
                 }
-                return Ok(Some(self.host.factory().create_type_after_substitution(&p, &subst)));
+                return Ok(Some(self.host.factory().create_type_after_substitution(&p, &subst).wrap_property_reference(&self.host)?));
             }
 
             // Compile-time constant
@@ -904,20 +1134,29 @@ impl Subverifier {
     }
 
     /// Ensures a definition in a base class is not to be shadowed.
-    pub fn ensure_not_shadowing_definition(&mut self, name_loc: &Location, output: &Names, parent: &Entity, name: &QName) {
+    ///
+    /// `override_fixes` are attached to the reported error when
+    /// the shadowed definition is a method or virtual property.
+    pub fn ensure_not_shadowing_definition(&mut self, name_loc: &Location, output: &Names, parent: &Entity, name: &QName, override_fixes: Vec<DiagnosticFix>) {
         // Do not worry about enums as they always extend Object directly.
         if parent.is::<ClassType>() && output == &parent.prototype(&self.host) {
             let mut p1 = parent.extends_class(&self.host);
             while p1.is_some() {
                 let p = p1.unwrap();
                 let dup;
+                let shadowed;
                 if name.namespace().is_public_ns() {
-                    dup = p.prototype(&self.host).get_in_any_public_ns(&name.local_name()).map(|e| e.is_some()).unwrap_or(true);
+                    let r = p.prototype(&self.host).get_in_any_public_ns(&name.local_name());
+                    dup = r.as_ref().map(|e| e.is_some()).unwrap_or(true);
+                    shadowed = r.ok().flatten();
                 } else {
-                    dup = p.prototype(&self.host).has(name);
+                    shadowed = p.prototype(&self.host).get(name);
+                    dup = shadowed.is_some();
                 }
                 if dup {
-                    self.add_syntax_error(name_loc, WhackDiagnosticKind::ShadowingDefinitionInBaseClass, diagarg![name.to_string()]);
+                    let overridable = shadowed.map(|e| e.is::<MethodSlot>() || e.is::<VirtualSlot>()).unwrap_or(false);
+                    let fixes = if overridable { override_fixes } else { vec![] };
+                    self.add_syntax_error_with_fixes(name_loc, WhackDiagnosticKind::ShadowingDefinitionInBaseClass, diagarg![name.to_string()], fixes);
                     break;
                 }
                 p1 = p.extends_class(&self.host);
@@ -0,0 +1,142 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const DISPATCHER: &str = r#"
+    package com.x {
+        public class Dispatcher {
+            public function dispatchEvent(event: Object): Boolean {
+                return true;
+            }
+        }
+    }
+"#;
+
+/// Verifies a source alongside the `com.x.Dispatcher` class.
+fn verify_with_dispatcher(source: &str) -> Vec<i32> {
+    verify_files(&[("Dispatcher.as", DISPATCHER), ("source.as", source)], Default::default(), ids)
+}
+
+#[test]
+fn bindable_properties() {
+    let ids = verify_with_dispatcher(r#"
+        package com.x {
+            public class Model extends Dispatcher {
+                [Bindable]
+                public var x: Number = 0;
+
+                [Bindable(event="yChange")]
+                public var y: Number = 0;
+
+                [Bindable("zChange")]
+                public function get z(): Number {
+                    return 0;
+                }
+                public function set z(value: Number): void {}
+            }
+        }
+    "#);
+    assert_eq!(ids, Vec::<i32>::new());
+}
+
+#[test]
+fn malformed_bindable_metadata() {
+    let ids = verify_with_dispatcher(r#"
+        package com.x {
+            public class Model extends Dispatcher {
+                [Bindable(type="yChange")]
+                public var y: Number = 0;
+
+                [Bindable(event="")]
+                public var z: Number = 0;
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::MalformedBindableMetadata.id(); 2]);
+}
+
+#[test]
+fn bindable_not_allowed_here() {
+    let ids = verify_with_dispatcher(r#"
+        package com.x {
+            public class Model extends Dispatcher {
+                [Bindable]
+                public static var x: Number = 0;
+
+                [Bindable]
+                public const y: Number = 0;
+            }
+
+            [Bindable]
+            public var z: Number = 0;
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::BindableNotAllowedHere.id(); 3]);
+}
+
+#[test]
+fn bindable_requires_event_dispatcher() {
+    let messages = verify_files(&[("source.as", r#"
+        package com.x {
+            public class Model {
+                [Bindable]
+                public var x: Number = 0;
+            }
+        }
+    "#)], Default::default(), |diagnostics| {
+        diagnostics.iter().map(|d| WhackDiagnostic(d).format_message_english()).collect::<Vec<_>>()
+    });
+    assert_eq!(messages, vec!["[Bindable] requires com.x.Model to be an event dispatcher.".to_owned()]);
+}
+
+#[test]
+fn data_binding_sources() {
+    let model = r#"
+        package com.x {
+            public class Model extends Dispatcher {
+                [Bindable]
+                public var watched: Number = 0;
+                public var unwatched: Number = 0;
+                public const fixed: Number = 0;
+            }
+        }
+    "#;
+    let box_source = r#"
+        package com.ui {
+            public class Box {
+                public var width: Number = 0;
+            }
+        }
+    "#;
+    let main = r#"<?xml version="1.0"?>
+<ui:Box xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:ui="com.ui.*">
+    <fx:Script><![CDATA[
+        import com.x.Model;
+        public const model: Model = new Model();
+    ]]></fx:Script>
+    <fx:Declarations>
+        <ui:Box id="a" width="{model.watched + model.fixed}"/>
+        <ui:Box id="b" width="{model.unwatched}"/>
+        <ui:Box id="c" width="{a.width}"/>
+    </fx:Declarations>
+</ui:Box>"#;
+    let compiler_options = CompilerOptions {
+        source_path: vec!["/src".to_owned()],
+        ..Default::default()
+    };
+    let files = [
+        ("/src/com/x/Dispatcher.as", DISPATCHER),
+        ("/src/com/x/Model.as", model),
+        ("/src/com/ui/Box.as", box_source),
+        ("/src/com/app/Main.mxml", main),
+    ];
+    let messages = verify_files(&files, compiler_options, |diagnostics| {
+        diagnostics.iter().map(|d| WhackDiagnostic(d).format_message_english()).collect::<Vec<_>>()
+    });
+    // The `a` component variable is not reported, but its `width` property is.
+    assert_eq!(messages, vec![
+        "Data binding will not be able to detect assignments to unwatched.".to_owned(),
+        "Data binding will not be able to detect assignments to width.".to_owned(),
+    ]);
+}
@@ -0,0 +1,118 @@
+package
+{
+    public const undefined: * = void 0;
+
+    public const NaN: Number = 0 / 0;
+
+    public const Infinity: Number = 1 / 0;
+
+    public function trace(...args): void {
+    }
+
+    public dynamic final class Array.<T>
+    {
+    }
+
+    public final class Boolean
+    {
+    }
+
+    public class Class
+    {
+    }
+
+    public final class Date
+    {
+    }
+
+    public dynamic class Error
+    {
+    }
+
+    public final class float
+    {
+    }
+
+    public final class Function
+    {
+    }
+
+    public final class Generator.<T>
+    {
+    }
+
+    public interface Iterator.<T>
+    {
+    }
+
+    public final class int
+    {
+    }
+
+    public static class JSBridge
+    {
+    }
+
+    public dynamic final class JSVal
+    {
+    }
+
+    public final class Map.<K, V>
+    {
+    }
+
+    public final class Namespace
+    {
+    }
+
+    public final class Number
+    {
+    }
+
+    public dynamic class Object
+    {
+    }
+
+    public final class Promise.<T>
+    {
+    }
+
+    public final class QName
+    {
+    }
+
+    public dynamic class RegExp
+    {
+    }
+
+    public final class String
+    {
+    }
+
+    public final class uint
+    {
+    }
+
+    public final class Vector.<T>
+    {
+    }
+
+    public final class XML
+    {
+    }
+
+    public final class XMLList
+    {
+    }
+}
+
+package whack.utils
+{
+    public class ByteArray
+    {
+    }
+
+    public class Proxy
+    {
+    }
+}
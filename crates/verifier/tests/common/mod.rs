@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+
+use whackengine_verifier::ns::*;
+
+/// Minimal top-level definitions required by the verifier.
+const BUILTINS: &str = include_str!("builtins.as");
+
+/// Verifies ActionScript 3 and MXML sources, given as (file path, text)
+/// pairs, after the built-ins and passes the diagnostics of the given
+/// sources to `f` while the database is still alive.
+///
+/// Sources whose path ends with `.mxml` are parsed as MXML.
+pub fn verify_files<T>(files: &[(&str, &str)], compiler_options: CompilerOptions, f: impl FnOnce(&[Diagnostic]) -> T) -> T {
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    let compiler_options = Rc::new(compiler_options);
+
+    let mut programs: Vec<Rc<Program>> = vec![];
+    let mut mxml: Vec<Rc<Mxml>> = vec![];
+    let mut compilation_units: Vec<Rc<CompilationUnit>> = vec![];
+    for (file_path, text) in [("builtins.as", BUILTINS)].iter().chain(files.iter()) {
+        let cu = CompilationUnit::new(Some(file_path.to_string()), text.to_string());
+        cu.set_compiler_options(Some(compiler_options.clone()));
+        if file_path.ends_with(".mxml") {
+            mxml.push(ParserFacade(&cu, Default::default()).parse_mxml());
+        } else {
+            programs.push(ParserFacade(&cu, Default::default()).parse_program());
+        }
+        compilation_units.push(cu);
+    }
+    verifier.verify_programs(&compiler_options, programs, mxml);
+
+    let mut diagnostics = vec![];
+    for cu in compilation_units[1..].iter() {
+        cu.sort_diagnostics();
+        diagnostics.extend(cu.nested_diagnostics());
+    }
+    f(&diagnostics)
+}
+
+/// Verifies an ActionScript 3 source after the built-ins and returns
+/// its diagnostics.
+pub fn verify(source: &str) -> Vec<Diagnostic> {
+    verify_files(&[("source.as", source)], Default::default(), |diagnostics| diagnostics.to_vec())
+}
+
+/// Verifies an ActionScript 3 source after the built-ins and returns
+/// its diagnostic messages in English.
+pub fn messages(source: &str) -> Vec<String> {
+    verify_files(&[("source.as", source)], Default::default(), |diagnostics| {
+        diagnostics.iter().map(|d| WhackDiagnostic(d).format_message_english()).collect()
+    })
+}
+
+/// Verifies an ActionScript 3 source after the built-ins and returns
+/// the IDs of its diagnostics.
+pub fn diagnostic_ids(source: &str) -> Vec<i32> {
+    ids(&verify(source))
+}
+
+/// Verifies `body` as the body of the top-level function
+/// `f(c: Boolean): void` and returns the IDs of its diagnostics.
+pub fn verify_body(body: &str) -> Vec<i32> {
+    verify_body_with("", "c: Boolean", body)
+}
+
+/// Verifies `body` as the body of the top-level function
+/// `f({params}): void`, preceded by the top-level definitions
+/// `prelude`, and returns the IDs of its diagnostics.
+pub fn verify_body_with(prelude: &str, params: &str, body: &str) -> Vec<i32> {
+    verify_body_with_files(&[], prelude, params, body)
+}
+
+/// Like `verify_body_with()`, verifying the given (file path, text)
+/// pairs before the function.
+pub fn verify_body_with_files(files: &[(&str, &str)], prelude: &str, params: &str, body: &str) -> Vec<i32> {
+    let source = format!(r#"
+        package {{
+            {prelude}
+            public function f({params}): void {{
+                {body}
+            }}
+        }}
+    "#);
+    let files = files.iter().copied().chain([("source.as", source.as_str())]).collect::<Vec<_>>();
+    verify_files(&files, Default::default(), ids)
+}
+
+pub fn ids(diagnostics: &[Diagnostic]) -> Vec<i32> {
+    diagnostics.iter().map(|d| WhackDiagnostic(d).id()).collect()
+}
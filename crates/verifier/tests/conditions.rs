@@ -0,0 +1,46 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+/// Definitions preceding the function whose body is verified.
+const PRELUDE: &str = r#"
+    public interface I {}
+    public class A {}
+    public class B extends A {}
+    public class C {}
+    public function g(): void {}
+"#;
+
+const PARAMS: &str = "c: Boolean, a: A, p: Promise.<Number>";
+
+#[test]
+fn void_condition() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (g()) { trace(1); }"), vec![WhackDiagnosticKind::VoidCondition.id()]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "while (g()) { trace(1); }"), vec![WhackDiagnosticKind::VoidCondition.id()]);
+}
+
+#[test]
+fn non_boolean_constant_condition() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (1) { trace(1); }"), vec![WhackDiagnosticKind::NonBooleanConstantCondition.id()]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (c) { trace(1); }"), Vec::<i32>::new());
+}
+
+#[test]
+fn unawaited_promise_condition() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (p) { trace(1); }"), vec![WhackDiagnosticKind::UnawaitedPromiseCondition.id()]);
+    assert_eq!(
+        messages("package { public function f(p: Promise.<Number>): void { if (p) { trace(1); } } }"),
+        vec!["Condition of type Promise.<Number> always evaluates to true. Did you forget to await it?".to_owned()],
+    );
+}
+
+#[test]
+fn switch_type_cases() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "switch type (a) { case (b: B) { trace(b); } }"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "switch type (a) { case (b: C) { trace(b); } }"), vec![WhackDiagnosticKind::UnrelatedSwitchTypeCase.id()]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "switch type (a) { case (b: ?C) { trace(b); } }"), vec![WhackDiagnosticKind::UnrelatedSwitchTypeCase.id()]);
+
+    // A subclass may implement any interface.
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "switch type (a) { case (i: I) { trace(i); } }"), Vec::<i32>::new());
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const NOT_ALL_PATHS: i32 = WhackDiagnosticKind::NotAllCodePathsReturnAValue as i32;
+const UNREACHABLE: i32 = WhackDiagnosticKind::UnreachableCode as i32;
+
+#[test]
+fn missing_return() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function f(c: Boolean): Number {
+                if (c) {
+                    return 1;
+                }
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![NOT_ALL_PATHS]);
+}
+
+#[test]
+fn all_paths_return() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function f(c: Boolean, n: Number): Number {
+                if (c) {
+                    return 1;
+                } else {
+                    switch (n) {
+                        case 0:
+                            return 0;
+                        default:
+                            throw new Error();
+                    }
+                }
+            }
+        }
+    "#);
+    assert_eq!(ids, Vec::<i32>::new());
+}
+
+#[test]
+fn return_through_finally() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function f(): Number {
+                try {
+                    return 1;
+                } finally {
+                    trace("done");
+                }
+            }
+        }
+    "#);
+    assert_eq!(ids, Vec::<i32>::new());
+}
+
+#[test]
+fn unreachable_after_return() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function f(): void {
+                return;
+                trace("never");
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![UNREACHABLE]);
+}
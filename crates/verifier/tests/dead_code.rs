@@ -0,0 +1,35 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const UNREACHABLE: i32 = WhackDiagnosticKind::UnreachableCode as i32;
+const CONSTANT_CONDITION: i32 = WhackDiagnosticKind::ConstantCondition as i32;
+
+#[test]
+fn unreachable_after_jumps() {
+    assert_eq!(verify_body("throw new Error(); trace(1);"), vec![UNREACHABLE]);
+    assert_eq!(verify_body("while (c) { break; trace(1); }"), vec![UNREACHABLE]);
+    assert_eq!(verify_body("while (c) { continue; trace(1); }"), vec![UNREACHABLE]);
+    assert_eq!(verify_body("if (c) { return; } else { throw new Error(); } trace(1);"), vec![UNREACHABLE]);
+}
+
+#[test]
+fn constant_conditions() {
+    assert_eq!(verify_body("if (1 > 2) { trace(1); }"), vec![CONSTANT_CONDITION, UNREACHABLE]);
+    assert_eq!(verify_body("const K: Boolean = false; if (K) { trace(1); }"), vec![CONSTANT_CONDITION, UNREACHABLE]);
+    assert_eq!(verify_body("if (!(1 > 2)) { trace(1); } else { trace(2); }"), vec![CONSTANT_CONDITION, UNREACHABLE]);
+    assert_eq!(messages("package { public function f(): void { if (2 > 1) { trace(1); } } }"), vec!["Condition is always true.".to_owned()]);
+}
+
+#[test]
+fn literal_conditions() {
+    // Literal conditions are deliberate and are not reported,
+    // but code they skip is still unreachable.
+    assert_eq!(verify_body("if (true) { trace(1); }"), Vec::<i32>::new());
+    assert_eq!(verify_body("while (true) { break; }"), Vec::<i32>::new());
+    assert_eq!(verify_body("for (;;) { break; }"), Vec::<i32>::new());
+    assert_eq!(verify_body("do { trace(1); } while (false);"), Vec::<i32>::new());
+    assert_eq!(verify_body("while (false) { trace(1); }"), vec![UNREACHABLE]);
+    assert_eq!(verify_body("while (true) { trace(1); } trace(2);"), vec![UNREACHABLE]);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+/// Parameters of the function whose body is verified.
+const PARAMS: &str = "c: Boolean, x: XML";
+
+#[test]
+fn namespace_or_string() {
+    assert_eq!(verify_body_with("", PARAMS, "default xml namespace = new Namespace();"), Vec::<i32>::new());
+    assert_eq!(verify_body_with("", PARAMS, r#"default xml namespace = "http://example.com";"#), Vec::<i32>::new());
+    assert_eq!(verify_body_with("", PARAMS, "var ns: Namespace = null; default xml namespace = ns;"), Vec::<i32>::new());
+    assert_eq!(verify_body_with("", PARAMS, r#"if (c) { default xml namespace = "http://example.com"; }"#), Vec::<i32>::new());
+}
+
+#[test]
+fn default_xml_namespace_must_be_namespace_or_string() {
+    assert_eq!(verify_body_with("", PARAMS, "default xml namespace = 1;"), vec![WhackDiagnosticKind::DefaultXmlNamespaceMustBeNamespaceOrString.id()]);
+    assert_eq!(
+        messages("package { public function f(): void { default xml namespace = new Error(); } }"),
+        vec!["Default XML namespace must be a Namespace or String, found Error.".to_owned()],
+    );
+}
+
+#[test]
+fn unqualified_xml_names() {
+    assert_eq!(verify_body_with("", PARAMS, r#"default xml namespace = "http://example.com"; var items: XMLList = x.item; x.item = "1";"#), Vec::<i32>::new());
+}
+
+#[test]
+fn namespace_defined_later() {
+    // The statement is verified again once `Names` is resolved.
+    let user = r#"
+        package com.x {
+            public function f(x: XML): void {
+                default xml namespace = Names.NS;
+                trace(x.item);
+            }
+        }
+    "#;
+    let names = r#"
+        package com.x {
+            public class Names {
+                public static const NS: Namespace = null;
+            }
+        }
+    "#;
+    assert_eq!(verify_files(&[("User.as", user), ("Names.as", names)], Default::default(), ids), Vec::<i32>::new());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const INVALID_SOURCE: &str = r#"
+    package {
+        public function f(): Number {
+            return new Error();
+        }
+    }
+"#;
+
+fn format_messages(messages: &DiagnosticMessages, source: &str) -> Vec<String> {
+    verify_files(&[("source.as", source)], Default::default(), |diagnostics| {
+        diagnostics.iter().map(|d| messages.format_message(d)).collect()
+    })
+}
+
+#[test]
+fn shipped_locales() {
+    assert!(DiagnosticMessages::shipped_locales().contains(&"pt-BR"));
+    assert_eq!(DiagnosticMessages::shipped("pt-BR").unwrap().locale(), "pt-BR");
+    assert_eq!(DiagnosticMessages::shipped("pt_BR.UTF-8").unwrap().locale(), "pt-BR");
+    assert_eq!(DiagnosticMessages::shipped("pt").unwrap().locale(), "pt-BR");
+    assert_eq!(DiagnosticMessages::shipped("en_US.UTF-8").unwrap().locale(), "en");
+    assert_eq!(DiagnosticMessages::shipped("C").unwrap().locale(), "en");
+    assert!(DiagnosticMessages::shipped("xx").is_none());
+}
+
+#[test]
+fn shipped_messages() {
+    let messages = DiagnosticMessages::shipped("pt-BR").unwrap();
+    assert_eq!(format_messages(&messages, INVALID_SOURCE), vec![
+        "Coerção implícita de um valor do tipo Error para um tipo não relacionado Number.".to_owned(),
+    ]);
+}
+
+#[test]
+fn messages_fall_back_to_english() {
+    let messages = DiagnosticMessages::from_toml("xx", "2158 = \"{1}?\"").unwrap();
+    assert_eq!(format_messages(&messages, INVALID_SOURCE), vec![
+        "Implicit coercion of a value of type Error to an unrelated type Number.".to_owned(),
+    ]);
+}
+
+#[test]
+fn messages_from_toml() {
+    let messages = DiagnosticMessages::from_toml("xx", "2049 = \"{1} -> {2}\"").unwrap();
+    assert_eq!(messages.locale(), "xx");
+    assert_eq!(format_messages(&messages, INVALID_SOURCE), vec!["Error -> Number".to_owned()]);
+
+    assert_eq!(DiagnosticMessages::from_toml("xx", "Unused = \"{1}?\"").err().unwrap(), "Key is not a diagnostic ID: Unused");
+    assert!(DiagnosticMessages::from_toml("xx", "2049 = ").is_err());
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+/// Verifies a class body in a source file next to a `logo.png` asset.
+fn verify_class_body(name: &str, body: &str) -> Vec<i32> {
+    let dir = std::env::temp_dir().join("verifier-tests").join(format!("embed-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("logo.png"), [0x89, b'P', b'N', b'G']).unwrap();
+    let source_path = dir.join("Assets.as");
+    let source = format!(r#"
+        package {{
+            import whack.utils.ByteArray;
+
+            public class Assets {{
+                {body}
+            }}
+        }}
+    "#);
+    let ids = verify_files(&[(source_path.to_str().unwrap(), &source)], Default::default(), ids);
+    std::fs::remove_dir_all(&dir).unwrap();
+    ids
+}
+
+#[test]
+fn embedded_assets() {
+    let ids = verify_class_body("valid", r#"
+        [Embed(source="logo.png")]
+        public static const LOGO: Class;
+
+        [Embed("logo.png", mimeType="application/octet-stream")]
+        public static const LOGO_BYTES: ByteArray;
+    "#);
+    assert_eq!(ids, Vec::<i32>::new());
+}
+
+#[test]
+fn embedded_asset_not_found() {
+    let ids = verify_class_body("not-found", r#"
+        [Embed(source="missing.png")]
+        public static const LOGO: Class;
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::EmbeddedAssetNotFound.id()]);
+}
+
+#[test]
+fn unsupported_mime_type() {
+    let ids = verify_class_body("mime-type", r#"
+        [Embed(source="logo.png", mimeType="text/x-unknown")]
+        public static const LOGO: Class;
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::UnsupportedEmbedMimeType.id()]);
+}
+
+#[test]
+fn malformed_embed_metadata() {
+    let ids = verify_class_body("malformed", r#"
+        [Embed(mimeType="image/png")]
+        public static const LOGO: Class;
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::MalformedEmbedMetadata.id()]);
+}
+
+#[test]
+fn embed_not_allowed_here() {
+    let ids = verify_class_body("not-allowed", r#"
+        [Embed(source="logo.png")]
+        public const LOGO: Class;
+
+        [Embed(source="logo.png")]
+        public static var LOGO_2: Class;
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::EmbedNotAllowedHere.id(); 2]);
+}
+
+#[test]
+fn embed_must_be_class_or_byte_array() {
+    let ids = verify_class_body("type", r#"
+        [Embed(source="logo.png")]
+        public static const LOGO: String;
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::EmbedMustBeClassOrByteArray.id()]);
+}
+
+#[test]
+fn embed_bindings() {
+    let ids = verify_class_body("bindings", r#"
+        [Embed(source="logo.png")]
+        public static const LOGO: Class = null;
+
+        [Embed(source="logo.png")]
+        public static const LOGO_2: Class, LOGO_3: Class;
+    "#);
+    assert_eq!(ids, vec![
+        WhackDiagnosticKind::EmbedConstantMustNotContainInitializer.id(),
+        WhackDiagnosticKind::EmbedMustApplyToSingleBinding.id(),
+    ]);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const EVENTS: &str = r#"
+    package com.x {
+        public class Event {}
+        public class ProgressEvent extends Event {}
+
+        [Event(name="close", type="com.x.Event")]
+        public interface IWindow {}
+
+        public class Dispatcher {
+            public function addEventListener(type: String, listener: Function): void {}
+            public function removeEventListener(type: String, listener: Function): void {}
+        }
+
+        [Event(name="progress", type="com.x.ProgressEvent")]
+        [Event(name="complete", type="com.x.Event")]
+        public class Loader extends Dispatcher implements IWindow {}
+
+        public class URLLoader extends Loader {}
+    }
+"#;
+
+const FILES: &[(&str, &str)] = &[("Events.as", EVENTS)];
+
+/// Listeners of the events of `com.x`.
+const PRELUDE: &str = r#"
+    import com.x.*;
+    public function onEvent(event: Event): void {}
+    public function onProgress(event: ProgressEvent): void {}
+"#;
+
+const PARAMS: &str = "loader: URLLoader, dispatcher: Dispatcher";
+
+#[test]
+fn compatible_listeners() {
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("progress", onProgress);"#), Vec::<i32>::new());
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("progress", onEvent);"#), Vec::<i32>::new());
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.removeEventListener("complete", onEvent);"#), Vec::<i32>::new());
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("complete", function(event: Event): void {});"#), Vec::<i32>::new());
+
+    // Events of interfaces are inherited.
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("close", onEvent);"#), Vec::<i32>::new());
+}
+
+#[test]
+fn incompatible_listeners() {
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("complete", onProgress);"#), vec![WhackDiagnosticKind::IncompatibleEventListener.id()]);
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.removeEventListener("close", onProgress);"#), vec![WhackDiagnosticKind::IncompatibleEventListener.id()]);
+    assert_eq!(
+        verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("complete", function(event: ProgressEvent): void {});"#),
+        vec![WhackDiagnosticKind::IncompatibleEventListener.id()],
+    );
+}
+
+#[test]
+fn unknown_event_name() {
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"loader.addEventListener("open", onEvent);"#), vec![WhackDiagnosticKind::UnknownEventName.id()]);
+
+    // Types without events and event names that are not constant are not checked.
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"dispatcher.addEventListener("open", onProgress);"#), Vec::<i32>::new());
+    assert_eq!(verify_body_with_files(FILES, PRELUDE, PARAMS, r#"var name: String = "open"; loader.addEventListener(name, onProgress);"#), Vec::<i32>::new());
+}
+
+#[test]
+fn messages() {
+    let source = r#"
+        package com.x {
+            public function f(loader: URLLoader): void {
+                loader.addEventListener("open", null);
+                loader.addEventListener("complete", function(event: ProgressEvent): void {});
+            }
+        }
+    "#;
+    let messages = verify_files(&[("Events.as", EVENTS), ("source.as", source)], Default::default(), |diagnostics| {
+        diagnostics.iter().map(|d| WhackDiagnostic(d).format_message_english()).collect::<Vec<_>>()
+    });
+    assert_eq!(messages, vec![
+        "Unknown event open for com.x.URLLoader.".to_owned(),
+        "Listener parameter of type com.x.ProgressEvent is incompatible with event complete of type com.x.Event.".to_owned(),
+    ]);
+}
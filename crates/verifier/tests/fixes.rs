@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+/// A fix as its title, whether it is machine-applicable
+/// and the source text after applying its edits.
+type AppliedFix = (String, bool, String);
+
+/// Verifies a source and returns the fixes of each diagnostic.
+fn fixes(source: &str) -> Vec<(i32, Vec<AppliedFix>)> {
+    verify_files(&[("source.as", source)], Default::default(), |diagnostics| {
+        diagnostics.iter().map(|d| {
+            let fixes = WhackDiagnostic(d).fixes().iter().map(|fix| {
+                let mut edits = fix.edits.clone();
+                edits.sort_by_key(|edit| std::cmp::Reverse(edit.location.first_offset()));
+                let mut text = source.to_owned();
+                for edit in edits.iter() {
+                    text.replace_range(edit.location.first_offset()..edit.location.last_offset(), &edit.replacement);
+                }
+                (fix.title.clone(), fix.is_machine_applicable(), text)
+            }).collect();
+            (WhackDiagnostic(d).id(), fixes)
+        }).collect()
+    })
+}
+
+#[test]
+fn add_override() {
+    let source = r#"
+        package {
+            public class A {
+                public function f(): void {}
+            }
+            public class B extends A {
+                public function f(): void {}
+            }
+        }
+    "#;
+    assert_eq!(fixes(source), vec![(WhackDiagnosticKind::ShadowingDefinitionInBaseClass.id(), vec![(
+        "Add 'override'".to_owned(),
+        true,
+        source.replace("B extends A {\n                public function", "B extends A {\n                public override function"),
+    )])]);
+}
+
+#[test]
+fn remove_override() {
+    let source = r#"
+        package {
+            public class A {
+                override public function f(): void {}
+            }
+        }
+    "#;
+    assert_eq!(fixes(source), vec![(WhackDiagnosticKind::MustOverrideAMethod.id(), vec![(
+        "Remove 'override'".to_owned(),
+        false,
+        source.replace("override public", "public"),
+    )])]);
+}
+
+#[test]
+fn did_you_mean() {
+    let source = r#"
+        package {
+            public function f(): void {
+                var counter: Number = 0;
+                countre++;
+            }
+        }
+    "#;
+    assert_eq!(fixes(source), vec![(WhackDiagnosticKind::UndefinedProperty.id(), vec![(
+        "Change to 'counter'".to_owned(),
+        false,
+        source.replace("countre++", "counter++"),
+    )])]);
+
+    // Names too far from any available name have no suggestion.
+    let source = r#"
+        package {
+            public function f(): void {
+                qwerty++;
+            }
+        }
+    "#;
+    assert_eq!(fixes(source), vec![(WhackDiagnosticKind::UndefinedProperty.id(), vec![])]);
+}
+
+#[test]
+fn declare_type() {
+    let source = r#"
+        package {
+            public function f(): void {
+                var x;
+                trace(x);
+            }
+        }
+    "#;
+    assert_eq!(fixes(source), vec![(WhackDiagnosticKind::VariableHasNoTypeAnnotation.id(), vec![(
+        "Declare type '*'".to_owned(),
+        false,
+        source.replace("var x;", "var x:*;"),
+    )])]);
+}
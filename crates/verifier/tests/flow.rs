@@ -0,0 +1,67 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+/// Nullable types have no properties of their own.
+const NULLABLE: i32 = WhackDiagnosticKind::UndefinedPropertyWithStaticType as i32;
+const NOT_ASSIGNED: i32 = WhackDiagnosticKind::VariableNotDefinitelyAssigned as i32;
+
+/// `Box` has a `size: Number` property.
+const PRELUDE: &str = r#"
+    public class Box {
+        public var size: Number = 0;
+    }
+"#;
+
+const PARAMS: &str = "c: Boolean, b: ?Box, o: Object, n: Box!";
+
+#[test]
+fn nullable_access() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "trace(b.size);"), vec![NULLABLE]);
+}
+
+#[test]
+fn narrowing_to_non_null() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (b != null) { trace(b.size); }"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (b) { trace(b.size); }"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (b == null) { trace(b.size); }"), vec![NULLABLE]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (b == null) { trace(0); } else { trace(b.size); }"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var s: Number = b != null && b.size;"), Vec::<i32>::new());
+
+    // The rest of the list is narrowed after an early return.
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (b == null) { return; } trace(b.size);"), Vec::<i32>::new());
+}
+
+#[test]
+fn assignment_cancels_narrowing() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (b != null) { b = null; trace(b.size); }"), vec![NULLABLE]);
+}
+
+#[test]
+fn narrowing_to_type() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "if (o is Box) { var s: Number = o.size; }"), Vec::<i32>::new());
+}
+
+#[test]
+fn definite_assignment() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; trace(x);"), vec![NOT_ASSIGNED]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; x = n; trace(x);"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; if (c) { x = n; } trace(x);"), vec![NOT_ASSIGNED]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; if (c) { x = n; } else { x = n; } trace(x);"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; if (c) { x = n; } else { throw new Error(); } trace(x);"), Vec::<i32>::new());
+
+    // Types with a default value need not be assigned.
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Number; var y: ?Box; trace(x, y);"), Vec::<i32>::new());
+}
+
+#[test]
+fn definite_assignment_through_loops() {
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; while (true) { x = n; break; } trace(x);"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; while (c) { x = n; } trace(x);"), vec![NOT_ASSIGNED]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; while (true) { if (c) { break; } x = n; } trace(x);"), vec![NOT_ASSIGNED]);
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; do { x = n; } while (c); trace(x);"), Vec::<i32>::new());
+
+    // A `continue` statement reaches the loop condition without the assignment.
+    assert_eq!(verify_body_with(PRELUDE, PARAMS, "var x: Box!; do { if (c) { continue; } x = n; } while (c); trace(x);"), vec![NOT_ASSIGNED]);
+}
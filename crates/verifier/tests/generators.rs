@@ -0,0 +1,66 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const IMPLICIT_COERCION: i32 = WhackDiagnosticKind::ImplicitCoercionToUnrelatedType as i32;
+
+/// The `numbers()` generator, whose result type is inferred as
+/// `Generator.<Number>` from the yielded values, and the `texts()`
+/// generator, whose result type is `Iterator.<String>`.
+const PRELUDE: &str = r#"
+    public function numbers(c: Boolean) {
+        yield 1;
+        if (c) {
+            yield 2;
+        }
+    }
+    public function texts(): Iterator.<String> {
+        yield "a";
+    }
+"#;
+
+#[test]
+fn declared_generator_types() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function g(): Generator.<Number> {
+                yield 1;
+                yield "x";
+            }
+            public function h(): Iterator.<Number> {
+                yield 1;
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![IMPLICIT_COERCION]);
+}
+
+#[test]
+fn return_type_must_be_generator() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function g(): Number {
+                yield 1;
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::ReturnTypeDeclarationMustBeGenerator.id()]);
+}
+
+#[test]
+fn undeclared_generator_types() {
+    assert_eq!(verify_body_with(PRELUDE, "", "var g: Generator.<Number> = numbers(true);"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, "", "var g: Generator.<String> = numbers(true);"), vec![IMPLICIT_COERCION]);
+
+    // The yielded type of a function expression is inferred from the yielded values.
+    assert_eq!(verify_body_with(PRELUDE, "", "const g = function() { yield 1; yield 2; };"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, "", "const g = function() { yield 1; yield new Error(); };"), vec![IMPLICIT_COERCION]);
+}
+
+#[test]
+fn for_each_over_iterators() {
+    assert_eq!(verify_body_with(PRELUDE, "", "for each (var n: Number in numbers(true)) { trace(n); }"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, "", "for each (var s: String in texts()) { trace(s); }"), Vec::<i32>::new());
+    assert_eq!(verify_body_with(PRELUDE, "", "for each (var s: Error in texts()) { trace(s); }"), vec![WhackDiagnosticKind::ExpectedToIterateType.id()]);
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+#[test]
+fn valid_labels() {
+    assert_eq!(verify_body("a: while (c) { continue a; }"), Vec::<i32>::new());
+    assert_eq!(verify_body("a: while (c) { b: while (c) { break a; } }"), Vec::<i32>::new());
+    assert_eq!(verify_body("a: { if (c) { break a; } trace(1); }"), Vec::<i32>::new());
+    assert_eq!(verify_body("switch (1) { case 1: break; }"), Vec::<i32>::new());
+
+    // Sibling statements may reuse a label.
+    assert_eq!(verify_body("a: while (c) { trace(1); } a: while (c) { trace(2); }"), Vec::<i32>::new());
+}
+
+#[test]
+fn duplicate_label() {
+    assert_eq!(verify_body("a: { a: { break a; } }"), vec![WhackDiagnosticKind::DuplicateLabel.id()]);
+    assert_eq!(verify_body("a: while (c) { if (c) { a: { break a; } } }"), vec![WhackDiagnosticKind::DuplicateLabel.id()]);
+    assert_eq!(messages("package { public function f(): void { a: { a: { break a; } } } }"), vec!["Duplicate label: a.".to_owned()]);
+}
+
+#[test]
+fn invalid_jumps() {
+    assert_eq!(messages("package { public function f(): void { a: { break b; } } }"), vec!["Undefined label 'b'.".to_owned()]);
+    assert_eq!(messages("package { public function f(): void { a: { continue a; } } }"), vec!["Illegal continue statement.".to_owned()]);
+    assert_eq!(messages("package { public function f(): void { break; } }"), vec!["Illegal break statement.".to_owned()]);
+    assert_eq!(messages("package { public function f(): void { continue; } }"), vec!["Illegal continue statement.".to_owned()]);
+}
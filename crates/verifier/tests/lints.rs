@@ -0,0 +1,83 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const UNREACHABLE: &str = r#"
+    package {
+        public function f(): void {
+            return;
+            trace("never");
+        }
+    }
+"#;
+
+fn verify_with_levels(source: &str, default_level: WarningLevel, levels: &[(WhackDiagnosticKind, WarningLevel)]) -> Vec<(i32, bool)> {
+    let mut warnings = CompilerWarningOptions::default();
+    warnings.default_level = default_level;
+    warnings.levels.extend(levels.iter().map(|(kind, level)| (kind.id(), *level)));
+    let compiler_options = CompilerOptions {
+        warnings,
+        ..Default::default()
+    };
+    verify_files(&[("source.as", source)], compiler_options, |diagnostics| {
+        diagnostics.iter().map(|d| (WhackDiagnostic(d).id(), d.is_warning())).collect()
+    })
+}
+
+#[test]
+fn warnings_are_reported_by_default() {
+    let diagnostics = verify_with_levels(UNREACHABLE, WarningLevel::Warn, &[]);
+    assert_eq!(diagnostics, vec![(WhackDiagnosticKind::UnreachableCode.id(), true)]);
+}
+
+#[test]
+fn allowed_warning() {
+    let diagnostics = verify_with_levels(UNREACHABLE, WarningLevel::Warn, &[(WhackDiagnosticKind::UnreachableCode, WarningLevel::Allow)]);
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn denied_warnings() {
+    let diagnostics = verify_with_levels(UNREACHABLE, WarningLevel::Deny, &[]);
+    assert_eq!(diagnostics, vec![(WhackDiagnosticKind::UnreachableCode.id(), false)]);
+}
+
+#[test]
+fn individual_level_overrides_default_level() {
+    let diagnostics = verify_with_levels(UNREACHABLE, WarningLevel::Deny, &[(WhackDiagnosticKind::UnreachableCode, WarningLevel::Warn)]);
+    assert_eq!(diagnostics, vec![(WhackDiagnosticKind::UnreachableCode.id(), true)]);
+}
+
+#[test]
+fn allow_metadata() {
+    let ids = diagnostic_ids(r#"
+        package {
+            [Allow("UnreachableCode")]
+            public function f(): void {
+                return;
+                trace("never");
+            }
+
+            [Allow("2163")]
+            public function g(): void {
+                return;
+                trace("never");
+            }
+
+            public function h(): void {
+                return;
+                trace("never");
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::UnreachableCode.id()]);
+}
+
+#[test]
+fn diagnostic_names() {
+    assert_eq!(WhackDiagnosticKind::from_name("UnreachableCode").map(|kind| kind.id()), Some(WhackDiagnosticKind::UnreachableCode.id()));
+    assert!(WhackDiagnosticKind::from_name("Unknown").is_none());
+    assert!(WhackDiagnosticKind::Unused.is_warning());
+    assert!(!WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.is_warning());
+}
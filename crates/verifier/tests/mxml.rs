@@ -0,0 +1,114 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+const BOX: &str = r#"
+    package com.ui {
+        public class Box {
+            public var width: Number = 0;
+            public var label: String = "";
+        }
+    }
+"#;
+
+/// Verifies a MXML component at `/src/com/app/Main.mxml`
+/// alongside the `com.ui.Box` class.
+fn verify_main(mxml: &str) -> Vec<i32> {
+    let compiler_options = CompilerOptions {
+        source_path: vec!["/src".to_owned()],
+        ..Default::default()
+    };
+    verify_files(&[("/src/com/ui/Box.as", BOX), ("/src/com/app/Main.mxml", mxml)], compiler_options, ids)
+}
+
+#[test]
+fn component_is_verified() {
+    let ids = verify_main(r#"<?xml version="1.0"?>
+<ui:Box xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:ui="com.ui.*" width="10" label="main">
+    <fx:Script><![CDATA[
+        public function half(): Number {
+            return width / 2;
+        }
+    ]]></fx:Script>
+</ui:Box>"#);
+    assert_eq!(ids, Vec::<i32>::new());
+}
+
+#[test]
+fn script_errors_are_reported() {
+    let ids = verify_main(r#"<?xml version="1.0"?>
+<ui:Box xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:ui="com.ui.*">
+    <fx:Script><![CDATA[
+        public function f(): Error {
+            return width;
+        }
+    ]]></fx:Script>
+</ui:Box>"#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id()]);
+}
+
+#[test]
+fn attribute_values_are_checked() {
+    let ids = verify_main(r#"<?xml version="1.0"?>
+<ui:Box xmlns:ui="com.ui.*" width="wide" height="10"/>"#);
+    assert_eq!(ids, vec![
+        WhackDiagnosticKind::MxmlValueNotConvertible.id(),
+        WhackDiagnosticKind::UnknownMxmlAttribute.id(),
+    ]);
+}
+
+#[test]
+fn unknown_namespace() {
+    let ids = verify_main(r#"<?xml version="1.0"?>
+<q:Box xmlns:q="http://example.com/q"/>"#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::UnknownMxmlNamespace.id()]);
+}
+
+#[test]
+fn source_outside_source_path() {
+    let ids = verify_files(&[("/src/com/ui/Box.as", BOX), ("/other/Main.mxml", r#"<ui:Box xmlns:ui="com.ui.*"/>"#)], CompilerOptions {
+        source_path: vec!["/src".to_owned()],
+        ..Default::default()
+    }, ids);
+    assert_eq!(ids, vec![WhackDiagnosticKind::MxmlSourceOutsideSourcePath.id()]);
+}
+
+#[test]
+fn component_is_referenced_from_actionscript() {
+    let user = r#"
+        package com.other {
+            import com.app.Main;
+
+            public class User {
+                public function use(): void {
+                    var main: Main = new Main();
+                    main.width = 10;
+                    var n: Number = main.half();
+                }
+            }
+        }
+    "#;
+    let main = r#"<?xml version="1.0"?>
+<ui:Box xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:ui="com.ui.*">
+    <fx:Script><![CDATA[
+        public function half(): Number {
+            return width / 2;
+        }
+    ]]></fx:Script>
+</ui:Box>"#;
+    let ids = verify_files(&[("/src/com/other/User.as", user), ("/src/com/ui/Box.as", BOX), ("/src/com/app/Main.mxml", main)], CompilerOptions {
+        source_path: vec!["/src".to_owned()],
+        ..Default::default()
+    }, ids);
+    assert_eq!(ids, Vec::<i32>::new());
+}
+
+#[test]
+fn source_name_is_not_an_identifier() {
+    let ids = verify_files(&[("/src/com/ui/Box.as", BOX), ("/src/com/my-app/Main.mxml", r#"<ui:Box xmlns:ui="com.ui.*"/>"#)], CompilerOptions {
+        source_path: vec!["/src".to_owned()],
+        ..Default::default()
+    }, ids);
+    assert_eq!(ids, vec![WhackDiagnosticKind::MxmlSourceNameIsNotAnIdentifier.id()]);
+}
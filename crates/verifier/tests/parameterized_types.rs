@@ -0,0 +1,21 @@
+mod common;
+
+use common::*;
+
+/// A parameterized type referenced without type arguments, such as
+/// `Vector`, is a value of type `Class`.
+#[test]
+fn parameterized_type_without_arguments() {
+    assert_eq!(messages(r#"
+        package {
+            public function f(): void {
+                var v = Vector;
+                v.length;
+                var c: Class = Vector;
+                trace(c);
+            }
+        }
+    "#), vec![
+        "Access of possibly undefined property length through a reference with static type Class.".to_owned(),
+    ]);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::*;
+use whackengine_verifier::ns::*;
+
+#[test]
+fn no_warning_for_unannotated_functions() {
+    let messages = messages(r#"
+        package {
+            public function g(): void {
+                const k = function(c: Boolean) {
+                    if (c) {
+                        return null;
+                    }
+                    return new Error();
+                };
+                var s: String = f(true);
+                var v: Number = h();
+            }
+            public function f(c: Boolean) {
+                if (c) {
+                    return null;
+                }
+                return new Error();
+            }
+            public function h() {
+                trace("h");
+            }
+        }
+    "#);
+    assert_eq!(messages, vec![
+        "Implicit coercion of a value of type Error to an unrelated type String.",
+        "Implicit coercion of a value of type void to an unrelated type Number.",
+    ]);
+}
+
+#[test]
+fn unannotated_static_and_private_methods() {
+    let messages = messages(r#"
+        package {
+            public function g(): void {
+                var n: String = C.count();
+            }
+            public class C {
+                public static function count() {
+                    return C.helper();
+                }
+                private static function helper() {
+                    return 1;
+                }
+            }
+        }
+    "#);
+    assert_eq!(messages, vec!["Implicit coercion of a value of type Number to an unrelated type String."]);
+}
+
+#[test]
+fn recursive_function_definitions() {
+    let messages = messages(r#"
+        package {
+            public function g(): void {
+                var e: Error = factorial(3);
+            }
+            public function factorial(n: Number) {
+                if (n <= 1) {
+                    return 1;
+                }
+                return n * factorial(n - 1);
+            }
+        }
+    "#);
+    assert_eq!(messages, vec!["Implicit coercion of a value of type Number to an unrelated type Error."]);
+}
+
+#[test]
+fn functions_referenced_from_directives_are_not_inferred() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public const x = f();
+            public function f() {
+                return 1;
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::ReturnValueHasNoTypeDeclaration.id()]);
+}
+
+#[test]
+fn unrelated_returned_values() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function g(): void {
+                const f = function(c: Boolean) {
+                    if (c) {
+                        return 1;
+                    }
+                    return new Error();
+                };
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id()]);
+}
+
+#[test]
+fn missing_returned_value() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function g(): void {
+                const f = function(c: Boolean) {
+                    if (c) {
+                        return 1;
+                    }
+                    return;
+                };
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::ReturnValueMustBeSpecified.id()]);
+}
+
+#[test]
+fn not_all_code_paths_return_the_inferred_type() {
+    let ids = diagnostic_ids(r#"
+        package {
+            public function g(): void {
+                const f = function(c: Boolean) {
+                    if (c) {
+                        return 1;
+                    }
+                };
+            }
+        }
+    "#);
+    assert_eq!(ids, vec![WhackDiagnosticKind::NotAllCodePathsReturnAValue.id()]);
+}
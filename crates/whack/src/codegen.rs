@@ -0,0 +1,5 @@
+mod js_codegen;
+pub use js_codegen::*;
+
+mod js_exp;
+pub use js_exp::*;
@@ -0,0 +1,1131 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use whackengine_verifier::ns::*;
+use crate::packagemanager::*;
+
+/// JavaScript code generator.
+///
+/// `JsCodegen` generates a classic script for a Whack package from
+/// its verified sources, reading the semantic model through
+/// `host.node_mapping()`. Scripts from a directed acyclic graph are
+/// expected to be loaded in ascending order, as they share
+/// the global lexical environment.
+///
+/// # Runtime
+///
+/// Generated code relies on the ActionCore runtime, available as the
+/// global `$core` object. Instances of classes are arrays whose first
+/// two slots are the constructor and the dynamic properties, followed by
+/// the instance variables in the order given by `codegen_slot_vars()`;
+/// a `[whack_external]` class determines its number of slots through
+/// `codegen_slots()`.
+///
+/// A `[Bindable]` instance variable is exposed as a prototype property
+/// whose setter is wrapped by `$core.bindablesetter(setter, name, event)`,
+/// which is expected to call the setter and dispatch the named event
+/// when the value changes. The setter of a `[Bindable]` accessor
+/// is wrapped likewise unless the meta-data specifies an event,
+/// in which case the class is responsible for dispatching it.
+///
+/// A `[Embed]` constant is initialized by `$core.embedasset(url, mimeType, type)`,
+/// where `url` is relative to the script and `type` is either `Class`
+/// or `ByteArray`; the assets are listed by `embedded_assets()` so that
+/// they are copied next to the scripts.
+///
+/// A `default xml namespace` statement assigns a `Namespace` to the
+/// function-scoped `$$dxns` local, which is initialized to the empty
+/// namespace at the start of a function containing such a statement;
+/// unqualified XML element names of that function are looked up as
+/// `$core.qname($$dxns, name)`, and XML literals are parsed by
+/// `$core.newxml(markup, $$dxns)` or `$core.newxmllist(markup, $$dxns)`.
+///
+/// Definitions accompanied by `[whack_external]` meta-data are only
+/// verified and not compiled; a reference to such a type uses the
+/// local given by `codegen_local()` if any.
+pub struct JsCodegen {
+    pub(crate) host: Rc<Database>,
+    out: String,
+    pub(crate) indent: usize,
+    line_prefix: String,
+    /// Classes or enums whose blocks are being generated.
+    pub(crate) class_stack: Vec<Entity>,
+    /// JavaScript locals standing for `with` and filter scopes.
+    pub(crate) scope_locals: HashMap<Entity, String>,
+    /// JavaScript locals standing for optional chaining bases.
+    pub(crate) opt_chaining_locals: Vec<String>,
+    temp_counter: usize,
+    /// Embedded assets as (path, URL) pairs.
+    embedded_assets: Vec<(String, String)>,
+    /// Whether the `$$dxns` local is declared by the function
+    /// being generated, for each function (innermost last).
+    dxns_declared: Vec<bool>,
+}
+
+impl JsCodegen {
+    /// Number of slots that precede the instance variables of
+    /// an instance (the constructor and the dynamic properties).
+    pub const FIXED_SLOTS: usize = 2;
+
+    pub fn new(host: &Rc<Database>) -> Self {
+        Self {
+            host: host.clone(),
+            out: String::new(),
+            indent: 0,
+            line_prefix: String::new(),
+            class_stack: vec![],
+            scope_locals: HashMap::new(),
+            opt_chaining_locals: vec![],
+            temp_counter: 0,
+            embedded_assets: vec![],
+            dxns_declared: vec![false],
+        }
+    }
+
+    /// Assets embedded by the generated scripts as (path, URL) pairs,
+    /// where the URL is relative to the scripts.
+    pub fn embedded_assets(&self) -> &[(String, String)] {
+        &self.embedded_assets
+    }
+
+    /// Generates the script of a verified Whack package.
+    pub fn generate_package(&mut self, pckg: &WhackPackage, verifier: &Verifier) -> String {
+        self.out.clear();
+        self.indent = 0;
+        self.temp_counter = 0;
+
+        let name = pckg.manifest.package.as_ref().map(|p| p.name.clone()).unwrap_or_default();
+        self.line(&format!("// Generated by Whack from the {name} package."));
+        self.line("\"use strict\";");
+
+        // MXML documents are generated from the programs they were desugared into.
+        let mut programs: Vec<Rc<Program>> = vec![];
+        for source in pckg.sources.iter() {
+            match source {
+                WhackSource::As3(program) => {
+                    programs.push(program.clone());
+                },
+                WhackSource::Mxml(mxml) => {
+                    if let Some(program) = verifier.mxml_program(&mxml) {
+                        programs.push(program);
+                    }
+                },
+            }
+        }
+
+        let mut blocks: Vec<Rc<Block>> = vec![];
+        for program in programs.iter() {
+            Self::collect_package_blocks(&program.packages, &program.directives, &mut blocks);
+        }
+
+        // Generate type definitions first, bases before subtypes.
+        let mut type_defns: Vec<(Entity, Rc<Directive>)> = vec![];
+        for block in blocks.iter() {
+            self.collect_type_definitions(&block.directives, &mut type_defns);
+        }
+        for program in programs.iter() {
+            self.collect_type_definitions(&program.directives, &mut type_defns);
+        }
+        for (entity, drtv) in self.order_type_definitions(type_defns) {
+            self.type_definition(&entity, &drtv);
+        }
+
+        // Package-level variables, functions and initialization code
+        for block in blocks.iter() {
+            self.directives(&block.directives);
+        }
+
+        // Top-level program code
+        for program in programs.iter() {
+            if program.directives.iter().all(Self::is_type_definition) {
+                continue;
+            }
+            self.line("{");
+            self.indent += 1;
+            self.directives(&program.directives);
+            self.indent -= 1;
+            self.line("}");
+        }
+
+        std::mem::take(&mut self.out)
+    }
+
+    fn collect_package_blocks(packages: &[Rc<PackageDefinition>], directives: &[Rc<Directive>], output: &mut Vec<Rc<Block>>) {
+        for pckgdefn in packages.iter() {
+            output.push(pckgdefn.block.clone());
+        }
+        for drtv in directives.iter() {
+            if let Directive::IncludeDirective(incdrtv) = drtv.as_ref() {
+                Self::collect_package_blocks(&incdrtv.nested_packages, &incdrtv.nested_directives, output);
+            }
+        }
+    }
+
+    fn is_type_definition(drtv: &Rc<Directive>) -> bool {
+        matches!(drtv.as_ref(), Directive::ClassDefinition(_) | Directive::EnumDefinition(_) | Directive::InterfaceDefinition(_))
+    }
+
+    fn collect_type_definitions(&self, list: &[Rc<Directive>], output: &mut Vec<(Entity, Rc<Directive>)>) {
+        for drtv in list.iter() {
+            match drtv.as_ref() {
+                Directive::ClassDefinition(_) |
+                Directive::EnumDefinition(_) |
+                Directive::InterfaceDefinition(_) => {
+                    if let Some(entity) = self.host.node_mapping().get(drtv) {
+                        output.push((entity, drtv.clone()));
+                    }
+                },
+                Directive::ConfigurationDirective(cfgdrtv) => {
+                    if self.is_enabled_configuration(drtv) {
+                        self.collect_type_definitions(std::slice::from_ref(&cfgdrtv.directive), output);
+                    }
+                },
+                Directive::Block(block) => {
+                    self.collect_type_definitions(&block.directives, output);
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    self.collect_type_definitions(&incdrtv.nested_directives, output);
+                },
+                Directive::DirectiveInjection(inj) => {
+                    self.collect_type_definitions(inj.directives.borrow().as_ref(), output);
+                },
+                _ => {},
+            }
+        }
+    }
+
+    /// Orders type definitions so that base classes and interfaces
+    /// from the same package are generated first.
+    fn order_type_definitions(&self, list: Vec<(Entity, Rc<Directive>)>) -> Vec<(Entity, Rc<Directive>)> {
+        let by_entity: HashMap<Entity, Rc<Directive>> = list.iter().cloned().collect();
+        let mut visited: HashSet<Entity> = HashSet::new();
+        let mut output: Vec<(Entity, Rc<Directive>)> = vec![];
+        for (entity, _) in list.iter() {
+            self.order_type_definition(entity, &by_entity, &mut visited, &mut output);
+        }
+        output
+    }
+
+    fn order_type_definition(&self, entity: &Entity, by_entity: &HashMap<Entity, Rc<Directive>>, visited: &mut HashSet<Entity>, output: &mut Vec<(Entity, Rc<Directive>)>) {
+        let Some(drtv) = by_entity.get(entity) else {
+            return;
+        };
+        if !visited.insert(entity.clone()) {
+            return;
+        }
+        let mut dependencies: Vec<Entity> = vec![];
+        if entity.is::<ClassType>() {
+            dependencies.extend(entity.extends_class(&self.host));
+            dependencies.extend(entity.implements(&self.host).iter());
+        } else if entity.is::<InterfaceType>() {
+            dependencies.extend(entity.extends_interfaces(&self.host).iter());
+        }
+        for dependency in dependencies.iter() {
+            self.order_type_definition(&Self::type_origin(dependency), by_entity, visited, output);
+        }
+        output.push((entity.clone(), drtv.clone()));
+    }
+
+    fn type_definition(&mut self, entity: &Entity, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                self.class_definition(entity, &defn.block.directives);
+            },
+            Directive::EnumDefinition(defn) => {
+                self.enum_definition(entity, defn);
+            },
+            Directive::InterfaceDefinition(_) => {
+                self.interface_definition(entity);
+            },
+            _ => {},
+        }
+    }
+
+    fn class_definition(&mut self, class: &Entity, block: &[Rc<Directive>]) {
+        if class.is_external() {
+            return;
+        }
+        let name = self.definition_name(class);
+        let base = class.extends_class(&self.host);
+
+        self.class_stack.push(class.clone());
+        let members = self.class_members(block);
+
+        self.line(&format!("const {name} = $core.defineclass({}, {{", Self::string_literal(&class.fully_qualified_name())));
+        self.indent += 1;
+        let base_code = base.as_ref().map(|b| self.type_reference(b)).unwrap_or("null".to_owned());
+        self.line(&format!("baseclass: {base_code},"));
+        let interfaces = class.implements(&self.host).iter().map(|itrfc| self.type_reference(&itrfc)).collect::<Vec<_>>();
+        self.line(&format!("interfaces: [{}],", interfaces.join(", ")));
+        self.line(&format!("final: {},", class.is_final()));
+        self.line(&format!("dynamic: {},", class.is_dynamic()));
+        self.line(&format!("abstract: {},", class.is_abstract()));
+        self.line(&format!("slots: {},", self.instance_slots(class)));
+        let varslots = class.codegen_slot_vars().iter().map(|slot| Self::string_literal(&slot.name().to_string())).collect::<Vec<_>>();
+        self.line(&format!("varslots: [{}],", varslots.join(", ")));
+
+        // Constructor, initializing instance variables first.
+        let mut prologue: Vec<String> = vec![];
+        self.indent += 1;
+        for (slot, init) in members.instance_vars.iter() {
+            let Some(index) = self.instance_slot_index(slot) else {
+                continue;
+            };
+            let value = self.variable_initial_value(slot, init.as_ref());
+            prologue.push(format!("this[{index}] = {value};"));
+        }
+        self.indent -= 1;
+        let ctor_code = if let Some(common) = members.ctor.as_ref() {
+            let calls_super = Self::has_super_statement(common);
+            if base.is_some() && !calls_super {
+                prologue.push(format!("$core.callsuperctor({name}, this);"));
+            }
+            self.function_code(common, "function", prologue)
+        } else {
+            if base.is_some() {
+                prologue.push(format!("$core.callsuperctor({name}, this);"));
+            }
+            self.lines_function_code("function()", prologue)
+        };
+        self.line(&format!("ctor: {ctor_code},"));
+
+        self.property_table("staticproperties", &members.static_properties);
+        self.property_table("prototypeproperties", &members.prototype_properties);
+
+        self.indent -= 1;
+        self.line("});");
+
+        self.static_initialization(&name, &members);
+        self.class_stack.pop();
+    }
+
+    fn enum_definition(&mut self, enum_type: &Entity, defn: &EnumDefinition) {
+        let name = self.definition_name(enum_type);
+        self.class_stack.push(enum_type.clone());
+        let mut members = self.class_members(&defn.block.directives);
+
+        // Members are not ordinary static variables.
+        let member_slots = enum_type.enum_member_slot_mapping();
+        let member_slot_list = member_slots.borrow().values().cloned().collect::<Vec<_>>();
+        members.static_vars.retain(|(slot, _)| !member_slot_list.contains(slot));
+        members.static_properties.retain(|(_, prop)| !prop.var_slot.as_ref().map(|slot| member_slot_list.contains(slot)).unwrap_or(false));
+
+        let mut member_list: Vec<(String, String, Number)> = vec![];
+        for (string, slot) in member_slots.borrow().iter() {
+            let Some(number) = enum_type.enum_member_number_mapping().get(string) else {
+                continue;
+            };
+            member_list.push((slot.name().to_string(), string.clone(), number));
+        }
+        member_list.sort_by(|a, b| Self::number_literal(&a.2).cmp(&Self::number_literal(&b.2)).then(a.0.cmp(&b.0)));
+
+        self.line(&format!("const {name} = $core.defineenum({}, {{", Self::string_literal(&enum_type.fully_qualified_name())));
+        self.indent += 1;
+        self.line(&format!("set: {},", defn.is_set));
+        self.line("members: [");
+        self.indent += 1;
+        for (slot_name, string, number) in member_list.iter() {
+            self.line(&format!("[{}, {}, {}],", Self::string_literal(slot_name), Self::string_literal(string), Self::number_literal(number)));
+        }
+        self.indent -= 1;
+        self.line("],");
+        self.property_table("staticproperties", &members.static_properties);
+        self.property_table("prototypeproperties", &members.prototype_properties);
+        self.indent -= 1;
+        self.line("});");
+
+        self.static_initialization(&name, &members);
+        self.class_stack.pop();
+    }
+
+    fn interface_definition(&mut self, itrfc: &Entity) {
+        if itrfc.is_external() {
+            return;
+        }
+        let name = self.definition_name(itrfc);
+        let base_interfaces = itrfc.extends_interfaces(&self.host).iter().map(|base| self.type_reference(&base)).collect::<Vec<_>>();
+        self.line(&format!("const {name} = $core.defineinterface({}, {{", Self::string_literal(&itrfc.fully_qualified_name())));
+        self.indent += 1;
+        self.line(&format!("baseinterfaces: [{}],", base_interfaces.join(", ")));
+        self.indent -= 1;
+        self.line("});");
+    }
+
+    fn class_members(&mut self, block: &[Rc<Directive>]) -> JsClassMembers {
+        let mut members = JsClassMembers::default();
+        self.collect_class_members(block, &mut members);
+        members
+    }
+
+    fn collect_class_members(&mut self, block: &[Rc<Directive>], members: &mut JsClassMembers) {
+        for drtv in block.iter() {
+            match drtv.as_ref() {
+                Directive::VariableDefinition(defn) => {
+                    let is_static = Attribute::find_static(&defn.attributes).is_some();
+                    for binding in defn.bindings.iter() {
+                        let Some(slot) = self.host.node_mapping().get(&binding.destructuring.destructuring) else {
+                            continue;
+                        };
+                        // Class variables are not declared through destructuring patterns.
+                        if !slot.is::<VariableSlot>() || slot.is_external() || !matches!(binding.destructuring.destructuring.as_ref(), Expression::QualifiedIdentifier(_)) {
+                            continue;
+                        }
+                        if is_static {
+                            let property = members.static_property(&slot.name().to_string());
+                            property.var_slot = Some(slot.clone());
+                            members.static_vars.push((slot, binding.initializer.clone()));
+                        } else {
+                            if slot.bindable_event().is_some() {
+                                members.prototype_property(&slot.name().to_string()).var_slot = Some(slot.clone());
+                            }
+                            members.instance_vars.push((slot, binding.initializer.clone()));
+                        }
+                    }
+                },
+                Directive::FunctionDefinition(defn) => {
+                    let Some(slot) = self.host.node_mapping().get(drtv) else {
+                        continue;
+                    };
+                    if defn.is_constructor() {
+                        members.ctor = Some(defn.common.clone());
+                        continue;
+                    }
+                    if slot.is_external() || slot.is_native() || slot.is_abstract() || defn.common.body.is_none() {
+                        continue;
+                    }
+                    let key = slot.name().to_string();
+                    let property = if slot.is_static() { members.static_property(&key) } else { members.prototype_property(&key) };
+                    if defn.is_getter() {
+                        property.getter = Some(defn.common.clone());
+                    } else if defn.is_setter() {
+                        property.setter = Some(defn.common.clone());
+                        property.bindable_event = Self::implicit_bindable_event(&self.host, &slot);
+                    } else {
+                        property.method = Some(defn.common.clone());
+                    }
+                },
+                Directive::ConfigurationDirective(cfgdrtv) => {
+                    if self.is_enabled_configuration(drtv) {
+                        self.collect_class_members(std::slice::from_ref(&cfgdrtv.directive), members);
+                    }
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    self.collect_class_members(&incdrtv.nested_directives, members);
+                },
+                Directive::DirectiveInjection(inj) => {
+                    self.collect_class_members(inj.directives.borrow().as_ref(), members);
+                },
+                Directive::Block(block) => {
+                    self.collect_class_members(&block.directives, members);
+                },
+                Directive::ImportDirective(_) |
+                Directive::UseNamespaceDirective(_) |
+                Directive::NamespaceDefinition(_) |
+                Directive::TypeDefinition(_) |
+                Directive::EmptyStatement(_) |
+                Directive::Invalidated(_) => {},
+                _ => {
+                    members.static_statements.push(drtv.clone());
+                },
+            }
+        }
+    }
+
+    fn property_table(&mut self, field: &str, properties: &[(String, JsClassProperty)]) {
+        self.line(&format!("{field}: {{"));
+        self.indent += 1;
+        for (key, property) in properties.iter() {
+            let key = Self::string_literal(key);
+            if let Some(slot) = property.var_slot.as_ref() {
+                if let (Some(event), Some(index)) = (slot.bindable_event(), self.instance_slot_index(slot)) {
+                    let event = Self::string_literal(&event);
+                    self.line(&format!("{key}: {{ get: function() {{ return this[{index}]; }}, set: $core.bindablesetter(function(value) {{ this[{index}] = value; }}, {key}, {event}) }},"));
+                    continue;
+                }
+                let kind = if slot.read_only(&self.host) { "const" } else { "var" };
+                self.line(&format!("{key}: {{ {kind}: true }},"));
+                continue;
+            }
+            if let Some(method) = property.method.as_ref() {
+                let code = self.function_code(method, "function", vec![]);
+                self.line(&format!("{key}: {{ method: {code} }},"));
+                continue;
+            }
+            let mut fields: Vec<String> = vec![];
+            if let Some(getter) = property.getter.as_ref() {
+                fields.push(format!("get: {}", self.function_code(getter, "function", vec![])));
+            }
+            if let Some(setter) = property.setter.as_ref() {
+                let code = self.function_code(setter, "function", vec![]);
+                if let Some(event) = property.bindable_event.as_ref() {
+                    fields.push(format!("set: $core.bindablesetter({code}, {key}, {})", Self::string_literal(event)));
+                } else {
+                    fields.push(format!("set: {code}"));
+                }
+            }
+            self.line(&format!("{key}: {{ {} }},", fields.join(", ")));
+        }
+        self.indent -= 1;
+        self.line("},");
+    }
+
+    fn static_initialization(&mut self, name: &str, members: &JsClassMembers) {
+        for (slot, init) in members.static_vars.iter() {
+            let value = self.variable_initial_value(slot, init.as_ref());
+            self.line(&format!("$core.initproperty({name}, {}, {value});", Self::string_literal(&slot.name().to_string())));
+        }
+        self.directives(&members.static_statements);
+    }
+
+    /// Returns the event dispatched by the generated setter of a `[Bindable]`
+    /// accessor, which is only the case when the meta-data specifies no event.
+    fn implicit_bindable_event(host: &Database, setter: &Entity) -> Option<String> {
+        let virtual_var = setter.of_virtual_slot(host)?;
+        let event = virtual_var.bindable_event()?;
+        let implicit = virtual_var.metadata().iter().any(|m| m.name.0 == "Bindable" && m.entries.is_none());
+        if implicit { Some(event) } else { None }
+    }
+
+    fn has_super_statement(common: &Rc<FunctionCommon>) -> bool {
+        let Some(FunctionBody::Block(block)) = common.body.as_ref() else {
+            return false;
+        };
+        block.directives.iter().any(|drtv| matches!(drtv.as_ref(), Directive::SuperStatement(_)))
+    }
+
+    /// Returns the number of slots of an instance of a class.
+    pub(crate) fn instance_slots(&self, class: &Entity) -> usize {
+        let class = Self::type_origin(class);
+        if class.is_external() {
+            return class.codegen_slots();
+        }
+        let base_slots = class.extends_class(&self.host)
+            .map(|base| self.instance_slots(&base))
+            .unwrap_or(Self::FIXED_SLOTS);
+        base_slots + class.codegen_slot_vars().length()
+    }
+
+    /// Returns the index of an instance variable in its instance array,
+    /// or `None` if its class is external.
+    pub(crate) fn instance_slot_index(&self, slot: &Entity) -> Option<usize> {
+        let slot = if slot.is::<VariableSlotAfterSubstitution>() { slot.origin() } else { slot.clone() };
+        let class = slot.parent()?;
+        if !class.is::<ClassType>() || class.is_external() {
+            return None;
+        }
+        let index = class.codegen_slot_vars().index_of(&slot)?;
+        let base_slots = class.extends_class(&self.host)
+            .map(|base| self.instance_slots(&base))
+            .unwrap_or(Self::FIXED_SLOTS);
+        Some(base_slots + index)
+    }
+
+    pub(crate) fn type_origin(t: &Entity) -> Entity {
+        if t.is::<TypeAfterSubstitution>() { t.origin() } else { t.clone() }
+    }
+
+    /// Returns the JavaScript name of a package-level definition.
+    pub(crate) fn definition_name(&self, entity: &Entity) -> String {
+        if entity.is::<Type>() && entity.is_external() {
+            if let Some(local) = entity.codegen_local() {
+                return local;
+            }
+        }
+        let mut name = String::new();
+        for segment in entity.fully_qualified_name_list() {
+            name.push('$');
+            name.push_str(&segment.chars().map(|ch| if ch.is_alphanumeric() || ch == '_' || ch == '$' { ch } else { '_' }).collect::<String>());
+        }
+        name
+    }
+
+    /// Returns the JavaScript name of a local.
+    pub(crate) fn local_name(name: &str) -> String {
+        const RESERVED: [&str; 22] = [
+            "arguments", "await", "class", "const", "debugger", "enum", "eval",
+            "export", "extends", "implements", "import", "interface", "let",
+            "package", "private", "protected", "public", "static", "super",
+            "yield", "undefined", "globalThis",
+        ];
+        if RESERVED.contains(&name) { format!("{name}$") } else { name.to_owned() }
+    }
+
+    pub(crate) fn next_temp(&mut self) -> String {
+        self.temp_counter += 1;
+        format!("$$t{}", self.temp_counter)
+    }
+
+    pub(crate) fn is_enabled_configuration(&self, drtv: &Rc<Directive>) -> bool {
+        self.host.node_mapping().get(drtv).map(|cval| cval.is::<BooleanConstant>() && cval.boolean_value()).unwrap_or(false)
+    }
+
+    /// Whether unqualified XML names of the function being generated
+    /// are in the namespace held by the `$$dxns` local.
+    pub(crate) fn has_default_xml_namespace(&self) -> bool {
+        self.dxns_declared.last().copied().unwrap_or(false)
+    }
+
+    /// Whether a list of directives contains a `default xml namespace`
+    /// statement, excluding nested functions.
+    fn contains_default_xml_namespace(list: &[Rc<Directive>]) -> bool {
+        list.iter().any(Self::directive_contains_default_xml_namespace)
+    }
+
+    fn directive_contains_default_xml_namespace(drtv: &Rc<Directive>) -> bool {
+        match drtv.as_ref() {
+            Directive::DefaultXmlNamespaceStatement(_) => true,
+            Directive::Block(block) => Self::contains_default_xml_namespace(&block.directives),
+            Directive::LabeledStatement(labeled) => Self::directive_contains_default_xml_namespace(&labeled.substatement),
+            Directive::IfStatement(ifstmt) => {
+                Self::directive_contains_default_xml_namespace(&ifstmt.consequent)
+                    || ifstmt.alternative.as_ref().map(Self::directive_contains_default_xml_namespace).unwrap_or(false)
+            },
+            Directive::SwitchStatement(swstmt) => swstmt.cases.iter().any(|case| Self::contains_default_xml_namespace(&case.directives)),
+            Directive::SwitchTypeStatement(swstmt) => swstmt.cases.iter().any(|case| Self::contains_default_xml_namespace(&case.block.directives)),
+            Directive::DoStatement(dostmt) => Self::directive_contains_default_xml_namespace(&dostmt.body),
+            Directive::WhileStatement(whilestmt) => Self::directive_contains_default_xml_namespace(&whilestmt.body),
+            Directive::ForStatement(forstmt) => Self::directive_contains_default_xml_namespace(&forstmt.body),
+            Directive::ForInStatement(forstmt) => Self::directive_contains_default_xml_namespace(&forstmt.body),
+            Directive::WithStatement(withstmt) => Self::directive_contains_default_xml_namespace(&withstmt.body),
+            Directive::TryStatement(trystmt) => {
+                Self::contains_default_xml_namespace(&trystmt.block.directives)
+                    || trystmt.catch_clauses.iter().any(|catch_clause| Self::contains_default_xml_namespace(&catch_clause.block.directives))
+                    || trystmt.finally_clause.as_ref().map(|finally_clause| Self::contains_default_xml_namespace(&finally_clause.block.directives)).unwrap_or(false)
+            },
+            Directive::ConfigurationDirective(cfgdrtv) => Self::directive_contains_default_xml_namespace(&cfgdrtv.directive),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn line(&mut self, line: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        self.out.push_str(&std::mem::take(&mut self.line_prefix));
+        self.out.push_str(line);
+        self.out.push('\n');
+    }
+
+    pub(crate) fn indentation(&self) -> String {
+        "    ".repeat(self.indent)
+    }
+
+    /// Generates a function from a `FunctionCommon`, where `keyword` is
+    /// either `function` or a function keyword followed by a name.
+    pub(crate) fn function_code(&mut self, common: &Rc<FunctionCommon>, keyword: &str, mut prologue: Vec<String>) -> String {
+        self.indent += 1;
+        let mut params: Vec<String> = vec![];
+        for (i, param) in common.signature.parameters.iter().enumerate() {
+            let pattern = &param.destructuring.destructuring;
+            let name = if let Some(slot) = self.host.node_mapping().get(pattern).filter(|_| matches!(pattern.as_ref(), Expression::QualifiedIdentifier(_))) {
+                Self::local_name(&slot.name().local_name())
+            } else {
+                let name = format!("$$p{i}");
+                prologue.extend(self.declarative_destructuring(pattern, &name, "var"));
+                name
+            };
+            match param.kind {
+                ParameterKind::Required => params.push(name),
+                ParameterKind::Optional => {
+                    let default_value = param.default_value.as_ref().map(|exp| self.exp(exp)).unwrap_or("undefined".to_owned());
+                    params.push(format!("{name} = {default_value}"));
+                },
+                ParameterKind::Rest => params.push(format!("...{name}")),
+            }
+        }
+        self.indent -= 1;
+
+        // Generator functions are marked after the `function` keyword.
+        // Arrow functions are generated for an empty keyword.
+        let head = if keyword.is_empty() {
+            format!("({}) =>", params.join(", "))
+        } else if common.contains_yield {
+            format!("{}({})", keyword.replacen("function", "function*", 1), params.join(", "))
+        } else {
+            format!("{keyword}({})", params.join(", "))
+        };
+
+        let saved_out = std::mem::take(&mut self.out);
+        self.indent += 1;
+        let declares_dxns = matches!(common.body.as_ref(), Some(FunctionBody::Block(block)) if Self::contains_default_xml_namespace(&block.directives));
+        self.dxns_declared.push(declares_dxns);
+
+        // Asynchronous methods wrap the JavaScript Promise object
+        // into an ActionScript 3 Promise object.
+        if common.contains_await {
+            self.line("return $core.promise((async () => {");
+            self.indent += 1;
+        }
+        if declares_dxns {
+            let ns_type = self.type_reference(&self.host.namespace_type());
+            self.line(&format!("var $$dxns = $core.construct({ns_type}, \"\");"));
+        }
+        for line in prologue.iter() {
+            self.line(line);
+        }
+        match common.body.as_ref() {
+            Some(FunctionBody::Block(block)) => {
+                self.directives(&block.directives);
+            },
+            Some(FunctionBody::Expression(exp)) => {
+                let value = self.exp(exp);
+                self.line(&format!("return {value};"));
+            },
+            None => {},
+        }
+        if common.contains_await {
+            self.indent -= 1;
+            self.line("})());");
+        }
+
+        self.dxns_declared.pop();
+        self.indent -= 1;
+        let body = std::mem::replace(&mut self.out, saved_out);
+        format!("{head} {{\n{body}{}}}", self.indentation())
+    }
+
+    /// Generates a function consisting of given lines.
+    fn lines_function_code(&mut self, head: &str, lines: Vec<String>) -> String {
+        let saved_out = std::mem::take(&mut self.out);
+        self.indent += 1;
+        for line in lines.iter() {
+            self.line(line);
+        }
+        self.indent -= 1;
+        let body = std::mem::replace(&mut self.out, saved_out);
+        format!("{head} {{\n{body}{}}}", self.indentation())
+    }
+
+    pub(crate) fn directives(&mut self, list: &[Rc<Directive>]) {
+        for drtv in list.iter() {
+            self.directive(drtv);
+        }
+    }
+
+    /// Generates a substatement surrounded by braces.
+    fn substatement(&mut self, drtv: &Rc<Directive>) {
+        self.indent += 1;
+        if let Directive::Block(block) = drtv.as_ref() {
+            self.directives(&block.directives);
+        } else {
+            self.directive(drtv);
+        }
+        self.indent -= 1;
+    }
+
+    pub(crate) fn directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ExpressionStatement(expstmt) => {
+                let exp = self.exp(&expstmt.expression);
+                self.line(&format!("{exp};"));
+            },
+            Directive::SuperStatement(supstmt) => {
+                let class = self.class_stack.last().map(|c| self.definition_name(c)).unwrap_or("null".to_owned());
+                let mut args = vec![class, "this".to_owned()];
+                args.extend(supstmt.arguments.iter().map(|arg| self.exp(arg)));
+                self.line(&format!("$core.callsuperctor({});", args.join(", ")));
+            },
+            Directive::Block(block) => {
+                self.line("{");
+                self.indent += 1;
+                self.directives(&block.directives);
+                self.indent -= 1;
+                self.line("}");
+            },
+            Directive::LabeledStatement(labstmt) => {
+                self.line_prefix = format!("{}: ", labstmt.label.0);
+                self.directive(&labstmt.substatement);
+                self.line_prefix.clear();
+            },
+            Directive::IfStatement(ifstmt) => {
+                let test = self.exp(&ifstmt.test);
+                self.line(&format!("if ({test}) {{"));
+                self.substatement(&ifstmt.consequent);
+                if let Some(alternative) = ifstmt.alternative.as_ref() {
+                    self.line("} else {");
+                    self.substatement(alternative);
+                }
+                self.line("}");
+            },
+            Directive::SwitchStatement(swstmt) => {
+                let discriminant = self.exp(&swstmt.discriminant);
+                self.line(&format!("switch ({discriminant}) {{"));
+                self.indent += 1;
+                for case in swstmt.cases.iter() {
+                    for label in case.labels.iter() {
+                        match label {
+                            CaseLabel::Case((exp, _)) => {
+                                let exp = self.exp(exp);
+                                self.line(&format!("case {exp}:"));
+                            },
+                            CaseLabel::Default(_) => {
+                                self.line("default:");
+                            },
+                        }
+                    }
+                    self.indent += 1;
+                    self.directives(&case.directives);
+                    self.indent -= 1;
+                }
+                self.indent -= 1;
+                self.line("}");
+            },
+            Directive::SwitchTypeStatement(swstmt) => {
+                self.switch_type_statement(swstmt);
+            },
+            Directive::DoStatement(dostmt) => {
+                self.line("do {");
+                self.substatement(&dostmt.body);
+                let test = self.exp(&dostmt.test);
+                self.line(&format!("}} while ({test});"));
+            },
+            Directive::WhileStatement(whilestmt) => {
+                let test = self.exp(&whilestmt.test);
+                self.line(&format!("while ({test}) {{"));
+                self.substatement(&whilestmt.body);
+                self.line("}");
+            },
+            Directive::ForStatement(forstmt) => {
+                let init = match forstmt.init.as_ref() {
+                    Some(ForInitializer::Expression(exp)) => self.exp(exp),
+                    Some(ForInitializer::VariableDefinition(defn)) => self.simple_variable_definition(defn),
+                    None => "".to_owned(),
+                };
+                let test = forstmt.test.as_ref().map(|exp| self.exp(exp)).unwrap_or_default();
+                let update = forstmt.update.as_ref().map(|exp| self.exp(exp)).unwrap_or_default();
+                self.line(&format!("for ({init}; {test}; {update}) {{"));
+                self.substatement(&forstmt.body);
+                self.line("}");
+            },
+            Directive::ForInStatement(forstmt) => {
+                self.for_in_statement(forstmt);
+            },
+            Directive::BreakStatement(brkstmt) => {
+                if let Some(label) = brkstmt.label.as_ref() {
+                    self.line(&format!("break {};", label.0));
+                } else {
+                    self.line("break;");
+                }
+            },
+            Directive::ContinueStatement(contstmt) => {
+                if let Some(label) = contstmt.label.as_ref() {
+                    self.line(&format!("continue {};", label.0));
+                } else {
+                    self.line("continue;");
+                }
+            },
+            Directive::WithStatement(withstmt) => {
+                let object = self.exp(&withstmt.object);
+                let local = self.next_temp();
+                if let Some(scope) = self.host.node_mapping().get(drtv) {
+                    self.scope_locals.insert(scope, local.clone());
+                }
+                self.line("{");
+                self.indent += 1;
+                self.line(&format!("const {local} = {object};"));
+                self.directive(&withstmt.body);
+                self.indent -= 1;
+                self.line("}");
+            },
+            Directive::ReturnStatement(retstmt) => {
+                if let Some(exp) = retstmt.expression.as_ref() {
+                    let exp = self.exp(exp);
+                    self.line(&format!("return {exp};"));
+                } else {
+                    self.line("return;");
+                }
+            },
+            Directive::ThrowStatement(throwstmt) => {
+                let exp = self.exp(&throwstmt.expression);
+                self.line(&format!("throw {exp};"));
+            },
+            Directive::DefaultXmlNamespaceStatement(dxnstmt) => {
+                let exp = self.exp(&dxnstmt.right);
+                // Outside of a function there is no activation
+                // whose default XML namespace would be assigned.
+                if self.has_default_xml_namespace() {
+                    let ns_type = self.type_reference(&self.host.namespace_type());
+                    self.line(&format!("$$dxns = $core.construct({ns_type}, {exp});"));
+                } else {
+                    self.line(&format!("{exp};"));
+                }
+            },
+            Directive::TryStatement(trystmt) => {
+                self.try_statement(trystmt);
+            },
+            Directive::ConfigurationDirective(cfgdrtv) => {
+                if self.is_enabled_configuration(drtv) {
+                    if let Directive::Block(block) = cfgdrtv.directive.as_ref() {
+                        self.directives(&block.directives);
+                    } else {
+                        self.directive(&cfgdrtv.directive);
+                    }
+                }
+            },
+            Directive::IncludeDirective(incdrtv) => {
+                self.directives(&incdrtv.nested_directives);
+            },
+            Directive::DirectiveInjection(inj) => {
+                self.directives(inj.directives.borrow().as_ref());
+            },
+            Directive::VariableDefinition(defn) => {
+                self.variable_definition(defn);
+            },
+            Directive::FunctionDefinition(defn) => {
+                self.function_definition(drtv, defn);
+            },
+            // Type definitions are generated separately; other
+            // definitions and directives do not generate code.
+            _ => {},
+        }
+    }
+
+    fn variable_definition(&mut self, defn: &VariableDefinition) {
+        let keyword = if defn.kind.0 == VariableDefinitionKind::Const { "const" } else { "var" };
+        for binding in defn.bindings.iter() {
+            let pattern = &binding.destructuring.destructuring;
+            let Some(slot) = self.host.node_mapping().get(pattern) else {
+                continue;
+            };
+            if slot.is_external() {
+                continue;
+            }
+            if let Expression::QualifiedIdentifier(_) = pattern.as_ref() {
+                let name = self.variable_name(&slot);
+                let value = self.variable_initial_value(&slot, binding.initializer.as_ref());
+                self.line(&format!("{keyword} {name} = {value};"));
+            } else {
+                let init = binding.initializer.as_ref().map(|init| self.exp(init)).unwrap_or("undefined".to_owned());
+                for line in self.declarative_destructuring(pattern, &init, keyword) {
+                    self.line(&line);
+                }
+            }
+        }
+    }
+
+    fn simple_variable_definition(&mut self, defn: &SimpleVariableDefinition) -> String {
+        let keyword = if defn.kind.0 == VariableDefinitionKind::Const { "const" } else { "var" };
+        let mut bindings: Vec<String> = vec![];
+        for binding in defn.bindings.iter() {
+            let pattern = &binding.destructuring.destructuring;
+            let Some(slot) = self.host.node_mapping().get(pattern) else {
+                continue;
+            };
+            let name = self.variable_name(&slot);
+            let value = self.variable_initial_value(&slot, binding.initializer.as_ref());
+            bindings.push(format!("{name} = {value}"));
+        }
+        format!("{keyword} {}", bindings.join(", "))
+    }
+
+    /// Returns the JavaScript name of a variable, either a package-level
+    /// definition or a local.
+    pub(crate) fn variable_name(&self, slot: &Entity) -> String {
+        if slot.parent().map(|p| p.is::<Package>()).unwrap_or(false) {
+            self.definition_name(slot)
+        } else {
+            Self::local_name(&slot.name().local_name())
+        }
+    }
+
+    pub(crate) fn variable_initial_value(&mut self, slot: &Entity, init: Option<&Rc<Expression>>) -> String {
+        if let Some(init) = init {
+            return self.exp(init);
+        }
+        if let Some(asset) = slot.embedded_asset() {
+            let url = self.embedded_asset_url(&asset);
+            let t = self.type_reference(&slot.static_type(&self.host).escape_of_nullable_or_non_nullable());
+            return format!("$core.embedasset({}, {}, {t})", Self::string_literal(&url), Self::string_literal(&asset.mime_type));
+        }
+        let t = slot.static_type(&self.host);
+        match t.type_default_value(&self.host) {
+            Ok(Some(value)) => self.constant(&value),
+            _ => "undefined".to_owned(),
+        }
+    }
+
+    /// Returns the URL of an embedded asset, listing it if necessary.
+    fn embedded_asset_url(&mut self, asset: &EmbeddedAsset) -> String {
+        if let Some((_, url)) = self.embedded_assets.iter().find(|(path, _)| *path == asset.path) {
+            return url.clone();
+        }
+        let file_name = std::path::Path::new(&asset.path).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let url = format!("assets/{}-{file_name}", self.embedded_assets.len());
+        self.embedded_assets.push((asset.path.clone(), url.clone()));
+        url
+    }
+
+    fn function_definition(&mut self, drtv: &Rc<Directive>, defn: &FunctionDefinition) {
+        let Some(slot) = self.host.node_mapping().get(drtv) else {
+            return;
+        };
+        if slot.is_external() || slot.is_native() || defn.common.body.is_none() {
+            return;
+        }
+        let mut name = self.variable_name(&slot);
+        if defn.is_getter() {
+            name.push_str("$get");
+        } else if defn.is_setter() {
+            name.push_str("$set");
+        }
+        let code = self.function_code(&defn.common, &format!("function {name}"), vec![]);
+        self.line(&code);
+    }
+
+    fn for_in_statement(&mut self, forstmt: &ForInStatement) {
+        let right = self.exp(&forstmt.right);
+        let iterator = if forstmt.each { "values" } else { "keys" };
+        let item = self.next_temp();
+        self.line(&format!("for (const {item} of $core.{iterator}({right})) {{"));
+        self.indent += 1;
+        match &forstmt.left {
+            ForInBinding::VariableDefinition(defn) => {
+                let keyword = if defn.kind.0 == VariableDefinitionKind::Const { "const" } else { "var" };
+                if let Some(binding) = defn.bindings.first() {
+                    for line in self.declarative_destructuring(&binding.destructuring.destructuring, &item, keyword) {
+                        self.line(&line);
+                    }
+                }
+            },
+            ForInBinding::Expression(exp) => {
+                let assignment = self.reference(exp).write(&item);
+                self.line(&format!("{assignment};"));
+            },
+        }
+        self.indent -= 1;
+        self.substatement(&forstmt.body);
+        self.line("}");
+    }
+
+    fn switch_type_statement(&mut self, swstmt: &SwitchTypeStatement) {
+        let discriminant = self.exp(&swstmt.discriminant);
+        let local = self.next_temp();
+        self.line("{");
+        self.indent += 1;
+        self.line(&format!("const {local} = {discriminant};"));
+        let mut default_case: Option<&TypeCase> = None;
+        let mut first = true;
+        for case in swstmt.cases.iter() {
+            let Some(parameter) = case.parameter.as_ref() else {
+                default_case = Some(case);
+                continue;
+            };
+            let t = self.type_annotation_reference(parameter);
+            self.line(&format!("{}if ($core.istype({local}, {t})) {{", if first { "" } else { "} else " }));
+            first = false;
+            self.type_case_block(parameter, &local, &case.block);
+        }
+        if let Some(case) = default_case {
+            if first {
+                self.line("{");
+            } else {
+                self.line("} else {");
+            }
+            self.indent += 1;
+            self.directives(&case.block.directives);
+            self.indent -= 1;
+            self.line("}");
+        } else if !first {
+            self.line("}");
+        }
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn try_statement(&mut self, trystmt: &TryStatement) {
+        self.line("try {");
+        self.indent += 1;
+        self.directives(&trystmt.block.directives);
+        self.indent -= 1;
+        if !trystmt.catch_clauses.is_empty() {
+            let error = self.next_temp();
+            self.line(&format!("}} catch ({error}) {{"));
+            self.indent += 1;
+            let mut first = true;
+            let mut caught_all = false;
+            for catch_clause in trystmt.catch_clauses.iter() {
+                let t = self.type_annotation_reference(&catch_clause.parameter);
+                if t == "null" {
+                    self.line(if first { "{" } else { "} else {" });
+                    caught_all = true;
+                } else {
+                    self.line(&format!("{}if ($core.istype({error}, {t})) {{", if first { "" } else { "} else " }));
+                }
+                first = false;
+                self.type_case_block(&catch_clause.parameter, &error, &catch_clause.block);
+                if caught_all {
+                    break;
+                }
+            }
+            if !caught_all {
+                self.line("} else {");
+                self.indent += 1;
+                self.line(&format!("throw {error};"));
+                self.indent -= 1;
+            }
+            self.line("}");
+            self.indent -= 1;
+        }
+        if let Some(finally_clause) = trystmt.finally_clause.as_ref() {
+            self.line("} finally {");
+            self.indent += 1;
+            self.directives(&finally_clause.block.directives);
+            self.indent -= 1;
+        }
+        self.line("}");
+    }
+
+    /// Generates a type case or catch clause body, binding its parameter.
+    fn type_case_block(&mut self, parameter: &TypedDestructuring, value: &str, block: &Rc<Block>) {
+        self.indent += 1;
+        for line in self.declarative_destructuring(&parameter.destructuring, value, "const") {
+            self.line(&line);
+        }
+        self.directives(&block.directives);
+        self.indent -= 1;
+    }
+
+    fn type_annotation_reference(&mut self, parameter: &TypedDestructuring) -> String {
+        let t = self.host.node_mapping().get(&parameter.destructuring).map(|slot| slot.static_type(&self.host));
+        let t = parameter.type_annotation.as_ref().and(t);
+        t.map(|t| self.type_reference(&t)).unwrap_or("null".to_owned())
+    }
+}
+
+#[derive(Default)]
+struct JsClassMembers {
+    ctor: Option<Rc<FunctionCommon>>,
+    instance_vars: Vec<(Entity, Option<Rc<Expression>>)>,
+    static_vars: Vec<(Entity, Option<Rc<Expression>>)>,
+    static_properties: Vec<(String, JsClassProperty)>,
+    prototype_properties: Vec<(String, JsClassProperty)>,
+    /// Static initialization code.
+    static_statements: Vec<Rc<Directive>>,
+}
+
+impl JsClassMembers {
+    fn static_property(&mut self, key: &str) -> &mut JsClassProperty {
+        Self::property(&mut self.static_properties, key)
+    }
+
+    fn prototype_property(&mut self, key: &str) -> &mut JsClassProperty {
+        Self::property(&mut self.prototype_properties, key)
+    }
+
+    fn property<'a>(list: &'a mut Vec<(String, JsClassProperty)>, key: &str) -> &'a mut JsClassProperty {
+        if let Some(i) = list.iter().position(|(k, _)| k == key) {
+            return &mut list[i].1;
+        }
+        list.push((key.to_owned(), JsClassProperty::default()));
+        &mut list.last_mut().unwrap().1
+    }
+}
+
+#[derive(Default)]
+struct JsClassProperty {
+    var_slot: Option<Entity>,
+    method: Option<Rc<FunctionCommon>>,
+    getter: Option<Rc<FunctionCommon>>,
+    setter: Option<Rc<FunctionCommon>>,
+    /// Event dispatched by the generated setter of a `[Bindable]` accessor.
+    bindable_event: Option<String>,
+}
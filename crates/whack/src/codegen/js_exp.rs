@@ -0,0 +1,919 @@
+use std::rc::Rc;
+use whackengine_verifier::ns::*;
+use crate::codegen::*;
+
+/// A JavaScript reference produced from an expression.
+pub enum JsReference {
+    /// A JavaScript lexical binding or an instance slot.
+    Lvalue(String),
+    /// A function definition.
+    Function(String),
+    /// A property resolved at runtime, as `(base, key)`.
+    Property(String, String),
+    /// A package-level getter and setter pair, as `(getter, setter)`.
+    Virtual(String, String),
+    /// A property of the base class, as `(class, key)`.
+    Super(String, String),
+    /// A value that is not a reference.
+    Value(String),
+}
+
+impl JsReference {
+    pub fn read(&self) -> String {
+        match self {
+            Self::Lvalue(code) |
+            Self::Function(code) |
+            Self::Value(code) => code.clone(),
+            Self::Property(base, key) => format!("$core.getproperty({base}, {key})"),
+            Self::Virtual(getter, _) => format!("{getter}()"),
+            Self::Super(class, key) => format!("$core.getsuperproperty({class}, this, {key})"),
+        }
+    }
+
+    pub fn write(&self, value: &str) -> String {
+        match self {
+            Self::Lvalue(code) |
+            Self::Function(code) => format!("({code} = {value})"),
+            Self::Property(base, key) => format!("$core.setproperty({base}, {key}, {value})"),
+            Self::Virtual(_, setter) => format!("(($$v) => ({setter}($$v), $$v))({value})"),
+            Self::Super(class, key) => format!("$core.setsuperproperty({class}, this, {key}, {value})"),
+            Self::Value(code) => code.clone(),
+        }
+    }
+
+    pub fn delete(&self) -> String {
+        match self {
+            Self::Property(base, key) => format!("$core.deleteproperty({base}, {key})"),
+            _ => "false".to_owned(),
+        }
+    }
+
+    pub fn call(&self, arguments: &[String]) -> String {
+        match self {
+            Self::Function(code) => format!("{code}({})", arguments.join(", ")),
+            Self::Property(base, key) => {
+                let mut args = vec![base.clone(), key.clone()];
+                args.extend(arguments.iter().cloned());
+                format!("$core.callproperty({})", args.join(", "))
+            },
+            Self::Super(class, key) => {
+                let mut args = vec![class.clone(), "this".to_owned(), key.clone()];
+                args.extend(arguments.iter().cloned());
+                format!("$core.callsuperproperty({})", args.join(", "))
+            },
+            _ => {
+                let mut args = vec![self.read()];
+                args.extend(arguments.iter().cloned());
+                format!("$core.call({})", args.join(", "))
+            },
+        }
+    }
+
+    /// Generates a compound assignment.
+    pub fn compound(&self, operator: Operator, value: &str) -> String {
+        let logical = matches!(operator, Operator::LogicalAnd | Operator::LogicalOr | Operator::NullCoalescing);
+        match self {
+            Self::Lvalue(code) => {
+                if let Some(op) = JsCodegen::compound_operator(operator) {
+                    format!("({code} {op}= {value})")
+                } else {
+                    self.write(&JsCodegen::binary_operation(operator, code, value))
+                }
+            },
+            Self::Property(base, key) => {
+                let current = "$core.getproperty($$b, $$k)";
+                if logical {
+                    format!("(($$b, $$k) => $core.setproperty($$b, $$k, {}))({base}, {key})", JsCodegen::binary_operation(operator, current, value))
+                } else {
+                    format!("(($$b, $$k, $$v) => $core.setproperty($$b, $$k, {}))({base}, {key}, {value})", JsCodegen::binary_operation(operator, current, "$$v"))
+                }
+            },
+            _ => self.write(&JsCodegen::binary_operation(operator, &self.read(), value)),
+        }
+    }
+
+    /// Generates an increment or decrement.
+    pub fn increment(&self, operator: Operator) -> String {
+        let (prefix, op) = match operator {
+            Operator::PreIncrement => (true, "++"),
+            Operator::PreDecrement => (true, "--"),
+            Operator::PostIncrement => (false, "++"),
+            _ => (false, "--"),
+        };
+        let delta = if op == "++" { "+ 1" } else { "- 1" };
+        match self {
+            Self::Lvalue(code) => {
+                if prefix { format!("({op}{code})") } else { format!("({code}{op})") }
+            },
+            Self::Property(base, key) => {
+                if prefix {
+                    format!("(($$b, $$k) => $core.setproperty($$b, $$k, Number($core.getproperty($$b, $$k)) {delta}))({base}, {key})")
+                } else {
+                    format!("(($$b, $$k) => {{ const $$v = Number($core.getproperty($$b, $$k)); $core.setproperty($$b, $$k, $$v {delta}); return $$v; }})({base}, {key})")
+                }
+            },
+            _ => {
+                let current = format!("Number({})", self.read());
+                if prefix {
+                    self.write(&format!("({current} {delta})"))
+                } else {
+                    format!("(($$v) => ({}, $$v))({current})", self.write(&format!("($$v {delta})")))
+                }
+            },
+        }
+    }
+}
+
+impl JsCodegen {
+    /// Generates an expression.
+    pub(crate) fn exp(&mut self, exp: &Rc<Expression>) -> String {
+        let value = self.host.node_mapping().get(exp);
+
+        // Use constant values directly.
+        if let Some(value) = value.as_ref() {
+            let topmost = Self::topmost_value(value);
+            if topmost.is::<Constant>() && !matches!(exp.as_ref(), Expression::Assignment(_) | Expression::Call(_)) {
+                let code = self.constant(&topmost);
+                return self.convert(code, value);
+            }
+        }
+
+        let code = self.exp_code(exp, value.as_ref());
+        match value {
+            Some(value) => self.convert(code, &value),
+            None => code,
+        }
+    }
+
+    /// Unwraps conversions and non-null values.
+    pub(crate) fn topmost_value(value: &Entity) -> Entity {
+        let mut value = value.clone();
+        while value.is::<ConversionValue>() || value.is::<NonNullValue>() {
+            value = value.base();
+        }
+        value
+    }
+
+    fn convert(&mut self, code: String, value: &Entity) -> String {
+        if value.is::<NonNullValue>() {
+            return self.convert(code, &value.base());
+        }
+        if !value.is::<ConversionValue>() {
+            return code;
+        }
+        let code = self.convert(code, &value.base());
+        let target = value.conversion_target().escape_of_nullable_or_non_nullable();
+        match value.conversion_kind() {
+            ConversionKind::BetweenNumber |
+            ConversionKind::ToInt |
+            ConversionKind::ToUint |
+            ConversionKind::ToFloat => self.numeric_coercion(code, &target),
+            ConversionKind::ToNumber => format!("Number({code})"),
+            ConversionKind::ToString => format!("$core.tostring({code})"),
+            ConversionKind::ToBoolean => format!("(!!{code})"),
+            ConversionKind::StringToEnum |
+            ConversionKind::NumberToEnum => format!("$core.toenum({}, {code})", self.type_reference(&target)),
+            ConversionKind::ObjectToItrfc |
+            ConversionKind::ToContravariant |
+            ConversionKind::ToCovariantArray |
+            ConversionKind::ToCovariantVector |
+            ConversionKind::FromTypeParameter |
+            ConversionKind::ParameterizedTypeAlter => format!("$core.coerce({code}, {})", self.type_reference(&target)),
+            _ => code,
+        }
+    }
+
+    fn numeric_coercion(&self, code: String, target: &Entity) -> String {
+        if *target == self.host.int_type() {
+            format!("({code} | 0)")
+        } else if *target == self.host.uint_type() {
+            format!("({code} >>> 0)")
+        } else if *target == self.host.float_type() {
+            format!("Math.fround({code})")
+        } else {
+            code
+        }
+    }
+
+    fn exp_code(&mut self, exp: &Rc<Expression>, value: Option<&Entity>) -> String {
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(_) |
+            Expression::Member(_) |
+            Expression::ComputedMember(_) => {
+                self.reference(exp).read()
+            },
+            Expression::Paren(e) => {
+                let code = self.exp(&e.expression);
+                format!("({code})")
+            },
+            Expression::NullLiteral(_) => "null".to_owned(),
+            Expression::BooleanLiteral(e) => e.value.to_string(),
+            Expression::NumericLiteral(e) => {
+                e.parse_double(false).map(|n| Self::number_literal(&Number::Number(n))).unwrap_or("NaN".to_owned())
+            },
+            Expression::StringLiteral(e) => Self::string_literal(&e.value),
+            Expression::ThisLiteral(_) => "this".to_owned(),
+            Expression::RegExpLiteral(e) => {
+                format!("$core.newregexp({}, {})", Self::string_literal(&e.body), Self::string_literal(&e.flags))
+            },
+            Expression::Xml(e) => {
+                let markup = self.xml_element(&e.element);
+                format!("$core.newxml({markup}{})", self.default_xml_namespace_arg())
+            },
+            Expression::XmlMarkup(e) => {
+                format!("$core.newxml({}{})", Self::string_literal(&e.markup), self.default_xml_namespace_arg())
+            },
+            Expression::XmlList(e) => {
+                let markup = self.xml_content(&e.content);
+                format!("$core.newxmllist({markup}{})", self.default_xml_namespace_arg())
+            },
+            Expression::ArrayLiteral(e) => {
+                let elements = self.array_elements(&e.elements);
+                let t = value.map(|v| v.static_type(&self.host).escape_of_nullable_or_non_nullable());
+                let type_code = t.as_ref().map(|t| self.type_reference(t)).unwrap_or("null".to_owned());
+                if t.as_ref().map(|t| t.is::<TupleType>()).unwrap_or(false) {
+                    format!("$core.newtuple({type_code}, [{elements}])")
+                } else if t.as_ref().map(|t| t.type_after_sub_has_origin(&self.host.vector_type())).unwrap_or(false) {
+                    format!("$core.newvector({type_code}, [{elements}])")
+                } else {
+                    format!("$core.newarray({type_code}, [{elements}])")
+                }
+            },
+            Expression::VectorLiteral(e) => {
+                let elements = self.array_elements(&e.elements);
+                let type_code = value.map(|v| self.type_reference(&v.static_type(&self.host).escape_of_nullable_or_non_nullable())).unwrap_or("null".to_owned());
+                format!("$core.newvector({type_code}, [{elements}])")
+            },
+            Expression::ObjectInitializer(e) => {
+                self.object_initializer(e, value)
+            },
+            Expression::Function(e) => {
+                self.function_exp(e)
+            },
+            Expression::ImportMeta(_) => "$core.importmeta()".to_owned(),
+            Expression::New(e) => {
+                let mut args = vec![self.type_operand(&e.base)];
+                for arg in e.arguments.iter().flatten() {
+                    args.push(self.exp(arg));
+                }
+                format!("$core.construct({})", args.join(", "))
+            },
+            Expression::Descendants(e) => {
+                let base = self.exp(&e.base);
+                let mut key = self.qualified_key(&e.identifier);
+                if self.has_default_xml_namespace() {
+                    key = Self::default_xml_namespace_key(&e.identifier, key);
+                }
+                format!("$core.descendants({base}, {key})")
+            },
+            Expression::Filter(e) => {
+                let base = self.exp(&e.base);
+                let local = self.next_temp();
+                if let Some(value) = value.map(Self::topmost_value).filter(|v| v.is::<FilterValue>()) {
+                    self.scope_locals.insert(value.scope(), local.clone());
+                }
+                let test = self.exp(&e.test);
+                format!("$core.filter({base}, ({local}) => {test})")
+            },
+            Expression::Super(_) => "this".to_owned(),
+            Expression::Call(e) => {
+                self.call_exp(e)
+            },
+            Expression::WithTypeArguments(e) => {
+                if let Some(t) = value.and_then(|v| Self::topmost_value(v).as_type()) {
+                    return self.type_reference(&t);
+                }
+                let base = self.exp(&e.base);
+                let args = e.arguments.iter().map(|arg| self.type_operand(arg)).collect::<Vec<_>>();
+                format!("$core.applytype({base}, [{}])", args.join(", "))
+            },
+            Expression::Unary(e) => {
+                self.unary_exp(e, value)
+            },
+            Expression::OptionalChaining(e) => {
+                let base = self.exp(&e.base);
+                let local = self.next_temp();
+                self.opt_chaining_locals.push(local.clone());
+                let code = self.exp(&e.expression);
+                self.opt_chaining_locals.pop();
+                format!("(({local}) => ({local} === null || {local} === undefined ? null : {code}))({base})")
+            },
+            Expression::OptionalChainingPlaceholder(_) => {
+                self.opt_chaining_locals.last().cloned().unwrap_or("undefined".to_owned())
+            },
+            Expression::Binary(e) => {
+                self.binary_exp(e, value)
+            },
+            Expression::Conditional(e) => {
+                let test = self.exp(&e.test);
+                let consequent = self.exp(&e.consequent);
+                let alternative = self.exp(&e.alternative);
+                format!("({test} ? {consequent} : {alternative})")
+            },
+            Expression::Assignment(e) => {
+                self.assignment_exp(e)
+            },
+            Expression::Sequence(e) => {
+                let left = self.exp(&e.left);
+                let right = self.exp(&e.right);
+                format!("({left}, {right})")
+            },
+            Expression::NullableType(_) |
+            Expression::NonNullableType(_) |
+            Expression::AnyType(_) |
+            Expression::VoidType(_) |
+            Expression::ArrayType(_) |
+            Expression::TupleType(_) |
+            Expression::FunctionType(_) => {
+                value.and_then(|v| Self::topmost_value(v).as_type()).map(|t| self.type_reference(&t)).unwrap_or("null".to_owned())
+            },
+            Expression::ReservedNamespace(_) |
+            Expression::Invalidated(_) => "undefined".to_owned(),
+        }
+    }
+
+    /// Generates an expression used as a type, such as the right operand
+    /// of the `is` operator.
+    fn type_operand(&mut self, exp: &Rc<Expression>) -> String {
+        if let Some(t) = self.host.node_mapping().get(exp).and_then(|v| Self::topmost_value(&v).as_type()) {
+            return self.type_reference(&t);
+        }
+        self.exp(exp)
+    }
+
+    /// Resolves an expression to a JavaScript reference.
+    pub(crate) fn reference(&mut self, exp: &Rc<Expression>) -> JsReference {
+        let value = self.host.node_mapping().get(exp).map(|v| Self::topmost_value(&v));
+        match exp.as_ref() {
+            Expression::Paren(e) => self.reference(&e.expression),
+            Expression::Unary(e) if e.operator == Operator::NonNull => self.reference(&e.expression),
+            Expression::QualifiedIdentifier(id) => {
+                let key = self.qualified_key(id);
+                self.value_reference(value.as_ref(), None, key)
+            },
+            Expression::Member(e) => {
+                let mut key = self.qualified_key(&e.identifier);
+                if self.has_default_xml_namespace() && value.as_ref().map(|v| v.is::<XmlReferenceValue>()).unwrap_or(false) {
+                    key = Self::default_xml_namespace_key(&e.identifier, key);
+                }
+                if let Expression::Super(_) = e.base.as_ref() {
+                    let key = value.as_ref().filter(|v| v.is::<FixtureReferenceValue>()).map(|v| Self::string_literal(&v.property().name().to_string())).unwrap_or(key);
+                    return JsReference::Super(self.current_class_name(), key);
+                }
+                self.value_reference(value.as_ref(), Some(&e.base), key)
+            },
+            Expression::ComputedMember(e) => {
+                let key = self.exp(&e.key);
+                if let Expression::Super(_) = e.base.as_ref() {
+                    return JsReference::Super(self.current_class_name(), key);
+                }
+                self.value_reference(value.as_ref(), Some(&e.base), key)
+            },
+            _ => JsReference::Value(self.exp(exp)),
+        }
+    }
+
+    fn current_class_name(&self) -> String {
+        self.class_stack.last().map(|c| self.definition_name(c)).unwrap_or("null".to_owned())
+    }
+
+    /// Resolves a reference value to a JavaScript reference, where
+    /// `base` is the base expression, if any, and `key` is the code
+    /// of the property key used for runtime lookups.
+    pub(crate) fn value_reference(&mut self, value: Option<&Entity>, base: Option<&Rc<Expression>>, key: String) -> JsReference {
+        let Some(value) = value else {
+            let base = base.map(|b| self.exp(b)).unwrap_or("this".to_owned());
+            return JsReference::Property(base, key);
+        };
+
+        if value.is::<PackageReferenceValue>() || value.is::<ScopeReferenceValue>() {
+            let property = value.property();
+            if let Some(t) = property.as_type() {
+                return JsReference::Value(self.type_reference(&t));
+            }
+            if property.is::<Namespace>() {
+                return JsReference::Value(self.namespace_code(&property));
+            }
+            if property.is::<VirtualSlot>() {
+                let name = self.variable_name(&property);
+                return JsReference::Virtual(format!("{name}$get"), format!("{name}$set"));
+            }
+            if property.is::<MethodSlot>() {
+                return JsReference::Function(self.variable_name(&property));
+            }
+            return JsReference::Lvalue(self.variable_name(&property));
+        }
+
+        if value.is::<StaticReferenceValue>() || value.is::<InstanceReferenceValue>() {
+            let property = value.property();
+            if let Some(t) = property.as_type() {
+                return JsReference::Value(self.type_reference(&t));
+            }
+            let base_code = if value.is::<StaticReferenceValue>() {
+                self.type_reference(&value.base())
+            } else if let Some(base) = base {
+                self.exp(base)
+            } else {
+                "this".to_owned()
+            };
+            // `[Bindable]` variables are assigned through their setter.
+            if property.is::<VariableSlot>() && value.is::<InstanceReferenceValue>() && property.bindable_event().is_none() {
+                if let Some(index) = self.instance_slot_index(&property) {
+                    return JsReference::Lvalue(format!("{base_code}[{index}]"));
+                }
+            }
+            return JsReference::Property(base_code, Self::string_literal(&property.name().to_string()));
+        }
+
+        if value.is::<DynamicScopeReferenceValue>() {
+            let base_code = self.scope_locals.get(&value.base()).cloned().unwrap_or("globalThis".to_owned());
+            return JsReference::Property(base_code, key);
+        }
+
+        if value.is::<TupleReferenceValue>() {
+            let base_code = base.map(|b| self.exp(b)).unwrap_or("this".to_owned());
+            return JsReference::Property(base_code, value.tuple_index().to_string());
+        }
+
+        if value.is::<MetaEnvProperty>() {
+            return JsReference::Value("$core.importmetaenv()".to_owned());
+        }
+
+        let base_code = if let Some(base) = base {
+            self.exp(base)
+        } else {
+            self.scope_locals.get(&value.base()).cloned().unwrap_or("this".to_owned())
+        };
+        JsReference::Property(base_code, key)
+    }
+
+    /// Returns the key of a property used for runtime lookups.
+    fn qualified_key(&mut self, id: &QualifiedIdentifier) -> String {
+        let mut local = match &id.id {
+            QualifiedIdentifierIdentifier::Id((name, _)) => Self::string_literal(name),
+            QualifiedIdentifierIdentifier::Brackets(exp) => {
+                let code = self.exp(exp);
+                format!("$core.tostring({code})")
+            },
+        };
+        if id.attribute {
+            local = format!("(\"@\" + {local})");
+        }
+        if let Some(qual) = id.qualifier.as_ref() {
+            let qual = self.exp(qual);
+            format!("$core.qname({qual}, {local})")
+        } else {
+            local
+        }
+    }
+
+    /// Qualifies the key of an unqualified XML element name
+    /// by the default XML namespace.
+    fn default_xml_namespace_key(id: &QualifiedIdentifier, key: String) -> String {
+        if id.qualifier.is_none() && !id.attribute {
+            format!("$core.qname($$dxns, {key})")
+        } else {
+            key
+        }
+    }
+
+    fn default_xml_namespace_arg(&self) -> &'static str {
+        if self.has_default_xml_namespace() { ", $$dxns" } else { "" }
+    }
+
+    fn call_exp(&mut self, e: &CallExpression) -> String {
+        let base_value = self.host.node_mapping().get(&e.base).map(|v| Self::topmost_value(&v));
+
+        // Type casts
+        if let Some(t) = base_value.as_ref().and_then(|v| v.as_type()) {
+            let type_code = self.type_reference(&t);
+            let args = e.arguments.iter().map(|arg| self.exp(arg)).collect::<Vec<_>>();
+            if t == self.host.array_type() || t.type_after_sub_has_origin(&self.host.array_type()) || t == self.host.date_type() {
+                let mut construct_args = vec![type_code];
+                construct_args.extend(args);
+                return format!("$core.construct({})", construct_args.join(", "));
+            }
+            let arg = args.first().cloned().unwrap_or("undefined".to_owned());
+            return format!("$core.coerce({arg}, {type_code})");
+        }
+
+        let reference = self.reference(&e.base);
+        let args = e.arguments.iter().map(|arg| self.exp(arg)).collect::<Vec<_>>();
+        reference.call(&args)
+    }
+
+    fn unary_exp(&mut self, e: &UnaryExpression, value: Option<&Entity>) -> String {
+        match e.operator {
+            // The non-null operator generates no code.
+            Operator::NonNull => self.exp(&e.expression),
+            Operator::Delete => self.reference(&e.expression).delete(),
+            Operator::PreIncrement |
+            Operator::PreDecrement |
+            Operator::PostIncrement |
+            Operator::PostDecrement => self.reference(&e.expression).increment(e.operator),
+            _ => {
+                let operand = self.exp(&e.expression);
+                let code = match e.operator {
+                    Operator::Void => format!("(void {operand})"),
+                    Operator::Typeof => format!("$core.typeof({operand})"),
+                    Operator::Await => format!("(await {operand})"),
+                    Operator::Yield => format!("(yield {operand})"),
+                    Operator::Positive => format!("(+{operand})"),
+                    Operator::Negative => format!("(-{operand})"),
+                    Operator::BitwiseNot => format!("(~{operand})"),
+                    _ => format!("(!{operand})"),
+                };
+                if e.operator == Operator::Negative {
+                    self.numeric_result(code, value)
+                } else {
+                    code
+                }
+            },
+        }
+    }
+
+    fn binary_exp(&mut self, e: &BinaryExpression, value: Option<&Entity>) -> String {
+        let left = self.exp(&e.left);
+        let right = match e.operator {
+            Operator::Is | Operator::IsNot | Operator::As => self.type_operand(&e.right),
+            _ => self.exp(&e.right),
+        };
+        let code = Self::binary_operation(e.operator, &left, &right);
+        match e.operator {
+            Operator::Add |
+            Operator::Subtract |
+            Operator::Multiply |
+            Operator::Divide |
+            Operator::Remainder |
+            Operator::Power => self.numeric_result(code, value),
+            _ => code,
+        }
+    }
+
+    /// Wraps arithmetic results of type `int`, `uint` or `float`.
+    fn numeric_result(&self, code: String, value: Option<&Entity>) -> String {
+        let Some(value) = value else {
+            return code;
+        };
+        let t = Self::topmost_value(value).static_type(&self.host).escape_of_nullable_or_non_nullable();
+        self.numeric_coercion(code, &t)
+    }
+
+    /// Returns the JavaScript compound assignment operator
+    /// for an operator, without the trailing `=`.
+    pub(crate) fn compound_operator(operator: Operator) -> Option<&'static str> {
+        Some(match operator {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Remainder => "%",
+            Operator::Power => "**",
+            Operator::ShiftLeft => "<<",
+            Operator::ShiftRight => ">>",
+            Operator::ShiftRightUnsigned => ">>>",
+            Operator::BitwiseAnd => "&",
+            Operator::BitwiseXor => "^",
+            Operator::BitwiseOr => "|",
+            Operator::LogicalAnd => "&&",
+            Operator::LogicalOr => "||",
+            Operator::NullCoalescing => "??",
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn binary_operation(operator: Operator, left: &str, right: &str) -> String {
+        if let Some(op) = Self::compound_operator(operator) {
+            return format!("({left} {op} {right})");
+        }
+        match operator {
+            Operator::Lt => format!("({left} < {right})"),
+            Operator::Gt => format!("({left} > {right})"),
+            Operator::Le => format!("({left} <= {right})"),
+            Operator::Ge => format!("({left} >= {right})"),
+            Operator::Equals => format!("({left} == {right})"),
+            Operator::NotEquals => format!("({left} != {right})"),
+            Operator::StrictEquals => format!("({left} === {right})"),
+            Operator::StrictNotEquals => format!("({left} !== {right})"),
+            Operator::LogicalXor => format!("(!{left} !== !{right})"),
+            Operator::Instanceof => format!("$core.instanceof({left}, {right})"),
+            Operator::In => format!("$core.hasproperty({right}, {left})"),
+            Operator::NotIn => format!("(!$core.hasproperty({right}, {left}))"),
+            Operator::Is => format!("$core.istype({left}, {right})"),
+            Operator::IsNot => format!("(!$core.istype({left}, {right}))"),
+            Operator::As => format!("$core.astype({left}, {right})"),
+            _ => format!("({left}, {right})"),
+        }
+    }
+
+    fn assignment_exp(&mut self, e: &AssignmentExpression) -> String {
+        if let Some(operator) = e.compound {
+            let reference = self.reference(&e.left);
+            let right = self.exp(&e.right);
+            return reference.compound(operator, &right);
+        }
+
+        // Destructuring assignment
+        let mut pattern = &e.left;
+        while let Expression::Unary(UnaryExpression { operator: Operator::NonNull, expression, .. }) = pattern.as_ref() {
+            pattern = expression;
+        }
+        if matches!(pattern.as_ref(), Expression::ObjectInitializer(_) | Expression::ArrayLiteral(_)) {
+            let right = self.exp(&e.right);
+            let local = self.next_temp();
+            let lines = self.destructuring(pattern, &local, None);
+            return format!("(({local}) => {{ {} return {local}; }})({right})", lines.join(" "));
+        }
+
+        let reference = self.reference(&e.left);
+        let right = self.exp(&e.right);
+        reference.write(&right)
+    }
+
+    /// Generates a destructuring that declares variables.
+    pub(crate) fn declarative_destructuring(&mut self, pattern: &Rc<Expression>, value: &str, keyword: &str) -> Vec<String> {
+        self.destructuring(pattern, value, Some(keyword))
+    }
+
+    /// Generates a destructuring as a list of statements. The destructuring
+    /// assigns to references if `keyword` is `None`.
+    fn destructuring(&mut self, pattern: &Rc<Expression>, value: &str, keyword: Option<&str>) -> Vec<String> {
+        match pattern.as_ref() {
+            Expression::Unary(e) if e.operator == Operator::NonNull => {
+                self.destructuring(&e.expression, value, keyword)
+            },
+            Expression::ObjectInitializer(literal) => {
+                let local = self.next_temp();
+                let mut lines = vec![format!("const {local} = {value};")];
+                let mut keys: Vec<String> = vec![];
+                for field in literal.fields.iter() {
+                    match field.as_ref() {
+                        InitializerField::Field { name, value: subpattern, .. } => {
+                            let key = match &name.0 {
+                                FieldName::Identifier(id) => self.qualified_key(id),
+                                FieldName::Brackets(exp) |
+                                FieldName::StringLiteral(exp) |
+                                FieldName::NumericLiteral(exp) => self.exp(exp),
+                            };
+                            keys.push(key.clone());
+                            let field_value = format!("$core.getproperty({local}, {key})");
+                            if let Some(subpattern) = subpattern {
+                                lines.extend(self.destructuring(subpattern, &field_value, keyword));
+                            } else {
+                                lines.push(self.shorthand_destructuring(field, &key, &field_value, keyword));
+                            }
+                        },
+                        InitializerField::Rest((subpattern, _)) => {
+                            let rest = format!("$core.objectrest({local}, [{}])", keys.join(", "));
+                            lines.extend(self.destructuring(subpattern, &rest, keyword));
+                        },
+                    }
+                }
+                lines
+            },
+            Expression::ArrayLiteral(literal) => {
+                let local = self.next_temp();
+                let mut lines = vec![format!("const {local} = {value};")];
+                for (i, element) in literal.elements.iter().enumerate() {
+                    match element {
+                        Element::Elision => {},
+                        Element::Expression(subpattern) => {
+                            lines.extend(self.destructuring(subpattern, &format!("$core.getproperty({local}, {i})"), keyword));
+                        },
+                        Element::Rest((subpattern, _)) => {
+                            lines.extend(self.destructuring(subpattern, &format!("$core.arrayrest({local}, {i})"), keyword));
+                        },
+                    }
+                }
+                lines
+            },
+            _ => {
+                if let Some(keyword) = keyword {
+                    let name = self.host.node_mapping().get(pattern)
+                        .map(|slot| self.variable_name(&slot))
+                        .unwrap_or("$$unused".to_owned());
+                    vec![format!("{keyword} {name} = {value};")]
+                } else {
+                    let assignment = self.reference(pattern).write(value);
+                    vec![format!("{assignment};")]
+                }
+            },
+        }
+    }
+
+    fn shorthand_destructuring(&mut self, field: &Rc<InitializerField>, key: &str, value: &str, keyword: Option<&str>) -> String {
+        let resolution = self.host.node_mapping().get(field);
+        if let Some(keyword) = keyword {
+            let name = resolution.and_then(|r| r.var_slot())
+                .map(|slot| self.variable_name(&slot))
+                .unwrap_or("$$unused".to_owned());
+            format!("{keyword} {name} = {value};")
+        } else {
+            let target = resolution.and_then(|r| r.target_reference()).map(|r| Self::topmost_value(&r));
+            let assignment = self.value_reference(target.as_ref(), None, key.to_owned()).write(value);
+            format!("{assignment};")
+        }
+    }
+
+    fn array_elements(&mut self, elements: &[Element]) -> String {
+        let mut output: Vec<String> = vec![];
+        for element in elements.iter() {
+            match element {
+                Element::Elision => output.push("undefined".to_owned()),
+                Element::Expression(exp) => output.push(self.exp(exp)),
+                Element::Rest((exp, _)) => {
+                    let code = self.exp(exp);
+                    output.push(format!("...$core.elements({code})"));
+                },
+            }
+        }
+        output.join(", ")
+    }
+
+    fn object_initializer(&mut self, e: &ObjectInitializer, value: Option<&Entity>) -> String {
+        let type_code = value.map(|v| self.type_reference(&v.static_type(&self.host).escape_of_nullable_or_non_nullable())).unwrap_or("null".to_owned());
+        let mut fields: Vec<String> = vec![];
+        for field in e.fields.iter() {
+            match field.as_ref() {
+                InitializerField::Field { name, value, .. } => {
+                    let key = match &name.0 {
+                        FieldName::Identifier(id) => self.qualified_key(id),
+                        FieldName::Brackets(exp) |
+                        FieldName::StringLiteral(exp) |
+                        FieldName::NumericLiteral(exp) => self.exp(exp),
+                    };
+                    let field_value = if let Some(value) = value {
+                        self.exp(value)
+                    } else {
+                        let resolution = self.host.node_mapping().get(field)
+                            .and_then(|r| r.shorthand_resolution())
+                            .map(|r| Self::topmost_value(&r));
+                        self.value_reference(resolution.as_ref(), None, key.clone()).read()
+                    };
+                    fields.push(format!("[{key}, {field_value}]"));
+                },
+                InitializerField::Rest((exp, _)) => {
+                    let code = self.exp(exp);
+                    fields.push(format!("...$core.entries({code})"));
+                },
+            }
+        }
+        format!("$core.newobject({type_code}, [{}])", fields.join(", "))
+    }
+
+    fn function_exp(&mut self, e: &FunctionExpression) -> String {
+        let has_this = e.common.signature.this_parameter.is_some();
+        if e.name.is_none() && !has_this && !e.common.contains_yield {
+            let code = self.function_code(&e.common, "", vec![]);
+            return format!("({code})");
+        }
+        let keyword = e.name.as_ref().map(|(name, _)| format!("function {}", Self::local_name(name))).unwrap_or("function".to_owned());
+        let code = self.function_code(&e.common, &keyword, vec![]);
+        if has_this { format!("({code})") } else { format!("({code}).bind(this)") }
+    }
+
+    fn xml_element(&mut self, element: &Rc<XmlElement>) -> String {
+        let mut parts: Vec<String> = vec![];
+        let name = match &element.name {
+            XmlTagName::Name((name, _)) => Self::string_literal(name),
+            XmlTagName::Expression(exp) => {
+                let code = self.exp(exp);
+                format!("$core.tostring({code})")
+            },
+        };
+        parts.push(Self::string_literal("<"));
+        parts.push(name.clone());
+        for attr in element.attributes.iter() {
+            parts.push(Self::string_literal(&format!(" {}=\"", attr.name.0)));
+            match &attr.value {
+                XmlAttributeValue::Value((value, _)) => parts.push(Self::string_literal(value)),
+                XmlAttributeValue::Expression(exp) => {
+                    let code = self.exp(exp);
+                    parts.push(format!("$core.escapexmlattribute({code})"));
+                },
+            }
+            parts.push(Self::string_literal("\""));
+        }
+        if let Some(exp) = element.attribute_expression.as_ref() {
+            let code = self.exp(exp);
+            parts.push(format!("$core.xmlattributes({code})"));
+        }
+        if let Some(content) = element.content.as_ref() {
+            parts.push(Self::string_literal(">"));
+            parts.push(self.xml_content(content));
+            parts.push(Self::string_literal("</"));
+            parts.push(name);
+            parts.push(Self::string_literal(">"));
+        } else {
+            parts.push(Self::string_literal("/>"));
+        }
+        format!("({})", parts.join(" + "))
+    }
+
+    fn xml_content(&mut self, content: &[Rc<XmlContent>]) -> String {
+        let mut parts: Vec<String> = vec![Self::string_literal("")];
+        for item in content.iter() {
+            match item.as_ref() {
+                XmlContent::Characters((text, _)) |
+                XmlContent::Markup((text, _)) => parts.push(Self::string_literal(text)),
+                XmlContent::Element(element) => parts.push(self.xml_element(element)),
+                XmlContent::Expression(exp) => {
+                    let code = self.exp(exp);
+                    parts.push(format!("$core.xmlcontent({code})"));
+                },
+            }
+        }
+        format!("({})", parts.join(" + "))
+    }
+
+    /// Generates a constant value.
+    pub(crate) fn constant(&self, value: &Entity) -> String {
+        if value.is::<UndefinedConstant>() {
+            "undefined".to_owned()
+        } else if value.is::<NullConstant>() {
+            "null".to_owned()
+        } else if value.is::<BooleanConstant>() {
+            value.boolean_value().to_string()
+        } else if value.is::<NumberConstant>() {
+            let code = Self::number_literal(&value.number_value());
+            let t = value.static_type(&self.host).escape_of_nullable_or_non_nullable();
+            if t.is::<EnumType>() {
+                format!("$core.toenum({}, {code})", self.type_reference(&t))
+            } else {
+                code
+            }
+        } else if value.is::<StringConstant>() {
+            Self::string_literal(&value.string_value())
+        } else if value.is::<NamespaceConstant>() {
+            self.namespace_code(&value.referenced_ns())
+        } else if value.is::<TypeConstant>() {
+            self.type_reference(&value.referenced_type())
+        } else {
+            "undefined".to_owned()
+        }
+    }
+
+    fn namespace_code(&self, ns: &Entity) -> String {
+        if ns.is::<UserNamespace>() {
+            format!("$core.namespace({})", Self::string_literal(&ns.uri()))
+        } else {
+            "null".to_owned()
+        }
+    }
+
+    /// Generates a reference to a type.
+    pub(crate) fn type_reference(&self, t: &Entity) -> String {
+        if t.is::<AnyType>() || t.is::<TypeParameterType>() {
+            "null".to_owned()
+        } else if t.is::<VoidType>() {
+            "undefined".to_owned()
+        } else if t.is::<NullableType>() || t.is::<NonNullableType>() {
+            self.type_reference(&t.base())
+        } else if t.is::<TypeAfterSubstitution>() {
+            let args = t.substitute_types().iter().map(|arg| self.type_reference(&arg)).collect::<Vec<_>>();
+            format!("$core.applytype({}, [{}])", self.type_reference(&t.origin()), args.join(", "))
+        } else if t.is::<TupleType>() {
+            let elements = t.element_types().iter().map(|el| self.type_reference(&el)).collect::<Vec<_>>();
+            format!("$core.tupletype([{}])", elements.join(", "))
+        } else if t.is::<FunctionType>() {
+            self.type_reference(&self.host.function_type())
+        } else {
+            self.definition_name(t)
+        }
+    }
+
+    pub(crate) fn string_literal(s: &str) -> String {
+        let mut output = String::from("\"");
+        for ch in s.chars() {
+            match ch {
+                '"' => output.push_str("\\\""),
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                '\u{2028}' => output.push_str("\\u2028"),
+                '\u{2029}' => output.push_str("\\u2029"),
+                ch if (ch as u32) < 0x20 => output.push_str(&format!("\\x{:02x}", ch as u32)),
+                ch => output.push(ch),
+            }
+        }
+        output.push('"');
+        output
+    }
+
+    pub(crate) fn number_literal(n: &Number) -> String {
+        let n = match n {
+            Number::Number(n) => *n,
+            Number::Float(n) => *n as f64,
+            Number::Int(n) => return n.to_string(),
+            Number::Uint(n) => return n.to_string(),
+        };
+        if n.is_nan() {
+            "NaN".to_owned()
+        } else if n.is_infinite() {
+            if n > 0.0 { "Infinity".to_owned() } else { "-Infinity".to_owned() }
+        } else {
+            n.to_string()
+        }
+    }
+}
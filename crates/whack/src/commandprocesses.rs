@@ -1,5 +1,20 @@
+mod build_scripts;
+pub use build_scripts::*;
+
+mod build_process;
+pub use build_process::*;
+
 mod check_process;
 pub use check_process::*;
 
 mod commons;
-pub use commons::*;
\ No newline at end of file
+pub use commons::*;
+
+mod fix_process;
+pub use fix_process::*;
+
+mod lsp_process;
+pub use lsp_process::*;
+
+mod message_format;
+pub use message_format::*;
\ No newline at end of file
@@ -0,0 +1,64 @@
+use crate::codegen::*;
+use colored::Colorize;
+
+use super::CommandProcessCommons;
+
+pub async fn build_process(matches: &clap::ArgMatches) {
+    // Command line provided configuration constants
+    let defined_constants = matches
+        .get_many::<String>("define")
+        .unwrap_or_default()
+        .map(|v| {
+            let s = v.split("=").collect::<Vec<_>>();
+            (s[0].to_owned(), s.get(1).unwrap_or(&"").to_string())
+        })
+        .collect::<Vec<_>>();
+    // Command line provided lint levels
+    let lints = CommandProcessCommons::lints_from_matches(matches);
+    // Diagnostic messages of the chosen locale
+    let messages = CommandProcessCommons::diagnostic_messages_from_matches(matches);
+
+    // Resolve packages and run build scripts
+    let project = CommandProcessCommons::prepare_project(matches, &defined_constants, &messages).await;
+    let dag = &project.dag;
+
+    // Whether RT::client is on or off
+    let rt_client = true;
+
+    // Whether RT::server is on or off
+    let rt_server = false;
+
+    // Whether CONFIG::test is on or off
+    let testing = true;
+
+    // Check each dependency in ascending order for AS3 and MXML errors.
+    let (as3host, verifier) = CommandProcessCommons::verify_sources_from_dag(dag, &defined_constants, &lints, &messages, rt_client, rt_server, testing);
+
+    // Write to the run cache file and the lock file
+    project.write_run_cache_and_lockfile();
+
+    // Do not generate code if there are any errors.
+    if verifier.invalidated() {
+        std::process::exit(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE);
+    }
+
+    // Generate JavaScript for each package in ascending order,
+    // writing a script per package to `target/js/`.
+    let js_path = project.target_path.join("js");
+    std::fs::create_dir_all(&js_path).unwrap();
+    let mut codegen = JsCodegen::new(&as3host);
+    for pckg in dag.iter() {
+        let name = pckg.manifest.package.as_ref().unwrap().name.clone();
+        let script = codegen.generate_package(&pckg, &verifier);
+        std::fs::write(js_path.join(format!("{name}.js")), script).unwrap();
+    }
+
+    // Copy embedded assets next to the scripts.
+    for (path, url) in codegen.embedded_assets() {
+        let dest = js_path.join(url);
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        std::fs::copy(path, dest).unwrap();
+    }
+
+    println!("{} JavaScript written to {}", "Finished:".green(), js_path.to_str().unwrap());
+}
@@ -0,0 +1,178 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use colored::Colorize;
+use whackengine_verifier::ns::{shared_array, DiagnosticMessages, SharedArray};
+use crate::codegen::*;
+use crate::packagemanager::*;
+
+use super::CommandProcessCommons;
+
+/// Build script runner.
+///
+/// The build script of a package consists of the ActionScript sources
+/// given by the `build-script` manifest field. It is verified along with
+/// the built-ins and the package's build dependencies, compiled to a
+/// JavaScript file at `target/build-script/NAME/build.js` and executed with
+/// the JavaScript runtime given by the `WHACK_JS_RUNTIME` environment
+/// variable (`node` by default), from the package directory.
+///
+/// The build script may write ActionScript sources into the directory given
+/// by the `WHACK_OUT_DIR` environment variable (`target/build-script/NAME/out`),
+/// which is then added to the package's source path.
+pub struct BuildScripts;
+
+impl BuildScripts {
+    pub const RUNTIME_ENV_VAR: &'static str = "WHACK_JS_RUNTIME";
+
+    /// Runs build scripts across packages in ascending order, skipping those that
+    /// have already run since the package's manifest was last modified.
+    /// Exits the process if a build script fails.
+    ///
+    /// Progress, diagnostics of build scripts and failures are written to
+    /// the standard error stream, keeping the standard output stream for
+    /// the diagnostics of the command (such as `--message-format=json`).
+    pub async fn run(entry_dir: &Path, builtins: &[Rc<WhackPackage>], build_script_dag: &Dag, run_cache_file: &mut RunCacheFile, defined_constants: &Vec<(String, String)>, messages: &DiagnosticMessages) {
+        let packages = build_script_dag.iter().collect::<Vec<_>>();
+
+        for (i, pckg) in packages.iter().enumerate() {
+            let manifest = pckg.manifest.package.as_ref().unwrap();
+            if manifest.build_script.is_none() {
+                continue;
+            }
+
+            let script_dir = entry_dir.join("target").join("build-script").join(&manifest.name);
+            let out_dir = BuildScripts::out_dir(entry_dir, &manifest.name);
+            pckg.generated_source_path.replace(Some(out_dir.clone()));
+
+            let run_cache = run_cache_file.packages.iter_mut().find(|p| p.path == pckg.relative_path);
+            if run_cache.as_ref().map(|p| p.build_script_run).unwrap_or(false) && std::fs::exists(&out_dir).unwrap() {
+                continue;
+            }
+
+            // Compile the build script
+            let script_path = script_dir.join("build.js");
+            let dag = Dag::from_packages(&BuildScripts::compilation_packages(pckg, builtins, &packages[..i]));
+            let (as3host, verifier) = CommandProcessCommons::verify_sources(&dag, defined_constants, &[], false, true, false, &HashMap::new(), None)
+                .unwrap_or_else(|error| CommandProcessCommons::exit_with_package_processing_error(error));
+            for pckg in dag.iter() {
+                for source in pckg.sources.iter() {
+                    for diagnostic in source.compilation_unit().nested_diagnostics() {
+                        eprintln!("{}", CommandProcessCommons::format_diagnostic(&diagnostic, messages, &dag.last.absolute_path));
+                    }
+                }
+            }
+            if verifier.invalidated() {
+                eprintln!("{} Build script of {} contains errors.", "Error:".red(), manifest.name);
+                std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+            }
+            let mut codegen = JsCodegen::new(&as3host);
+            let mut script = String::new();
+            for pckg in dag.iter() {
+                script.push_str(&codegen.generate_package(&pckg, &verifier));
+                script.push('\n');
+            }
+            if std::fs::exists(&out_dir).unwrap() {
+                std::fs::remove_dir_all(&out_dir).unwrap();
+            }
+            std::fs::create_dir_all(&out_dir).unwrap();
+            std::fs::write(&script_path, script).unwrap();
+
+            // Execute the build script
+            let runtime = std::env::var(BuildScripts::RUNTIME_ENV_VAR).unwrap_or("node".to_owned());
+            eprintln!("{} build script of {}", "Running".green(), manifest.name);
+            let status = tokio::process::Command::new(&runtime)
+                .arg(&script_path)
+                .current_dir(&pckg.absolute_path)
+                .env("WHACK_OUT_DIR", &out_dir)
+                .env("WHACK_PACKAGE_DIR", &pckg.absolute_path)
+                .env("WHACK_PACKAGE_NAME", &manifest.name)
+                .status().await;
+            match status {
+                Ok(status) if status.success() => {},
+                Ok(status) => {
+                    eprintln!("{} Build script of {} failed with {}.", "Error:".red(), manifest.name, status);
+                    std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+                },
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                    eprintln!("{} Skipping build script of {}: JavaScript runtime {} not found (set {}).", "Warning:".yellow(), manifest.name, runtime, BuildScripts::RUNTIME_ENV_VAR);
+                    continue;
+                },
+                Err(error) => {
+                    eprintln!("{} Failed to run build script of {}: {}", "Error:".red(), manifest.name, error);
+                    std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+                },
+            }
+
+            if let Some(run_cache) = run_cache_file.packages.iter_mut().find(|p| p.path == pckg.relative_path) {
+                run_cache.build_script_run = true;
+            }
+        }
+    }
+
+    /// Directory into which the build script of the package `name` writes sources.
+    pub fn out_dir(entry_dir: &Path, name: &str) -> PathBuf {
+        entry_dir.join("target").join("build-script").join(name).join("out")
+    }
+
+    /// Returns the packages the build script of `pckg` is verified with,
+    /// in ascending order: the built-ins, the build dependencies
+    /// (and their dependencies) found in `preceding`, and lastly
+    /// a package consisting of the build script sources.
+    ///
+    /// Packages are copied so that their sources are not contributed
+    /// to those from the main directed acyclic graph.
+    fn compilation_packages(pckg: &Rc<WhackPackage>, builtins: &[Rc<WhackPackage>], preceding: &[Rc<WhackPackage>]) -> Vec<Rc<WhackPackage>> {
+        let mut names: HashSet<String> = HashSet::new();
+        if let Some(deps) = pckg.manifest.build_dependencies.as_ref() {
+            names.extend(deps.keys().cloned());
+        }
+        // Include dependencies of build dependencies, visiting
+        // preceding packages in descending order.
+        let mut included: Vec<Rc<WhackPackage>> = vec![];
+        for p in preceding.iter().rev() {
+            let name = &p.manifest.package.as_ref().unwrap().name;
+            if names.contains(name) {
+                if let Some(deps) = p.manifest.dependencies.as_ref() {
+                    names.extend(deps.keys().cloned());
+                }
+                included.insert(0, p.clone());
+            }
+        }
+
+        let mut found: Vec<Rc<WhackPackage>> = vec![];
+        let mut r: Vec<Rc<WhackPackage>> = vec![];
+        for p in builtins.iter().chain(included.iter()) {
+            if !found.iter().any(|p1| Rc::ptr_eq(p1, p)) {
+                found.push(p.clone());
+                r.push(Rc::new(WhackPackage {
+                    absolute_path: p.absolute_path.clone(),
+                    relative_path: p.relative_path.clone(),
+                    manifest: p.manifest.clone(),
+                    sources: shared_array![],
+                    build_script_sources: shared_array![],
+                    generated_source_path: RefCell::new(p.generated_source_path.borrow().clone()),
+                }));
+            }
+        }
+
+        let mut manifest = pckg.manifest.clone();
+        let package_manifest = manifest.package.as_mut().unwrap();
+        package_manifest.source_path = package_manifest.build_script.take();
+        manifest.client_side = None;
+        manifest.server_side = Some(ManifestServerSide { command_name: None });
+        manifest.dependencies = None;
+        manifest.build_dependencies = None;
+
+        r.push(Rc::new(WhackPackage {
+            absolute_path: pckg.absolute_path.clone(),
+            relative_path: pckg.relative_path.clone(),
+            manifest,
+            sources: pckg.build_script_sources.clone(),
+            build_script_sources: shared_array![],
+            generated_source_path: RefCell::new(None),
+        }));
+        r
+    }
+}
@@ -1,12 +1,44 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{collections::HashMap, path::{Path, PathBuf}, str::FromStr};
 use crate::packagemanager::*;
 use colored::Colorize;
 use hydroperfox_filepaths::FlexPath;
+use semver::VersionReq;
 use whackengine_verifier::ns::*;
+use super::BuildScripts;
 
 pub struct CommandProcessCommons;
 
+/// A project whose packages have been resolved, along with the built-ins,
+/// and whose build scripts have run.
+pub struct PreparedProject {
+    /// Entry point directory.
+    pub dir: PathBuf,
+    pub target_path: PathBuf,
+    /// Packages in ascending order, starting with the built-ins.
+    pub dag: Dag,
+    pub lockfile: WhackLockfile,
+    pub lockfile_path: PathBuf,
+    pub run_cache_file: RunCacheFile,
+    pub run_cache_path: PathBuf,
+}
+
+impl PreparedProject {
+    /// Writes the run cache file and the lock file.
+    pub fn write_run_cache_and_lockfile(&self) {
+        std::fs::create_dir_all(&self.target_path).unwrap();
+        std::fs::write(&self.run_cache_path, toml::to_string::<RunCacheFile>(&self.run_cache_file).unwrap()).unwrap();
+        std::fs::write(&self.lockfile_path, toml::to_string::<WhackLockfile>(&self.lockfile).unwrap()).unwrap();
+    }
+}
+
 impl CommandProcessCommons {
+    /// Exit code used when sources contain verification errors.
+    pub const SOURCE_VERIFICATION_FAILURE_EXIT_CODE: i32 = 1;
+
+    /// Exit code used when a manifest could not be processed or
+    /// dependencies could not be resolved.
+    pub const PACKAGE_PROCESSING_FAILURE_EXIT_CODE: i32 = 2;
+
     /// Returns (dir, lockfile, lockfile_path, found_base_manifest).
     pub fn entry_point_lookup(dir: &PathBuf) -> (FlexPath, Option<WhackLockfile>, PathBuf, bool) {
         let mut dir = FlexPath::new_native(dir.to_str().unwrap());
@@ -37,38 +69,289 @@ impl CommandProcessCommons {
         (dir, lockfile, lockfile_path, found_base_manifest)
     }
 
+    /// Resolves the packages of the project given by the `path`, `package`
+    /// and `builtins` options, populates the lock file and runs build scripts.
+    /// Exits the process if the project could not be prepared.
+    pub async fn prepare_project(matches: &clap::ArgMatches, defined_constants: &Vec<(String, String)>, messages: &DiagnosticMessages) -> PreparedProject {
+        let builtins = matches.get_one::<String>("builtins").cloned().unwrap_or("../lib/packages/whack.base".to_owned());
+        let builtins = PathBuf::from_str(&FlexPath::from_n_native([std::env::current_dir().unwrap().to_str().unwrap(), &builtins]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
+        let package: Option<&String> = matches.get_one::<String>("package");
+        let initial_path: Option<&String> = matches.get_one::<String>("path");
+        let frozen = matches.get_flag("frozen");
+        let locked = frozen || matches.get_flag("locked");
+
+        let mut dir = std::env::current_dir().unwrap();
+        if let Some(initial_path) = initial_path {
+            dir = PathBuf::from_str(&FlexPath::new_native(dir.to_str().unwrap()).resolve(initial_path).to_string_with_flex_separator()).unwrap();
+        }
+
+        // Detect entry point directory and read lockfile
+        let (dir, lockfile, lockfile_path, found_base_manifest) = CommandProcessCommons::entry_point_lookup(&dir);
+
+        if !found_base_manifest {
+            eprintln!("{} Currently not inside a Whack project.", "Error:".red());
+            std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+        }
+
+        // Target path
+        let target_path = PathBuf::from_str(&dir.resolve("target").to_string_with_flex_separator()).unwrap();
+
+        // Read the run cache file
+        let run_cache_path = PathBuf::from_str(&dir.resolve("target/.run-cache.toml").to_string_with_flex_separator()).unwrap();
+        let mut run_cache_file: Option<RunCacheFile> = None;
+        if std::fs::exists(&run_cache_path).unwrap() && std::fs::metadata(&run_cache_path).unwrap().is_file() {
+            run_cache_file = Some(toml::from_str::<RunCacheFile>(&std::fs::read_to_string(&run_cache_path).unwrap()).unwrap());
+        }
+        let mut run_cache_file = run_cache_file.unwrap_or(RunCacheFile {
+            packages: vec![]
+        });
+
+        // Initial lockfile
+        let mut lockfile = lockfile.unwrap_or(WhackLockfile {
+            package: vec![]
+        });
+        let original_lockfile = toml::to_string::<WhackLockfile>(&lockfile).unwrap();
+
+        // Entry point directory
+        let dir = PathBuf::from_str(&dir.to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
+
+        // Conflicting dependencies tracker
+        let mut conflicting_dependencies_tracker = HashMap::<String, HashMap<String, VersionReq>>::new();
+
+        // Package internator
+        let mut package_internator = WhackPackageInternator::new();
+
+        // Process directed acyclic graph
+        let (mut dag, mut build_script_dag) = match Dag::retrieve(dir.clone(), &dir, package.cloned(), &mut lockfile, &mut run_cache_file, &mut conflicting_dependencies_tracker, &mut package_internator, frozen, vec![]).await {
+            Ok(dag) => dag,
+            Err(error) => {
+                CommandProcessCommons::print_package_processing_error(error);
+                std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+            },
+        };
+
+        // Process the built-ins as well.
+        let (builtins_dag, builtins_build_script_dag) = match Dag::retrieve(builtins, &dir, package.cloned(), &mut lockfile, &mut run_cache_file, &mut conflicting_dependencies_tracker, &mut package_internator, frozen, vec![]).await {
+            Ok(dag) => dag,
+            Err(error) => {
+                CommandProcessCommons::print_package_processing_error(error);
+                std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+            },
+        };
+        let builtins_packages = builtins_dag.iter().collect::<Vec<_>>();
+        dag.prepend_dag(builtins_dag);
+        build_script_dag.prepend_dag(builtins_build_script_dag);
+
+        // Filter out duplicate entries from `dag` and `build_script_dag` by
+        // reorganizing each of them.
+        dag.filter_out_duplicates();
+        build_script_dag.filter_out_duplicates();
+
+        // Record every resolved package other than the built-ins in the lock file.
+        lockfile.populate(&dag.iter().chain(build_script_dag.iter())
+            .filter(|p| !builtins_packages.iter().any(|p1| Rc::ptr_eq(p, p1)))
+            .collect::<Vec<_>>());
+        if locked && toml::to_string::<WhackLockfile>(&lockfile).unwrap() != original_lockfile {
+            eprintln!("{} The lock file needs to be updated, but --locked was passed.", "Error:".red());
+            std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+        }
+
+        // Run build scripts across packages in ascending order
+        // (depending on the run cache file)
+        BuildScripts::run(&dir, &builtins_packages, &build_script_dag, &mut run_cache_file, defined_constants, messages).await;
+
+        PreparedProject {
+            dir,
+            target_path,
+            dag,
+            lockfile,
+            lockfile_path,
+            run_cache_file,
+            run_cache_path,
+        }
+    }
+
     pub fn print_package_processing_error(error: WhackPackageProcessingError) {
+        eprintln!("{} {}", "Error:".red(), CommandProcessCommons::package_processing_error_message(&error));
+    }
+
+    /// Prints a package processing error and exits the process.
+    pub fn exit_with_package_processing_error(error: WhackPackageProcessingError) -> ! {
+        CommandProcessCommons::print_package_processing_error(error);
+        std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+    }
+
+    /// Describes a package processing error, without the `Error:` prefix.
+    pub fn package_processing_error_message(error: &WhackPackageProcessingError) -> String {
         match error {
             WhackPackageProcessingError::ManifestNotFound => {
-                println!("{} {}", "Error:".red(), "Whack manifest not found.");
+                "Whack manifest not found.".to_owned()
             },
             WhackPackageProcessingError::PackageMustBeSpecified => {
-                println!("{} {}", "Error:".red(), "Package must be specified.");
+                "Package must be specified.".to_owned()
             },
             WhackPackageProcessingError::CircularDependency { directory } => {
-                println!("{} Circular dependency is not allowed: {}", "Error:".red(), directory);
+                format!("Circular dependency is not allowed: {}", directory)
             },
             WhackPackageProcessingError::InvalidManifest { manifest_path, message } => {
-                println!("{} Whack manifest at {} contains invalid TOML: {}", "Error:".red(), manifest_path, message);
+                format!("Whack manifest at {} contains invalid TOML: {}", manifest_path, message)
             },
             WhackPackageProcessingError::UnspecifiedWorkspaceMember => {
-                println!("{} Must specify which package to be processed in Whack workspace.", "Error:".red());
+                "Must specify which package to be processed in Whack workspace.".to_owned()
             },
             WhackPackageProcessingError::ManifestIsNotAPackage { manifest_path } => {
-                println!("{} Whack manifest at {} does not describe a package.", "Error:".red(), manifest_path);
+                format!("Whack manifest at {} does not describe a package.", manifest_path)
             },
             WhackPackageProcessingError::IllegalPackageName { name } => {
-                println!("{} Found illegal package name: {}", "Error:".red(), name);
+                format!("Found illegal package name: {}", name)
             },
             WhackPackageProcessingError::FileNotFound { path } => {
-                println!("{} File not found: {}", "Error:".red(), path);
+                format!("File not found: {}", path)
             },
             WhackPackageProcessingError::UnrecognizedSourceFileExtension { path } => {
-                println!("{} Unrecognized source file extension at: {}", "Error:".red(), path);
+                format!("Unrecognized source file extension at: {}", path)
+            },
+            WhackPackageProcessingError::RegistryNotConfigured => {
+                format!("Registry dependencies require the {} environment variable to be set.", WhackRegistry::ENV_VAR)
+            },
+            WhackPackageProcessingError::RegistryRequestFailed { url, message } => {
+                format!("Failed to fetch {}: {}", url, message)
+            },
+            WhackPackageProcessingError::InvalidRegistryIndex { url, message } => {
+                format!("Registry index at {} is invalid: {}", url, message)
+            },
+            WhackPackageProcessingError::NoMatchingRegistryVersion { name, version_req } => {
+                format!("No version of {} in the registry matches {}", name, version_req)
+            },
+            WhackPackageProcessingError::RegistryChecksumMismatch { name, version } => {
+                format!("Checksum mismatch for {} v{}", name, version)
+            },
+            WhackPackageProcessingError::InvalidPackageArchive { path, message } => {
+                format!("Package archive at {} is invalid: {}", path, message)
+            },
+            WhackPackageProcessingError::GitCommandFailed { url, message } => {
+                format!("Failed to update Git dependency {}: {}", url, message)
+            },
+            WhackPackageProcessingError::IllegalGitArgument { url, argument } => {
+                format!("Found illegal argument for Git dependency {}: {}", url, argument)
+            },
+            WhackPackageProcessingError::NetworkAccessDisallowed { url } => {
+                format!("Network access is required to fetch {}, but --frozen was passed.", url)
+            },
+            WhackPackageProcessingError::DependencyVersionConflict { name, requirements, candidates } => {
+                let mut message = format!("Conflicting version requirements for dependency {}:", name);
+                for (requirer, version_req) in requirements.iter() {
+                    message.push_str(&format!("\n    {} requires {}", requirer, version_req));
+                }
+                if candidates.is_empty() {
+                    message.push_str("\n    No candidate versions.");
+                } else {
+                    message.push_str(&format!("\n    Candidate versions: {}", candidates.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")));
+                }
+                message
+            },
+            WhackPackageProcessingError::UnknownLint { manifest_path, name } => {
+                format!("Whack manifest at {} contains an unknown lint: {}", manifest_path, name)
+            },
+            WhackPackageProcessingError::InvalidDependencyVersion { manifest_path } => {
+                format!("Whack manifest at {} contains invalid SemVer version at a dependency.", manifest_path)
+            },
+            WhackPackageProcessingError::AmbiguousDependencySource { manifest_path } => {
+                format!("Whack manifest at {} contains a dependency that is a Git or local dependency at the same time.", manifest_path)
+            },
+            WhackPackageProcessingError::WorkspaceMemberNotFound { name } => {
+                format!("Could not find member {}", name)
+            },
+            WhackPackageProcessingError::ClientAndServerSideApplication => {
+                "Package cannot be both a client-side and server-side application at the same time.".to_owned()
             },
         }
     }
 
+    /// Returns the lint levels given by the `--warn`, `--allow` and `--deny`
+    /// options in the order they appear in the command line.
+    pub fn lints_from_matches(matches: &clap::ArgMatches) -> Vec<(String, WarningLevel)> {
+        let mut lints: Vec<(usize, String, WarningLevel)> = vec![];
+        for (id, level) in [("warn", WarningLevel::Warn), ("allow", WarningLevel::Allow), ("deny", WarningLevel::Deny)] {
+            if let (Some(names), Some(indices)) = (matches.get_many::<String>(id), matches.indices_of(id)) {
+                lints.extend(indices.zip(names).map(|(i, name)| (i, name.clone(), level)));
+            }
+        }
+        lints.sort_by_key(|(i, _, _)| *i);
+
+        let mut probe = CompilerWarningOptions::default();
+        for (_, name, level) in lints.iter() {
+            if !CommandProcessCommons::apply_lint(&mut probe, name, *level) {
+                eprintln!("{} Unknown lint: {}", "Error:".red(), name);
+                std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+            }
+        }
+
+        lints.into_iter().map(|(_, name, level)| (name, level)).collect()
+    }
+
+    /// Returns the diagnostic messages for the `--locale` option, or else
+    /// for the locale of the environment. Exits the process if the option
+    /// is invalid.
+    ///
+    /// The `--locale` option takes either a shipped locale such as `pt-BR`
+    /// or the path to a TOML file of messages.
+    pub fn diagnostic_messages_from_matches(matches: &clap::ArgMatches) -> DiagnosticMessages {
+        let Some(locale) = matches.get_one::<String>("locale") else {
+            return CommandProcessCommons::environment_diagnostic_messages();
+        };
+        if locale.ends_with(".toml") {
+            let path = PathBuf::from_str(locale).unwrap();
+            let name = path.file_stem().unwrap().to_str().unwrap().to_owned();
+            let messages = std::fs::read_to_string(&path).map_err(|error| error.to_string())
+                .and_then(|source| DiagnosticMessages::from_toml(&name, &source));
+            match messages {
+                Ok(messages) => messages,
+                Err(message) => {
+                    eprintln!("{} Could not read locale file {}: {}", "Error:".red(), locale, message);
+                    std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+                },
+            }
+        } else if let Some(messages) = DiagnosticMessages::shipped(locale) {
+            messages
+        } else {
+            let mut shipped = vec!["en"];
+            shipped.extend(DiagnosticMessages::shipped_locales());
+            eprintln!("{} Unknown locale: {}. Available locales: {}.", "Error:".red(), locale, shipped.join(", "));
+            std::process::exit(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE);
+        }
+    }
+
+    /// Returns the diagnostic messages for the locale given by the
+    /// `LC_ALL`, `LC_MESSAGES` or `LANG` environment variables,
+    /// falling back to English.
+    pub fn environment_diagnostic_messages() -> DiagnosticMessages {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| DiagnosticMessages::shipped(&locale))
+            .unwrap_or_else(DiagnosticMessages::english)
+    }
+
+    /// Sets the level of a warning given its diagnostic name (such as `Unused`)
+    /// or ID (such as `2158`). The `warnings` name sets the level of every
+    /// warning that is not given a level individually.
+    ///
+    /// Returns `false` if the name is unknown or does not name a warning.
+    pub fn apply_lint(warnings: &mut CompilerWarningOptions, name: &str, level: WarningLevel) -> bool {
+        if name == "warnings" {
+            warnings.default_level = level;
+            return true;
+        }
+        let kind = WhackDiagnosticKind::from_name(name)
+            .or_else(|| name.parse::<i32>().ok().and_then(WhackDiagnosticKind::from_id));
+        let Some(kind) = kind.filter(|kind| kind.is_warning()) else {
+            return false;
+        };
+        warnings.levels.insert(kind.id(), level);
+        true
+    }
+
     pub fn recurse_source_files(path: &PathBuf) -> Result<Vec<Rc<CompilationUnit>>, WhackPackageProcessingError> {
         if !std::fs::exists(path).unwrap() {
             return Err(WhackPackageProcessingError::FileNotFound {
@@ -109,7 +392,34 @@ impl CommandProcessCommons {
         Ok(vec![])
     }
 
-    pub fn verify_sources_from_dag(dag: &Dag, defined_constants: &Vec<(String, String)>, mut rt_client: bool, mut rt_server: bool, testing: bool) -> (Rc<Database>, Verifier) {
+    /// Formats a diagnostic for the human message format,
+    /// followed by the code it points to.
+    pub fn format_diagnostic(diagnostic: &Diagnostic, messages: &DiagnosticMessages, base_path: &Path) -> String {
+        let message = messages.format_with_base_path(diagnostic, base_path.to_str().unwrap());
+        let prefix = if diagnostic.is_error() { "Error:".red() } else { "Warning:".yellow() };
+        format!("{} {}\n\n{}\n", prefix, message, diagnostic.location().show_code())
+    }
+
+    pub fn verify_sources_from_dag(dag: &Dag, defined_constants: &Vec<(String, String)>, lints: &[(String, WarningLevel)], messages: &DiagnosticMessages, rt_client: bool, rt_server: bool, testing: bool) -> (Rc<Database>, Verifier) {
+        CommandProcessCommons::verify_sources(dag, defined_constants, lints, rt_client, rt_server, testing, &HashMap::new(), Some(messages))
+            .unwrap_or_else(|error| CommandProcessCommons::exit_with_package_processing_error(error))
+    }
+
+    /// Verifies sources across the packages of a directed acyclic graph
+    /// in ascending order.
+    ///
+    /// `source_overlays` maps canonical file paths to source text that is
+    /// used instead of the file contents (such as unsaved editor buffers).
+    /// Diagnostics are printed using `messages`. If `messages` is `None`,
+    /// diagnostics are only collected into the compilation units.
+    ///
+    /// `lints` are applied over the `[lints]` table of the entry package
+    /// and of the other workspace members, but not of dependencies.
+    ///
+    /// Fails if the entry package is misconfigured or if its
+    /// source files cannot be read.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_sources(dag: &Dag, defined_constants: &[(String, String)], lints: &[(String, WarningLevel)], mut rt_client: bool, mut rt_server: bool, testing: bool, source_overlays: &HashMap<String, String>, messages: Option<&DiagnosticMessages>) -> Result<(Rc<Database>, Verifier), WhackPackageProcessingError> {
         let as3host = Rc::new(Database::new(DatabaseOptions {
             project_path: Some(dag.last.absolute_path.canonicalize().unwrap().to_str().unwrap().to_owned()),
             ..default()
@@ -121,8 +431,7 @@ impl CommandProcessCommons {
         let entry_pckg = dag.last.clone();
         if entry_pckg.manifest.client_side.is_some() {
             if entry_pckg.manifest.server_side.is_some() {
-                println!("{} Package cannot be both a client-side and server-side application at the same time.", "Error:".red());
-                std::process::exit(1);
+                return Err(WhackPackageProcessingError::ClientAndServerSideApplication);
             }
             rt_server = false;
         } else if entry_pckg.manifest.server_side.is_some() {
@@ -162,20 +471,42 @@ impl CommandProcessCommons {
                         let source_path_1_pathbuf = PathBuf::from_str(&source_path_1_str).unwrap();
                         source_path_list.push(source_path_1_pathbuf.canonicalize().unwrap().to_str().unwrap().to_owned());
                     }
-                    match CommandProcessCommons::recurse_source_files(&PathBuf::from_str(&source_path_1_str).unwrap()) {
-                        Ok(files) => {
-                            compilation_units.extend(files);
-                        },
-                        Err(error) => {
-                            CommandProcessCommons::print_package_processing_error(error);
-                            std::process::exit(1);
-                        },
-                    }
+                    compilation_units.extend(CommandProcessCommons::recurse_source_files(&PathBuf::from_str(&source_path_1_str).unwrap())?);
+                }
+            }
+
+            // Sources generated by the build script
+            if let Some(generated_source_path) = pckg.generated_source_path.borrow().as_ref() {
+                if std::fs::exists(generated_source_path).unwrap() {
+                    source_path_list.push(generated_source_path.canonicalize().unwrap().to_str().unwrap().to_owned());
+                    compilation_units.extend(CommandProcessCommons::recurse_source_files(generated_source_path)?);
+                }
+            }
+
+            // Replace overlaid sources
+            for cu in compilation_units.iter_mut() {
+                if let Some(text) = source_overlays.get(&cu.file_path().unwrap()) {
+                    *cu = CompilationUnit::new(cu.file_path(), text.clone());
+                }
+            }
+
+            // Warning levels from the manifest, then from the command line
+            // for workspace members
+            let mut warnings = CompilerWarningOptions::default();
+            if let Some(lints_1) = pckg.manifest.lints.as_ref() {
+                for (name, level) in lints_1.iter() {
+                    CommandProcessCommons::apply_lint(&mut warnings, name, level.warning_level());
+                }
+            }
+            if pckg.is_workspace_member() {
+                for (name, level) in lints.iter() {
+                    CommandProcessCommons::apply_lint(&mut warnings, name, *level);
                 }
             }
 
             // Build the default compiler options
             let compiler_options = Rc::new(CompilerOptions {
+                warnings,
                 source_path: source_path_list,
                 ..default()
             });
@@ -191,11 +522,6 @@ impl CommandProcessCommons {
                 if file_path.ends_with(".mxml") {
                     // Parse MXML
                     mxml.push(ParserFacade(cu, default()).parse_mxml());
-
-                    // @todo Remove this when MXML is implemented in verification, codegen,
-                    // IDE integration and ASDoc.
-                    println!("{} MXML is not implemented yet: {}", "Error:".red(), file_path);
-                    std::process::exit(1);
                 } else {
                     // Parse AS3
                     programs.push(ParserFacade(cu, default()).parse_program());
@@ -221,18 +547,16 @@ impl CommandProcessCommons {
                 let loc = entity.location().unwrap();
                 let cu = loc.compilation_unit();
                 if CompilerOptions::of(&cu).warnings.unused {
-                    let diag: Diagnostic;
                     if entity.is::<PackagePropertyImport>() || entity.is::<PackageWildcardImport>()
                     || entity.is::<PackageRecursiveImport>() {
-                        diag = WhackDiagnostic::new_warning(&loc, WhackDiagnosticKind::UnusedImport, diagarg![]);
+                        verifier.add_warning_with_fixes(&loc, WhackDiagnosticKind::UnusedImport, diagarg![], vec![
+                            DiagnosticFix::new_machine_applicable("Remove unused import", vec![TextEdit::delete_directive(&loc)]),
+                        ]);
                     // Nominal entity
                     } else {
                         let name = entity.name().to_string();
-                        diag = WhackDiagnostic::new_warning(&loc, WhackDiagnosticKind::Unused, diagarg![name.clone()]);
+                        verifier.add_warning(&loc, WhackDiagnosticKind::Unused, diagarg![name.clone()]);
                     }
-                    cu.add_diagnostic(diag.clone());
-                    // println!("{} {}", "Warning:".yellow(), WhackDiagnostic(&diag).format_english());
-                    // cu.sort_diagnostics();
                 }
             }
 
@@ -241,15 +565,11 @@ impl CommandProcessCommons {
             // Sort and log diagnostics
             for cu in compilation_units.iter() {
                 cu.sort_diagnostics();
+                let Some(messages) = messages else {
+                    continue;
+                };
                 for diagnostic in cu.nested_diagnostics() {
-                    let diagnostic = WhackDiagnostic(&diagnostic);
-                    if diagnostic.is_error() {
-                        println!("{} {}", "Error:".red(), diagnostic.format_english_with_base_path(&entry_pckg.absolute_path.to_str().unwrap()));
-                    } else {
-                        println!("{} {}", "Warning:".yellow(), diagnostic.format_english_with_base_path(&entry_pckg.absolute_path.to_str().unwrap()));
-                    }
-
-                    println!("\n{}\n", &diagnostic.location().show_code());
+                    println!("{}", CommandProcessCommons::format_diagnostic(&diagnostic, messages, &entry_pckg.absolute_path));
                 }
             }
 
@@ -262,7 +582,7 @@ impl CommandProcessCommons {
             }
         }
 
-        (as3host, verifier)
+        Ok((as3host, verifier))
     }
 }
 
@@ -289,4 +609,56 @@ pub enum WhackPackageProcessingError {
     UnrecognizedSourceFileExtension {
         path: String
     },
+    RegistryNotConfigured,
+    RegistryRequestFailed {
+        url: String,
+        message: String,
+    },
+    InvalidRegistryIndex {
+        url: String,
+        message: String,
+    },
+    NoMatchingRegistryVersion {
+        name: String,
+        version_req: String,
+    },
+    RegistryChecksumMismatch {
+        name: String,
+        version: semver::Version,
+    },
+    InvalidPackageArchive {
+        path: String,
+        message: String,
+    },
+    GitCommandFailed {
+        url: String,
+        message: String,
+    },
+    IllegalGitArgument {
+        url: String,
+        argument: String,
+    },
+    NetworkAccessDisallowed {
+        url: String,
+    },
+    DependencyVersionConflict {
+        name: String,
+        /// Pairs of (requirer, version requirement).
+        requirements: Vec<(String, String)>,
+        candidates: Vec<semver::Version>,
+    },
+    UnknownLint {
+        manifest_path: String,
+        name: String,
+    },
+    InvalidDependencyVersion {
+        manifest_path: String,
+    },
+    AmbiguousDependencySource {
+        manifest_path: String,
+    },
+    WorkspaceMemberNotFound {
+        name: String,
+    },
+    ClientAndServerSideApplication,
 }
\ No newline at end of file
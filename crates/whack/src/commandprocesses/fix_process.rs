@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use colored::Colorize;
+
+use whackengine_verifier::ns::*;
+
+use super::CommandProcessCommons;
+
+pub async fn fix_process(matches: &clap::ArgMatches) {
+    let dry_run = matches.get_flag("dry-run");
+    // Command line provided configuration constants
+    let defined_constants = matches
+        .get_many::<String>("define")
+        .unwrap_or_default()
+        .map(|v| {
+            let s = v.split("=").collect::<Vec<_>>();
+            (s[0].to_owned(), s.get(1).unwrap_or(&"").to_string())
+        })
+        .collect::<Vec<_>>();
+    // Command line provided lint levels
+    let lints = CommandProcessCommons::lints_from_matches(matches);
+    // Diagnostic messages of the chosen locale
+    let messages = CommandProcessCommons::diagnostic_messages_from_matches(matches);
+
+    // Resolve packages and run build scripts
+    let project = CommandProcessCommons::prepare_project(matches, &defined_constants, &messages).await;
+    let dag = &project.dag;
+
+    // Whether RT::client is on or off
+    let rt_client = true;
+
+    // Whether RT::server is on or off
+    let rt_server = false;
+
+    // Whether CONFIG::test is on or off
+    let testing = true;
+
+    // Verify sources without printing diagnostics.
+    if let Err(error) = CommandProcessCommons::verify_sources(dag, &defined_constants, &lints, rt_client, rt_server, testing, &HashMap::new(), None) {
+        CommandProcessCommons::exit_with_package_processing_error(error);
+    }
+
+    // Collect the fixes of the entry package's diagnostics by file.
+    // Only diagnostics with a single machine-applicable fix are fixed
+    // automatically, and fixes that overlap a previously accepted fix
+    // are skipped.
+    let mut file_edits: Vec<(Rc<CompilationUnit>, Vec<TextEdit>)> = vec![];
+    let mut fixed_count = 0usize;
+    for source in dag.last.sources.iter() {
+        for diagnostic in source.compilation_unit().nested_diagnostics() {
+            let fixes = WhackDiagnostic(&diagnostic).fixes().into_iter()
+                .filter(|fix| fix.is_machine_applicable())
+                .collect::<Vec<_>>();
+            if fixes.len() != 1 {
+                continue;
+            }
+            let edits = &fixes[0].edits;
+            if edits.iter().any(|edit| edit.location.compilation_unit().file_path().is_none()) {
+                continue;
+            }
+            let overlaps = edits.iter().any(|edit| {
+                file_edits.iter()
+                    .find(|(cu, _)| Rc::ptr_eq(cu, &edit.location.compilation_unit()))
+                    .map(|(_, accepted)| accepted.iter().any(|other| edits_overlap(&edit.location, &other.location)))
+                    .unwrap_or(false)
+            });
+            if overlaps {
+                continue;
+            }
+            for edit in edits.iter() {
+                let cu = edit.location.compilation_unit();
+                if let Some((_, accepted)) = file_edits.iter_mut().find(|(cu1, _)| Rc::ptr_eq(cu1, &cu)) {
+                    accepted.push(edit.clone());
+                } else {
+                    file_edits.push((cu, vec![edit.clone()]));
+                }
+            }
+            fixed_count += 1;
+        }
+    }
+
+    // Apply edits in descending order of offset.
+    for (cu, edits) in file_edits.iter_mut() {
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.location.first_offset()));
+        let mut text = cu.text().clone();
+        for edit in edits.iter() {
+            text.replace_range(edit.location.first_offset()..edit.location.last_offset(), &edit.replacement);
+        }
+        let file_path = cu.file_path().unwrap();
+        if dry_run {
+            println!("{} {} ({} {})", "Would fix".yellow(), file_path, edits.len(), if edits.len() == 1 { "edit" } else { "edits" });
+        } else {
+            std::fs::write(&file_path, text).unwrap();
+            println!("{} {} ({} {})", "Fixed".green(), file_path, edits.len(), if edits.len() == 1 { "edit" } else { "edits" });
+        }
+    }
+
+    // Write to the run cache file and the lock file
+    project.write_run_cache_and_lockfile();
+
+    // Print summary
+    println!("{} {} {} in {} {}",
+        if dry_run { "Fixable".yellow() } else { "Fixed".green() },
+        fixed_count, if fixed_count == 1 { "diagnostic" } else { "diagnostics" },
+        file_edits.len(), if file_edits.len() == 1 { "file" } else { "files" });
+}
+
+/// Indicates whether two edited ranges overlap or touch.
+fn edits_overlap(a: &Location, b: &Location) -> bool {
+    a.first_offset() <= b.last_offset() && b.first_offset() <= a.last_offset()
+}
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use colored::Colorize;
+use hydroperfox_filepaths::FlexPath;
+use lsp_server::Connection;
+use whackengine_verifier::ns::DiagnosticMessages;
+use crate::lsp::LanguageServer;
+use super::CommandProcessCommons;
+
+pub async fn lsp_process(matches: &clap::ArgMatches) {
+    let builtins = matches.get_one::<String>("builtins").cloned().unwrap_or("../lib/packages/whack.base".to_owned());
+    let builtins = PathBuf::from_str(&FlexPath::from_n_native([std::env::current_dir().unwrap().to_str().unwrap(), &builtins]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
+    let package: Option<&String> = matches.get_one::<String>("package");
+    let initial_path: Option<&String> = matches.get_one::<String>("path");
+    // Command line provided configuration constants
+    let defined_constants = matches
+        .get_many::<String>("define")
+        .unwrap_or_default()
+        .map(|v| {
+            let s = v.split("=").collect::<Vec<_>>();
+            (s[0].to_owned(), s.get(1).unwrap_or(&"").to_string())
+        })
+        .collect::<Vec<_>>();
+    // Command line provided lint levels
+    let lints = CommandProcessCommons::lints_from_matches(matches);
+    // Diagnostic messages given by --locale, if any
+    let messages = matches.get_one::<String>("locale").map(|_| CommandProcessCommons::diagnostic_messages_from_matches(matches));
+
+    // The standard output is reserved for the protocol from here.
+    let (connection, io_threads) = Connection::stdio();
+    let initialize_params = match connection.initialize(serde_json::to_value(LanguageServer::capabilities()).unwrap()) {
+        Ok(params) => serde_json::from_value::<lsp_types::InitializeParams>(params).ok(),
+        Err(error) => {
+            eprintln!("{} {}", "Error:".red(), error);
+            std::process::exit(1);
+        },
+    };
+
+    // Otherwise use the locale of the client or environment
+    let messages = messages
+        .or_else(|| initialize_params.as_ref().and_then(|p| p.locale.as_ref()).and_then(|locale| DiagnosticMessages::shipped(locale)))
+        .unwrap_or_else(CommandProcessCommons::environment_diagnostic_messages);
+
+    // Start from the first workspace folder if any
+    let mut dir = std::env::current_dir().unwrap();
+    if let Some(folder) = initialize_params.and_then(|p| p.workspace_folders).and_then(|f| f.into_iter().next()) {
+        if let Ok(path) = folder.uri.to_file_path() {
+            dir = path;
+        }
+    }
+    if let Some(initial_path) = initial_path {
+        dir = PathBuf::from_str(&FlexPath::new_native(dir.to_str().unwrap()).resolve(initial_path).to_string_with_flex_separator()).unwrap();
+    }
+
+    LanguageServer::new(connection, dir, builtins, package.cloned(), defined_constants, lints, messages).run().await;
+    io_threads.join().unwrap();
+}
@@ -0,0 +1,175 @@
+use serde_json::{json, Value};
+use whackengine_verifier::ns::*;
+use crate::packagemanager::*;
+
+/// Format of the diagnostics reported by a command,
+/// given by the `--message-format` option.
+///
+/// - `human`: colored messages with code snippets, printed as packages are verified.
+/// - `json`: one JSON object per diagnostic and line, printed after verification.
+/// - `sarif`: a single SARIF 2.1.0 log, printed after verification.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+impl MessageFormat {
+    pub const VALUES: [&'static str; 3] = ["human", "json", "sarif"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+
+    /// Prints the diagnostics of every verified source of `dag`
+    /// in ascending order. Does nothing for the `human` format.
+    pub fn emit(&self, dag: &Dag, messages: &DiagnosticMessages) {
+        let mut diagnostics: Vec<Diagnostic> = vec![];
+        for pckg in dag.iter() {
+            for source in pckg.sources.iter() {
+                diagnostics.extend(source.compilation_unit().nested_diagnostics());
+            }
+        }
+
+        match self {
+            Self::Human => {},
+            Self::Json => {
+                for diagnostic in diagnostics.iter() {
+                    println!("{}", MessageFormat::diagnostic_json(diagnostic, messages));
+                }
+            },
+            Self::Sarif => {
+                println!("{}", serde_json::to_string_pretty(&MessageFormat::sarif_log(&diagnostics, messages)).unwrap());
+            },
+        }
+    }
+
+    /// Returns a diagnostic as a JSON object. Lines and columns count from one.
+    pub fn diagnostic_json(diagnostic: &Diagnostic, messages: &DiagnosticMessages) -> Value {
+        let message = messages.format_message(diagnostic);
+        let diagnostic = WhackDiagnostic(diagnostic);
+        let location = diagnostic.location();
+        let fixes = diagnostic.fixes().iter().map(|fix| json!({
+            "title": fix.title,
+            "applicability": fix.applicability.name(),
+            "edits": fix.edits.iter().map(|edit| json!({
+                "file": MessageFormat::file_path(&edit.location),
+                "range": MessageFormat::range_json(&edit.location),
+                "replacement": edit.replacement,
+            })).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>();
+        json!({
+            "id": diagnostic.id(),
+            "kind": MessageFormat::kind(&diagnostic),
+            "severity": if diagnostic.is_error() { "error" } else { "warning" },
+            "file": MessageFormat::file_path(&location),
+            "range": MessageFormat::range_json(&location),
+            "message": message,
+            "arguments": diagnostic.arguments().iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            "fixes": fixes,
+        })
+    }
+
+    fn range_json(location: &Location) -> Value {
+        json!({
+            "start": { "line": location.first_line_number(), "column": location.first_column() + 1 },
+            "end": { "line": location.last_line_number(), "column": location.last_column() + 1 },
+        })
+    }
+
+    fn sarif_log(diagnostics: &[Diagnostic], messages: &DiagnosticMessages) -> Value {
+        let mut rule_ids: Vec<i32> = diagnostics.iter().map(|d| WhackDiagnostic(d).id()).collect();
+        rule_ids.sort();
+        rule_ids.dedup();
+
+        let results = diagnostics.iter().map(|diagnostic| {
+            let message = messages.format_message(diagnostic);
+            let diagnostic = WhackDiagnostic(diagnostic);
+            let location = diagnostic.location();
+            let mut physical_location = json!({
+                "region": MessageFormat::sarif_region(&location),
+            });
+            if let Some(uri) = MessageFormat::sarif_uri(&location) {
+                physical_location["artifactLocation"] = json!({ "uri": uri });
+            }
+            let fixes = diagnostic.fixes().iter().map(|fix| json!({
+                "description": { "text": fix.title },
+                "artifactChanges": fix.edits.iter().map(|edit| json!({
+                    "artifactLocation": { "uri": MessageFormat::sarif_uri(&edit.location) },
+                    "replacements": [{
+                        "deletedRegion": MessageFormat::sarif_region(&edit.location),
+                        "insertedContent": { "text": edit.replacement },
+                    }],
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>();
+            let mut result = json!({
+                "ruleId": diagnostic.id().to_string(),
+                "level": if diagnostic.is_error() { "error" } else { "warning" },
+                "message": { "text": message },
+                "locations": [{ "physicalLocation": physical_location }],
+                "properties": {
+                    "kind": MessageFormat::kind(&diagnostic),
+                    "arguments": diagnostic.arguments().iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                },
+            });
+            if !fixes.is_empty() {
+                result["fixes"] = json!(fixes);
+            }
+            result
+        }).collect::<Vec<_>>();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "whack",
+                        "informationUri": "https://github.com/whackengine/sdk",
+                        "rules": rule_ids.iter().map(|id| json!({ "id": id.to_string() })).collect::<Vec<_>>(),
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+
+    fn sarif_region(location: &Location) -> Value {
+        json!({
+            "startLine": location.first_line_number(),
+            "startColumn": location.first_column() + 1,
+            "endLine": location.last_line_number(),
+            "endColumn": location.last_column() + 1,
+        })
+    }
+
+    fn sarif_uri(location: &Location) -> Option<String> {
+        MessageFormat::file_path(location).and_then(|file| url::Url::from_file_path(file).ok()).map(|uri| uri.to_string())
+    }
+
+    /// Returns the name of the diagnostic kind, such as
+    /// `ImplicitCoercionToUnrelatedType`. Diagnostics of the parser
+    /// are described by their category instead.
+    fn kind(diagnostic: &WhackDiagnostic) -> &'static str {
+        if let Some(kind) = WhackDiagnosticKind::from_id(diagnostic.id()) {
+            kind.name()
+        } else if diagnostic.is_syntax_error() {
+            "syntax-error"
+        } else if diagnostic.is_verify_error() {
+            "verify-error"
+        } else {
+            "warning"
+        }
+    }
+
+    fn file_path(location: &Location) -> Option<String> {
+        let file_path = location.compilation_unit().file_path()?;
+        Some(file_path.strip_prefix(r"\\?\").map(|s| s.to_owned()).unwrap_or(file_path))
+    }
+}
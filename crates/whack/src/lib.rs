@@ -1,3 +1,7 @@
+pub mod codegen;
+
 pub mod commandprocesses;
 
+pub mod lsp;
+
 pub mod packagemanager;
\ No newline at end of file
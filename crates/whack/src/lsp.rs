@@ -0,0 +1,5 @@
+mod language_server;
+pub use language_server::*;
+
+mod node_locator;
+pub use node_locator::*;
@@ -0,0 +1,628 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types as lsp;
+use lsp_types::notification::Notification as _;
+use lsp_types::request::Request as _;
+use semver::VersionReq;
+use whackengine_verifier::ns::*;
+use crate::commandprocesses::*;
+use crate::packagemanager::*;
+use super::NodeLocator;
+
+/// Language server communicating through JSON-RPC over the standard
+/// input and output streams.
+///
+/// The directed acyclic graph is retrieved at startup and again whenever
+/// one of its manifests is modified. Dependencies are not fetched by the
+/// language server; missing dependencies are fetched by `whack check`.
+///
+/// Sources are verified again, with the contents of open documents, once
+/// pending messages are handled, or before answering a request. Changes
+/// that leave every verified source text as it was, such as reopening
+/// an unmodified file, do not trigger verification. The semantic database
+/// cannot drop definitions, so every package of the graph is verified
+/// again in a new database, including packages that neither contain
+/// nor depend on a changed source. Restricting verification to the
+/// package of a changed source and the packages depending on it is
+/// not supported yet.
+///
+/// Package processing failures are reported through `window/showMessage`.
+pub struct LanguageServer {
+    connection: Connection,
+    dir: PathBuf,
+    builtins: PathBuf,
+    package: Option<String>,
+    defined_constants: Vec<(String, String)>,
+    lints: Vec<(String, WarningLevel)>,
+    messages: DiagnosticMessages,
+    dag: Option<Dag>,
+    /// Last modification time of manifests contributing to `dag`.
+    manifests: Vec<(PathBuf, Option<SystemTime>)>,
+    /// Contents of open documents by canonical file path.
+    documents: HashMap<String, String>,
+    verification: Option<(Rc<Database>, Verifier)>,
+    /// Compilation units of the last verification by canonical file path.
+    verified_sources: HashMap<String, Rc<CompilationUnit>>,
+    outdated: bool,
+    published_diagnostics: HashSet<lsp::Url>,
+}
+
+impl LanguageServer {
+    pub fn new(connection: Connection, dir: PathBuf, builtins: PathBuf, package: Option<String>, defined_constants: Vec<(String, String)>, lints: Vec<(String, WarningLevel)>, messages: DiagnosticMessages) -> Self {
+        Self {
+            connection,
+            dir,
+            builtins,
+            package,
+            defined_constants,
+            lints,
+            messages,
+            dag: None,
+            manifests: vec![],
+            documents: HashMap::new(),
+            verification: None,
+            verified_sources: HashMap::new(),
+            outdated: true,
+            published_diagnostics: HashSet::new(),
+        }
+    }
+
+    pub fn capabilities() -> lsp::ServerCapabilities {
+        lsp::ServerCapabilities {
+            text_document_sync: Some(lsp::TextDocumentSyncCapability::Kind(lsp::TextDocumentSyncKind::FULL)),
+            hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+            definition_provider: Some(lsp::OneOf::Left(true)),
+            completion_provider: Some(lsp::CompletionOptions {
+                trigger_characters: Some(vec![".".to_owned()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Handles messages until the client shuts down the server.
+    pub async fn run(mut self) {
+        self.retrieve_dag().await;
+
+        loop {
+            if self.outdated && self.connection.receiver.is_empty() {
+                self.verify().await;
+            }
+            let Ok(message) = self.connection.receiver.recv() else {
+                break;
+            };
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request).unwrap_or(true) {
+                        break;
+                    }
+                    self.handle_request(request).await;
+                },
+                Message::Notification(notification) => {
+                    self.handle_notification(notification);
+                },
+                Message::Response(_) => {},
+            }
+        }
+    }
+
+    async fn handle_request(&mut self, request: Request) {
+        if self.outdated {
+            self.verify().await;
+        }
+        let id = request.id.clone();
+        let response = match request.method.as_str() {
+            lsp::request::HoverRequest::METHOD => {
+                let params = serde_json::from_value::<lsp::HoverParams>(request.params).ok();
+                Response::new_ok(id, params.and_then(|params| self.hover(&params.text_document_position_params)))
+            },
+            lsp::request::GotoDefinition::METHOD => {
+                let params = serde_json::from_value::<lsp::GotoDefinitionParams>(request.params).ok();
+                Response::new_ok(id, params.and_then(|params| self.definition(&params.text_document_position_params)))
+            },
+            lsp::request::Completion::METHOD => {
+                let params = serde_json::from_value::<lsp::CompletionParams>(request.params).ok();
+                Response::new_ok(id, params.and_then(|params| self.completion(&params.text_document_position)))
+            },
+            _ => Response::new_err(id, ErrorCode::MethodNotFound as i32, format!("Unhandled method {}", request.method)),
+        };
+        self.connection.sender.send(Message::Response(response)).unwrap();
+    }
+
+    fn handle_notification(&mut self, notification: Notification) {
+        match notification.method.as_str() {
+            lsp::notification::DidOpenTextDocument::METHOD => {
+                if let Ok(params) = serde_json::from_value::<lsp::DidOpenTextDocumentParams>(notification.params) {
+                    if let Some(path) = Self::file_path(&params.text_document.uri) {
+                        self.outdated |= self.differs_from_verified(&path, Some(&params.text_document.text));
+                        self.documents.insert(path, params.text_document.text);
+                    }
+                }
+            },
+            lsp::notification::DidChangeTextDocument::METHOD => {
+                if let Ok(params) = serde_json::from_value::<lsp::DidChangeTextDocumentParams>(notification.params) {
+                    if let (Some(path), Some(change)) = (Self::file_path(&params.text_document.uri), params.content_changes.into_iter().last()) {
+                        self.outdated |= self.differs_from_verified(&path, Some(&change.text));
+                        self.documents.insert(path, change.text);
+                    }
+                }
+            },
+            lsp::notification::DidCloseTextDocument::METHOD => {
+                if let Ok(params) = serde_json::from_value::<lsp::DidCloseTextDocumentParams>(notification.params) {
+                    if let Some(path) = Self::file_path(&params.text_document.uri) {
+                        self.documents.remove(&path);
+                        self.outdated |= self.differs_from_verified(&path, std::fs::read_to_string(&path).ok().as_deref());
+                    }
+                }
+            },
+            lsp::notification::DidSaveTextDocument::METHOD => {
+                if let Ok(params) = serde_json::from_value::<lsp::DidSaveTextDocumentParams>(notification.params) {
+                    if let Some(path) = Self::file_path(&params.text_document.uri) {
+                        if !self.documents.contains_key(&path) {
+                            self.outdated |= self.differs_from_verified(&path, std::fs::read_to_string(&path).ok().as_deref());
+                        }
+                    }
+                }
+            },
+            lsp::notification::DidChangeWatchedFiles::METHOD => {
+                self.outdated = true;
+            },
+            _ => {},
+        }
+    }
+
+    /// Whether `text` differs from the source text at `path` used
+    /// by the last verification. Sources that were not verified
+    /// are considered changed.
+    fn differs_from_verified(&self, path: &str, text: Option<&str>) -> bool {
+        match (self.verified_sources.get(path), text) {
+            (Some(cu), Some(text)) => cu.text() != text,
+            _ => true,
+        }
+    }
+
+    /// Retrieves the directed acyclic graph of the project without
+    /// fetching dependencies, along with the built-ins.
+    async fn retrieve_dag(&mut self) {
+        self.dag = None;
+        self.manifests.clear();
+
+        let (dir, lockfile, _, found_base_manifest) = CommandProcessCommons::entry_point_lookup(&self.dir);
+        if !found_base_manifest {
+            self.show_error("Currently not inside a Whack project.");
+            return;
+        }
+        let dir = PathBuf::from_str(&dir.to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
+        self.manifests.push((dir.join("whack.toml"), Self::modified(&dir.join("whack.toml"))));
+
+        let mut lockfile = lockfile.unwrap_or(WhackLockfile {
+            package: vec![]
+        });
+        let mut run_cache_file = std::fs::read_to_string(dir.join("target").join(".run-cache.toml")).ok()
+            .and_then(|text| toml::from_str::<RunCacheFile>(&text).ok())
+            .unwrap_or(RunCacheFile {
+                packages: vec![]
+            });
+        let mut conflicting_dependencies_tracker = HashMap::<String, HashMap<String, VersionReq>>::new();
+        let mut package_internator = WhackPackageInternator::new();
+
+        let mut dag = match Dag::retrieve(dir.clone(), &dir, self.package.clone(), &mut lockfile, &mut run_cache_file, &mut conflicting_dependencies_tracker, &mut package_internator, true, vec![]).await {
+            Ok((dag, _)) => dag,
+            Err(error) => {
+                self.show_error(&CommandProcessCommons::package_processing_error_message(&error));
+                return;
+            },
+        };
+        let builtins_dag = match Dag::retrieve(self.builtins.clone(), &dir, self.package.clone(), &mut lockfile, &mut run_cache_file, &mut conflicting_dependencies_tracker, &mut package_internator, true, vec![]).await {
+            Ok((dag, _)) => dag,
+            Err(error) => {
+                self.show_error(&CommandProcessCommons::package_processing_error_message(&error));
+                return;
+            },
+        };
+        dag.prepend_dag(builtins_dag);
+        dag.filter_out_duplicates();
+
+        for pckg in dag.iter() {
+            let manifest_path = pckg.absolute_path.join("whack.toml");
+            let modified = Self::modified(&manifest_path);
+            self.manifests.push((manifest_path, modified));
+
+            // Use sources generated by a previous build script run.
+            let manifest = pckg.manifest.package.as_ref().unwrap();
+            if manifest.build_script.is_some() {
+                pckg.generated_source_path.replace(Some(BuildScripts::out_dir(&dir, &manifest.name)));
+            }
+        }
+
+        self.dag = Some(dag);
+    }
+
+    async fn verify(&mut self) {
+        self.outdated = false;
+
+        // The graph is retrieved at startup; retrieve it again only
+        // once a manifest changes, so that failures are reported once.
+        if self.manifests.iter().any(|(path, modified)| &Self::modified(path) != modified) {
+            self.retrieve_dag().await;
+        }
+        let Some(dag) = self.dag.as_ref() else {
+            return;
+        };
+
+        // Verify the whole graph again; see the type documentation.
+        for pckg in dag.iter() {
+            pckg.sources.clone().clear();
+        }
+        self.verified_sources.clear();
+        match CommandProcessCommons::verify_sources(dag, &self.defined_constants, &self.lints, true, false, true, &self.documents, None) {
+            Ok(verification) => {
+                self.verification = Some(verification);
+            },
+            Err(error) => {
+                self.verification = None;
+                self.show_error(&CommandProcessCommons::package_processing_error_message(&error));
+            },
+        }
+        for pckg in dag.iter() {
+            for source in pckg.sources.iter() {
+                let cu = source.compilation_unit();
+                if let Some(path) = cu.file_path() {
+                    self.verified_sources.insert(path, cu);
+                }
+            }
+        }
+        self.publish_diagnostics();
+    }
+
+    fn publish_diagnostics(&mut self) {
+        let Some(dag) = self.dag.as_ref() else {
+            return;
+        };
+
+        let mut diagnostics: HashMap<lsp::Url, Vec<lsp::Diagnostic>> = HashMap::new();
+        for pckg in dag.iter() {
+            for source in pckg.sources.iter() {
+                for diagnostic in source.compilation_unit().nested_diagnostics() {
+                    let Some(uri) = diagnostic.location().compilation_unit().file_path().and_then(|path| lsp::Url::from_file_path(path).ok()) else {
+                        continue;
+                    };
+                    diagnostics.entry(uri).or_default().push(self.diagnostic(&diagnostic));
+                }
+            }
+        }
+
+        // Clear diagnostics of files that no longer have any.
+        for uri in self.published_diagnostics.iter() {
+            if !diagnostics.contains_key(uri) {
+                self.notify::<lsp::notification::PublishDiagnostics>(lsp::PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: vec![],
+                    version: None,
+                });
+            }
+        }
+
+        self.published_diagnostics = diagnostics.keys().cloned().collect();
+        for (uri, diagnostics) in diagnostics {
+            self.notify::<lsp::notification::PublishDiagnostics>(lsp::PublishDiagnosticsParams {
+                uri,
+                diagnostics,
+                version: None,
+            });
+        }
+    }
+
+    fn diagnostic(&self, diagnostic: &Diagnostic) -> lsp::Diagnostic {
+        let message = self.messages.format_message(diagnostic);
+        let diagnostic = WhackDiagnostic(diagnostic);
+        lsp::Diagnostic {
+            range: Self::range(&diagnostic.location()),
+            severity: Some(if diagnostic.is_error() { lsp::DiagnosticSeverity::ERROR } else { lsp::DiagnosticSeverity::WARNING }),
+            code: Some(lsp::NumberOrString::Number(diagnostic.id())),
+            source: Some("whack".to_owned()),
+            message,
+            ..Default::default()
+        }
+    }
+
+    fn hover(&self, position: &lsp::TextDocumentPositionParams) -> Option<lsp::Hover> {
+        let (host, program, offset) = self.locate(position)?;
+        let locator = NodeLocator::locate(&program, offset);
+        let (entity, location) = Self::entity_at(&host, &locator)?;
+        let entity = Self::referenced_entity(&entity);
+
+        let mut value = format!("```actionscript\n{}\n```", Self::describe(&host, &entity));
+        if let Some(asdoc) = entity.asdoc() {
+            if let Some((main_body, _)) = asdoc.main_body.as_ref() {
+                value.push_str("\n\n");
+                value.push_str(main_body);
+            }
+        }
+
+        Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(Self::range(&location)),
+        })
+    }
+
+    fn definition(&self, position: &lsp::TextDocumentPositionParams) -> Option<lsp::GotoDefinitionResponse> {
+        let (host, program, offset) = self.locate(position)?;
+        let locator = NodeLocator::locate(&program, offset);
+        let (entity, _) = Self::entity_at(&host, &locator)?;
+        let location = Self::referenced_entity(&entity).location()?;
+        let uri = lsp::Url::from_file_path(location.compilation_unit().file_path()?).ok()?;
+        Some(lsp::GotoDefinitionResponse::Scalar(lsp::Location {
+            uri,
+            range: Self::range(&location),
+        }))
+    }
+
+    fn completion(&self, position: &lsp::TextDocumentPositionParams) -> Option<lsp::CompletionResponse> {
+        let (host, program, offset) = self.locate(position)?;
+        let cu = program.location.compilation_unit();
+        let text = cu.text();
+
+        // Start of the identifier being typed
+        let start = text[..offset].char_indices().rev()
+            .take_while(|(_, ch)| ch.is_alphanumeric() || *ch == '_' || *ch == '$')
+            .last().map(|(i, _)| i).unwrap_or(offset);
+
+        let locator = NodeLocator::locate(&program, start);
+        let scope = locator.blocks.iter().rev().find_map(|block| host.node_mapping().get(block))
+            .or_else(|| host.node_mapping().get(&program))?;
+        let open_ns_set = scope.concat_open_ns_set_of_scope_chain();
+
+        let mut names: Vec<String> = vec![];
+        let mut items: Vec<lsp::CompletionItem> = vec![];
+        let mut add_item = |local_name: String, entity: Option<Entity>| {
+            if names.contains(&local_name) {
+                return;
+            }
+            if let Some(entity) = entity {
+                let entity = Self::referenced_entity(&entity);
+                items.push(lsp::CompletionItem {
+                    label: local_name.clone(),
+                    kind: Some(Self::completion_item_kind(&host, &entity)),
+                    detail: Some(Self::describe(&host, &entity)),
+                    ..Default::default()
+                });
+                names.push(local_name);
+            }
+        };
+
+        if start > 0 && text[..start].ends_with('.') {
+            // Member completion from the expression preceding the dot
+            let dot = start - 1;
+            let base_locator = NodeLocator::locate(&program, dot.saturating_sub(1));
+            let base = base_locator.expressions.iter().rev()
+                .filter(|exp| exp.location().last_offset() == dot)
+                .find_map(|exp| host.node_mapping().get(exp))?;
+
+            let candidates = if base.is::<FixtureReferenceValue>() && base.property().is::<Package>() {
+                base.property().available_definitions_in_package(&host, &open_ns_set)
+            } else if base.is::<FixtureReferenceValue>() && base.property().is::<Type>() {
+                base.property().available_static_definitions(&host, &open_ns_set)
+            } else {
+                base.static_type(&host).escape_of_nullable_or_non_nullable().available_prototype_definitions(&host, &open_ns_set)
+            };
+            for candidate in candidates.unwrap_or_default() {
+                let local_name = candidate.name().local_name();
+                let key = PropertyLookupKey::LocalName(local_name.clone());
+                add_item(local_name, PropertyLookup(&host).lookup_in_object(&base, &open_ns_set, None, &key, false).ok().flatten());
+            }
+        } else {
+            // Names available through the scope chain
+            let mut candidates: Vec<Entity> = vec![];
+            let mut scope1 = Some(scope.clone());
+            while let Some(s) = scope1 {
+                for (name, entity) in s.properties(&host).borrow().iter() {
+                    if name.accessible_from_ns_set(&host, &open_ns_set) {
+                        candidates.push(entity.clone());
+                    }
+                }
+                if s.is::<ClassScope>() || s.is::<EnumScope>() {
+                    candidates.extend(s.class().available_static_definitions(&host, &open_ns_set).unwrap_or_default());
+                    candidates.extend(s.class().available_prototype_definitions(&host, &open_ns_set).unwrap_or_default());
+                } else if s.is::<InterfaceScope>() {
+                    candidates.extend(s.interface().available_prototype_definitions(&host, &open_ns_set).unwrap_or_default());
+                } else if s.is::<PackageScope>() {
+                    candidates.extend(s.package().available_definitions_in_package(&host, &open_ns_set).unwrap_or_default());
+                }
+                for import in s.import_list().iter() {
+                    if import.is::<PackagePropertyImport>() {
+                        candidates.push(import.property());
+                    } else if import.is::<PackageWildcardImport>() {
+                        candidates.extend(import.package().available_definitions_in_package(&host, &open_ns_set).unwrap_or_default());
+                    }
+                }
+                scope1 = s.parent();
+            }
+            for candidate in candidates {
+                let local_name = candidate.name().local_name();
+                let key = PropertyLookupKey::LocalName(local_name.clone());
+                add_item(local_name, PropertyLookup(&host).lookup_in_scope_chain(&scope, None, &key).ok().flatten());
+            }
+        }
+
+        Some(lsp::CompletionResponse::Array(items))
+    }
+
+    /// Returns the semantic entity of the innermost node at the located
+    /// offset, along with the node's location.
+    fn entity_at(host: &Rc<Database>, locator: &NodeLocator) -> Option<(Entity, Location)> {
+        if let Some(drtv) = locator.definitions.last() {
+            if let Some(entity) = host.node_mapping().get(drtv) {
+                let location = entity.location().unwrap_or(drtv.location());
+                return Some((entity, location));
+            }
+        }
+        locator.expressions.iter().rev().find_map(|exp| {
+            host.node_mapping().get(exp)
+                .filter(|entity| !entity.is::<InvalidationEntity>())
+                .map(|entity| (entity, exp.location()))
+        })
+    }
+
+    /// Returns the entity that a value refers to, such as
+    /// the property of a reference value.
+    fn referenced_entity(entity: &Entity) -> Entity {
+        let mut entity = entity.clone();
+        while entity.is::<ConversionValue>() || entity.is::<NonNullValue>() {
+            entity = entity.base();
+        }
+        if entity.is::<FixtureReferenceValue>() || entity.is::<PackagePropertyImport>() {
+            entity.property()
+        } else if entity.is::<TypeConstant>() {
+            entity.referenced_type()
+        } else if entity.is::<NamespaceConstant>() {
+            entity.referenced_ns()
+        } else if entity.is::<PackageWildcardImport>() || entity.is::<PackageRecursiveImport>() {
+            entity.package()
+        } else {
+            entity
+        }
+    }
+
+    /// Describes an entity in ActionScript-like syntax.
+    fn describe(host: &Database, entity: &Entity) -> String {
+        if entity.is::<VariableSlot>() {
+            let kw = if entity.read_only(host) { "const" } else { "var" };
+            format!("{} {}: {}", kw, entity.name().local_name(), entity.static_type(host).to_string())
+        } else if entity.is::<VirtualSlot>() {
+            format!("(property) {}: {}", entity.name().local_name(), entity.static_type(host).to_string())
+        } else if entity.is::<MethodSlot>() {
+            let signature = entity.signature(host);
+            if signature.is::<FunctionType>() {
+                format!("function {}{}", entity.name().local_name(), signature.to_string().trim_start_matches("function"))
+            } else {
+                format!("function {}", entity.name().local_name())
+            }
+        } else if entity.is::<ClassType>() {
+            format!("class {}", entity.to_string())
+        } else if entity.is::<EnumType>() {
+            format!("enum {}", entity.to_string())
+        } else if entity.is::<InterfaceType>() {
+            format!("interface {}", entity.to_string())
+        } else if entity.is::<Alias>() {
+            if entity.alias_of().is::<Namespace>() {
+                format!("namespace {} = {}", entity.name().local_name(), entity.alias_of().to_string())
+            } else {
+                format!("type {} = {}", entity.name().local_name(), entity.alias_of().to_string())
+            }
+        } else if entity.is::<Package>() {
+            format!("package {}", entity.to_string())
+        } else if entity.is::<Namespace>() {
+            format!("namespace {}", entity.to_string())
+        } else if entity.is::<Type>() {
+            entity.to_string()
+        } else if entity.is::<Value>() {
+            entity.static_type(host).to_string()
+        } else {
+            entity.to_string()
+        }
+    }
+
+    fn completion_item_kind(host: &Database, entity: &Entity) -> lsp::CompletionItemKind {
+        if entity.is::<VariableSlot>() {
+            if entity.read_only(host) { lsp::CompletionItemKind::CONSTANT } else { lsp::CompletionItemKind::VARIABLE }
+        } else if entity.is::<VirtualSlot>() {
+            lsp::CompletionItemKind::PROPERTY
+        } else if entity.is::<MethodSlot>() {
+            lsp::CompletionItemKind::FUNCTION
+        } else if entity.is::<ClassType>() {
+            lsp::CompletionItemKind::CLASS
+        } else if entity.is::<EnumType>() {
+            lsp::CompletionItemKind::ENUM
+        } else if entity.is::<InterfaceType>() {
+            lsp::CompletionItemKind::INTERFACE
+        } else if entity.is::<Package>() {
+            lsp::CompletionItemKind::MODULE
+        } else {
+            lsp::CompletionItemKind::VALUE
+        }
+    }
+
+    /// Returns the verified program of a document along with
+    /// the offset of a position.
+    fn locate(&self, position: &lsp::TextDocumentPositionParams) -> Option<(Rc<Database>, Rc<Program>, usize)> {
+        let path = Self::file_path(&position.text_document.uri)?;
+        let (host, verifier) = self.verification.as_ref()?;
+        for pckg in self.dag.as_ref()?.iter() {
+            for source in pckg.sources.iter() {
+                let cu = source.compilation_unit();
+                if cu.file_path().as_ref() != Some(&path) {
+                    continue;
+                }
+                let program = match &source {
+                    WhackSource::As3(program) => program.clone(),
+                    WhackSource::Mxml(mxml) => verifier.mxml_program(mxml)?,
+                };
+                return Some((host.clone(), program, Self::offset(cu.text(), &position.position)));
+            }
+        }
+        None
+    }
+
+    fn notify<N: lsp::notification::Notification>(&self, params: N::Params) {
+        self.connection.sender.send(Message::Notification(Notification::new(N::METHOD.to_owned(), params))).unwrap();
+    }
+
+    fn show_error(&self, message: &str) {
+        self.notify::<lsp::notification::ShowMessage>(lsp::ShowMessageParams {
+            typ: lsp::MessageType::ERROR,
+            message: message.to_owned(),
+        });
+    }
+
+    fn file_path(uri: &lsp::Url) -> Option<String> {
+        Some(uri.to_file_path().ok()?.canonicalize().ok()?.to_str()?.to_owned())
+    }
+
+    fn modified(path: &PathBuf) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn range(location: &Location) -> lsp::Range {
+        let cu = location.compilation_unit();
+        lsp::Range {
+            start: Self::position(cu.text(), location.first_offset()),
+            end: Self::position(cu.text(), location.last_offset()),
+        }
+    }
+
+    /// Converts a byte offset into a position counting UTF-16 code units.
+    fn position(text: &str, offset: usize) -> lsp::Position {
+        let offset = offset.min(text.len());
+        let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        lsp::Position {
+            line: text[..line_start].matches('\n').count() as u32,
+            character: text[line_start..offset].encode_utf16().count() as u32,
+        }
+    }
+
+    /// Converts a position counting UTF-16 code units into a byte offset.
+    fn offset(text: &str, position: &lsp::Position) -> usize {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            match text[line_start..].find('\n') {
+                Some(i) => line_start += i + 1,
+                None => return text.len(),
+            }
+        }
+        let mut units = 0;
+        for (i, ch) in text[line_start..].char_indices() {
+            if units >= position.character as usize || ch == '\n' {
+                return line_start + i;
+            }
+            units += ch.len_utf16();
+        }
+        text.len()
+    }
+}
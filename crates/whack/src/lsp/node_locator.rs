@@ -0,0 +1,442 @@
+use std::rc::Rc;
+use whackengine_verifier::ns::*;
+
+/// Collects the nodes of a program that contain a source offset,
+/// each list going from the outermost to the innermost node.
+///
+/// Only nodes from the given compilation unit are considered, so that
+/// nodes from included sources do not match offsets of the including source.
+pub struct NodeLocator {
+    cu: Rc<CompilationUnit>,
+    offset: usize,
+    pub expressions: Vec<Rc<Expression>>,
+    pub directives: Vec<Rc<Directive>>,
+    pub blocks: Vec<Rc<Block>>,
+    pub function_commons: Vec<Rc<FunctionCommon>>,
+    /// Definitions whose name contains the offset.
+    pub definitions: Vec<Rc<Directive>>,
+}
+
+impl NodeLocator {
+    pub fn locate(program: &Rc<Program>, offset: usize) -> Self {
+        let mut locator = Self {
+            cu: program.location.compilation_unit(),
+            offset,
+            expressions: vec![],
+            directives: vec![],
+            blocks: vec![],
+            function_commons: vec![],
+            definitions: vec![],
+        };
+        for pckgdefn in program.packages.iter() {
+            locator.block(&pckgdefn.block);
+        }
+        locator.directives(&program.directives);
+        locator
+    }
+
+    fn contains(&self, location: &Location) -> bool {
+        Rc::ptr_eq(&location.compilation_unit(), &self.cu)
+            && location.first_offset() <= self.offset
+            && self.offset <= location.last_offset()
+    }
+
+    fn directives(&mut self, list: &[Rc<Directive>]) {
+        for drtv in list.iter() {
+            self.directive(drtv);
+        }
+    }
+
+    fn block(&mut self, block: &Rc<Block>) {
+        if !self.contains(&block.location) {
+            return;
+        }
+        self.blocks.push(block.clone());
+        self.directives(&block.directives);
+    }
+
+    fn directive(&mut self, drtv: &Rc<Directive>) {
+        // Nodes from nested compilation units are not delimited
+        // by the directive's location.
+        if let Directive::IncludeDirective(d) = drtv.as_ref() {
+            for pckgdefn in d.nested_packages.iter() {
+                self.block(&pckgdefn.block);
+            }
+            self.directives(&d.nested_directives);
+            return;
+        }
+        if let Directive::DirectiveInjection(d) = drtv.as_ref() {
+            self.directives(&d.directives.borrow());
+            return;
+        }
+
+        if !self.contains(&drtv.location()) {
+            return;
+        }
+        self.directives.push(drtv.clone());
+
+        match drtv.as_ref() {
+            Directive::ExpressionStatement(d) => self.exp(&d.expression),
+            Directive::SuperStatement(d) => self.exp_list(&d.arguments),
+            Directive::Block(d) => self.directives(&d.directives),
+            Directive::LabeledStatement(d) => self.directive(&d.substatement),
+            Directive::IfStatement(d) => {
+                self.exp(&d.test);
+                self.directive(&d.consequent);
+                if let Some(alternative) = d.alternative.as_ref() {
+                    self.directive(alternative);
+                }
+            },
+            Directive::SwitchStatement(d) => {
+                self.exp(&d.discriminant);
+                for case in d.cases.iter() {
+                    for label in case.labels.iter() {
+                        if let CaseLabel::Case((exp, _)) = label {
+                            self.exp(exp);
+                        }
+                    }
+                    self.directives(&case.directives);
+                }
+            },
+            Directive::SwitchTypeStatement(d) => {
+                self.exp(&d.discriminant);
+                for case in d.cases.iter() {
+                    if let Some(parameter) = case.parameter.as_ref() {
+                        self.typed_destructuring(parameter);
+                    }
+                    self.block(&case.block);
+                }
+            },
+            Directive::DoStatement(d) => {
+                self.directive(&d.body);
+                self.exp(&d.test);
+            },
+            Directive::WhileStatement(d) => {
+                self.exp(&d.test);
+                self.directive(&d.body);
+            },
+            Directive::ForStatement(d) => {
+                match d.init.as_ref() {
+                    Some(ForInitializer::Expression(exp)) => self.exp(exp),
+                    Some(ForInitializer::VariableDefinition(defn)) => self.variable_bindings(&defn.bindings),
+                    None => {},
+                }
+                if let Some(test) = d.test.as_ref() {
+                    self.exp(test);
+                }
+                if let Some(update) = d.update.as_ref() {
+                    self.exp(update);
+                }
+                self.directive(&d.body);
+            },
+            Directive::ForInStatement(d) => {
+                match &d.left {
+                    ForInBinding::Expression(exp) => self.exp(exp),
+                    ForInBinding::VariableDefinition(defn) => self.variable_bindings(&defn.bindings),
+                }
+                self.exp(&d.right);
+                self.directive(&d.body);
+            },
+            Directive::WithStatement(d) => {
+                self.exp(&d.object);
+                self.directive(&d.body);
+            },
+            Directive::ReturnStatement(d) => {
+                if let Some(exp) = d.expression.as_ref() {
+                    self.exp(exp);
+                }
+            },
+            Directive::ThrowStatement(d) => self.exp(&d.expression),
+            Directive::DefaultXmlNamespaceStatement(d) => self.exp(&d.right),
+            Directive::TryStatement(d) => {
+                self.block(&d.block);
+                for catch_clause in d.catch_clauses.iter() {
+                    self.typed_destructuring(&catch_clause.parameter);
+                    self.block(&catch_clause.block);
+                }
+                if let Some(finally_clause) = d.finally_clause.as_ref() {
+                    self.block(&finally_clause.block);
+                }
+            },
+            Directive::ConfigurationDirective(d) => self.directive(&d.directive),
+            Directive::UseNamespaceDirective(d) => self.exp(&d.expression),
+            Directive::VariableDefinition(d) => {
+                self.attributes(&d.attributes);
+                self.variable_bindings(&d.bindings);
+            },
+            Directive::FunctionDefinition(d) => {
+                self.attributes(&d.attributes);
+                let name_location = match &d.name {
+                    FunctionName::Identifier((_, loc))
+                    | FunctionName::Getter((_, loc))
+                    | FunctionName::Setter((_, loc))
+                    | FunctionName::Constructor((_, loc)) => loc,
+                };
+                if self.contains(name_location) {
+                    self.definitions.push(drtv.clone());
+                }
+                self.function_common(&d.common);
+            },
+            Directive::ClassDefinition(d) => {
+                self.attributes(&d.attributes);
+                if self.contains(&d.name.1) {
+                    self.definitions.push(drtv.clone());
+                }
+                if let Some(exp) = d.extends_clause.as_ref() {
+                    self.exp(exp);
+                }
+                if let Some(list) = d.implements_clause.as_ref() {
+                    self.exp_list(list);
+                }
+                self.block(&d.block);
+            },
+            Directive::EnumDefinition(d) => {
+                self.attributes(&d.attributes);
+                if self.contains(&d.name.1) {
+                    self.definitions.push(drtv.clone());
+                }
+                if let Some(exp) = d.as_clause.as_ref() {
+                    self.exp(exp);
+                }
+                self.block(&d.block);
+            },
+            Directive::InterfaceDefinition(d) => {
+                self.attributes(&d.attributes);
+                if self.contains(&d.name.1) {
+                    self.definitions.push(drtv.clone());
+                }
+                if let Some(list) = d.extends_clause.as_ref() {
+                    self.exp_list(list);
+                }
+                self.block(&d.block);
+            },
+            Directive::TypeDefinition(d) => {
+                self.attributes(&d.attributes);
+                if self.contains(&d.left.1) {
+                    self.definitions.push(drtv.clone());
+                }
+                self.exp(&d.right);
+            },
+            Directive::NamespaceDefinition(d) => {
+                self.attributes(&d.attributes);
+                if self.contains(&d.left.1) {
+                    self.definitions.push(drtv.clone());
+                }
+                if let Some(exp) = d.right.as_ref() {
+                    self.exp(exp);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn attributes(&mut self, list: &[Attribute]) {
+        for attr in list.iter() {
+            if let Attribute::Expression(exp) = attr {
+                self.exp(exp);
+            }
+        }
+    }
+
+    fn variable_bindings(&mut self, list: &[Rc<VariableBinding>]) {
+        for binding in list.iter() {
+            self.typed_destructuring(&binding.destructuring);
+            if let Some(init) = binding.initializer.as_ref() {
+                self.exp(init);
+            }
+        }
+    }
+
+    fn typed_destructuring(&mut self, destructuring: &TypedDestructuring) {
+        self.exp(&destructuring.destructuring);
+        if let Some(t) = destructuring.type_annotation.as_ref() {
+            self.exp(t);
+        }
+    }
+
+    fn function_common(&mut self, common: &Rc<FunctionCommon>) {
+        if !self.contains(&common.location) {
+            return;
+        }
+        self.function_commons.push(common.clone());
+        if let Some(this_param) = common.signature.this_parameter.as_ref() {
+            self.exp(&this_param.type_annotation);
+        }
+        for param in common.signature.parameters.iter() {
+            self.typed_destructuring(&param.destructuring);
+            if let Some(default_value) = param.default_value.as_ref() {
+                self.exp(default_value);
+            }
+        }
+        if let Some(result_type) = common.signature.result_type.as_ref() {
+            self.exp(result_type);
+        }
+        match common.body.as_ref() {
+            Some(FunctionBody::Block(block)) => self.block(block),
+            Some(FunctionBody::Expression(exp)) => self.exp(exp),
+            None => {},
+        }
+    }
+
+    fn exp_list(&mut self, list: &[Rc<Expression>]) {
+        for exp in list.iter() {
+            self.exp(exp);
+        }
+    }
+
+    fn elements(&mut self, list: &[Element]) {
+        for element in list.iter() {
+            match element {
+                Element::Expression(exp) => self.exp(exp),
+                Element::Rest((exp, _)) => self.exp(exp),
+                Element::Elision => {},
+            }
+        }
+    }
+
+    fn qualified_identifier(&mut self, id: &QualifiedIdentifier) {
+        if let Some(qual) = id.qualifier.as_ref() {
+            self.exp(qual);
+        }
+        if let QualifiedIdentifierIdentifier::Brackets(exp) = &id.id {
+            self.exp(exp);
+        }
+    }
+
+    fn xml_element(&mut self, element: &XmlElement) {
+        if let XmlTagName::Expression(exp) = &element.name {
+            self.exp(exp);
+        }
+        for attr in element.attributes.iter() {
+            if let XmlAttributeValue::Expression(exp) = &attr.value {
+                self.exp(exp);
+            }
+        }
+        if let Some(exp) = element.attribute_expression.as_ref() {
+            self.exp(exp);
+        }
+        if let Some(content) = element.content.as_ref() {
+            self.xml_content(content);
+        }
+    }
+
+    fn xml_content(&mut self, list: &[Rc<XmlContent>]) {
+        for content in list.iter() {
+            match content.as_ref() {
+                XmlContent::Element(element) => self.xml_element(element),
+                XmlContent::Expression(exp) => self.exp(exp),
+                _ => {},
+            }
+        }
+    }
+
+    fn exp(&mut self, exp: &Rc<Expression>) {
+        if !self.contains(&exp.location()) {
+            return;
+        }
+        self.expressions.push(exp.clone());
+
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(e) => self.qualified_identifier(e),
+            Expression::Paren(e) => self.exp(&e.expression),
+            Expression::Xml(e) => self.xml_element(&e.element),
+            Expression::XmlList(e) => self.xml_content(&e.content),
+            Expression::ArrayLiteral(e) => self.elements(&e.elements),
+            Expression::VectorLiteral(e) => {
+                self.exp(&e.element_type);
+                self.elements(&e.elements);
+            },
+            Expression::ObjectInitializer(e) => {
+                for field in e.fields.iter() {
+                    match field.as_ref() {
+                        InitializerField::Field { name, value, .. } => {
+                            match &name.0 {
+                                FieldName::Identifier(id) => self.qualified_identifier(id),
+                                FieldName::Brackets(exp)
+                                | FieldName::StringLiteral(exp)
+                                | FieldName::NumericLiteral(exp) => self.exp(exp),
+                            }
+                            if let Some(value) = value.as_ref() {
+                                self.exp(value);
+                            }
+                        },
+                        InitializerField::Rest((exp, _)) => self.exp(exp),
+                    }
+                }
+            },
+            Expression::Function(e) => self.function_common(&e.common),
+            Expression::New(e) => {
+                self.exp(&e.base);
+                if let Some(arguments) = e.arguments.as_ref() {
+                    self.exp_list(arguments);
+                }
+            },
+            Expression::Member(e) => {
+                self.exp(&e.base);
+                self.qualified_identifier(&e.identifier);
+            },
+            Expression::ComputedMember(e) => {
+                self.exp(&e.base);
+                self.exp(&e.key);
+            },
+            Expression::Descendants(e) => {
+                self.exp(&e.base);
+                self.qualified_identifier(&e.identifier);
+            },
+            Expression::Filter(e) => {
+                self.exp(&e.base);
+                self.exp(&e.test);
+            },
+            Expression::Super(e) => {
+                if let Some(object) = e.object.as_ref() {
+                    self.exp_list(object);
+                }
+            },
+            Expression::Call(e) => {
+                self.exp(&e.base);
+                self.exp_list(&e.arguments);
+            },
+            Expression::WithTypeArguments(e) => {
+                self.exp(&e.base);
+                self.exp_list(&e.arguments);
+            },
+            Expression::Unary(e) => self.exp(&e.expression),
+            Expression::OptionalChaining(e) => {
+                self.exp(&e.base);
+                self.exp(&e.expression);
+            },
+            Expression::Binary(e) => {
+                self.exp(&e.left);
+                self.exp(&e.right);
+            },
+            Expression::Conditional(e) => {
+                self.exp(&e.test);
+                self.exp(&e.consequent);
+                self.exp(&e.alternative);
+            },
+            Expression::Assignment(e) => {
+                self.exp(&e.left);
+                self.exp(&e.right);
+            },
+            Expression::Sequence(e) => {
+                self.exp(&e.left);
+                self.exp(&e.right);
+            },
+            Expression::NullableType(e) => self.exp(&e.base),
+            Expression::NonNullableType(e) => self.exp(&e.base),
+            Expression::ArrayType(e) => self.exp(&e.expression),
+            Expression::TupleType(e) => self.exp_list(&e.expressions),
+            Expression::FunctionType(e) => {
+                for param in e.parameters.iter() {
+                    if let Some(t) = param.type_expression.as_ref() {
+                        self.exp(t);
+                    }
+                }
+                if let Some(result_type) = e.result_type.as_ref() {
+                    self.exp(result_type);
+                }
+            },
+            _ => {},
+        }
+    }
+}
@@ -18,6 +18,110 @@ async fn main() {
                 .arg(clap::arg!(--"define" <KEYVALUE>)
                     .help("Defines a configuration constant with the syntax NS::NAME=val.")
                     .action(ArgAction::Append))
+                .arg(clap::arg!(-W --"warn" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as a warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-A --"allow" <NAME>)
+                    .help("Does not report a warning, given by diagnostic name or ID.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-D --"deny" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as an error. Use \"warnings\" for every warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(--"locale" <LOCALE>)
+                    .help("Locale of diagnostic messages, such as pt-BR, or path to a TOML file of messages. Defaults to the environment locale."))
+                .arg(clap::arg!(--"locked")
+                    .help("Requires the lock file to be up to date."))
+                .arg(clap::arg!(--"frozen")
+                    .help("Requires the lock file to be up to date and disallows network access."))
+                .arg(clap::arg!(--"message-format" <FORMAT>)
+                    .help("Format of the reported diagnostics.")
+                    .value_parser(whackengine_whack::commandprocesses::MessageFormat::VALUES)
+                    .default_value("human"))
+        )
+        .subcommand(
+            clap::command!("fix")
+                .about("Applies the machine-applicable fixes of diagnostics to ActionScript sources.")
+                .arg(clap::arg!(--"builtins" <PATH>)
+                    .help("Path to the Whack package defining the ActionScript built-ins."))
+                .arg(clap::arg!(--"package" <NAME>)
+                    .help("For a workspace, specifies the Whack package to operate on.")
+                    .alias("p"))
+                .arg(clap::arg!(--"path" <NAME>)
+                    .help("Specifies the path operate on."))
+                .arg(clap::arg!(--"define" <KEYVALUE>)
+                    .help("Defines a configuration constant with the syntax NS::NAME=val.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-W --"warn" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as a warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-A --"allow" <NAME>)
+                    .help("Does not report a warning, given by diagnostic name or ID.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-D --"deny" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as an error. Use \"warnings\" for every warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(--"locale" <LOCALE>)
+                    .help("Locale of diagnostic messages, such as pt-BR, or path to a TOML file of messages. Defaults to the environment locale."))
+                .arg(clap::arg!(--"locked")
+                    .help("Requires the lock file to be up to date."))
+                .arg(clap::arg!(--"frozen")
+                    .help("Requires the lock file to be up to date and disallows network access."))
+                .arg(clap::arg!(--"dry-run")
+                    .help("Reports the fixes without modifying source files."))
+        )
+        .subcommand(
+            clap::command!("build")
+                .about("Verifies ActionScript sources and generates JavaScript.")
+                .arg(clap::arg!(--"builtins" <PATH>)
+                    .help("Path to the Whack package defining the ActionScript built-ins."))
+                .arg(clap::arg!(--"package" <NAME>)
+                    .help("For a workspace, specifies the Whack package to operate on.")
+                    .alias("p"))
+                .arg(clap::arg!(--"path" <NAME>)
+                    .help("Specifies the path operate on."))
+                .arg(clap::arg!(--"define" <KEYVALUE>)
+                    .help("Defines a configuration constant with the syntax NS::NAME=val.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-W --"warn" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as a warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-A --"allow" <NAME>)
+                    .help("Does not report a warning, given by diagnostic name or ID.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-D --"deny" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as an error. Use \"warnings\" for every warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(--"locale" <LOCALE>)
+                    .help("Locale of diagnostic messages, such as pt-BR, or path to a TOML file of messages. Defaults to the environment locale."))
+                .arg(clap::arg!(--"locked")
+                    .help("Requires the lock file to be up to date."))
+                .arg(clap::arg!(--"frozen")
+                    .help("Requires the lock file to be up to date and disallows network access."))
+        )
+        .subcommand(
+            clap::command!("lsp")
+                .about("Runs the language server over the standard input and output streams.")
+                .arg(clap::arg!(--"builtins" <PATH>)
+                    .help("Path to the Whack package defining the ActionScript built-ins."))
+                .arg(clap::arg!(--"package" <NAME>)
+                    .help("For a workspace, specifies the Whack package to operate on.")
+                    .alias("p"))
+                .arg(clap::arg!(--"path" <NAME>)
+                    .help("Specifies the path operate on."))
+                .arg(clap::arg!(--"define" <KEYVALUE>)
+                    .help("Defines a configuration constant with the syntax NS::NAME=val.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-W --"warn" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as a warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-A --"allow" <NAME>)
+                    .help("Does not report a warning, given by diagnostic name or ID.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(-D --"deny" <NAME>)
+                    .help("Reports a warning, given by diagnostic name or ID, as an error. Use \"warnings\" for every warning.")
+                    .action(ArgAction::Append))
+                .arg(clap::arg!(--"locale" <LOCALE>)
+                    .help("Locale of diagnostic messages, such as pt-BR, or path to a TOML file of messages. Defaults to the environment locale."))
         );
 
     let matches = cmd.get_matches();
@@ -26,6 +130,15 @@ async fn main() {
         Some(("check", matches)) => {
             whackengine_whack::commandprocesses::check_process(matches).await;
         },
+        Some(("fix", matches)) => {
+            whackengine_whack::commandprocesses::fix_process(matches).await;
+        },
+        Some(("build", matches)) => {
+            whackengine_whack::commandprocesses::build_process(matches).await;
+        },
+        Some(("lsp", matches)) => {
+            whackengine_whack::commandprocesses::lsp_process(matches).await;
+        },
         _ => unreachable!(),
     }
 }
\ No newline at end of file
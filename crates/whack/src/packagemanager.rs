@@ -13,5 +13,11 @@ pub use run_cache_file::*;
 mod dag;
 pub use dag::*;
 
+mod whack_registry;
+pub use whack_registry::*;
+
+mod git_dependency;
+pub use git_dependency::*;
+
 mod dependency_update;
 pub use dependency_update::*;
\ No newline at end of file
@@ -2,13 +2,13 @@ use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::path::PathBuf;
 use std::str::FromStr;
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 use crate::packagemanager::*;
-use crate::commandprocesses::WhackPackageProcessingError;
+use crate::commandprocesses::{CommandProcessCommons, WhackPackageProcessingError};
 use by_address::ByAddress;
 use hydroperfox_filepaths::FlexPath;
-use colored::Colorize;
 use semver::VersionReq;
+use whackengine_verifier::ns::CompilerWarningOptions;
 
 /// Directed acyclic graph of the dependency tree.
 #[derive(Clone)]
@@ -19,13 +19,29 @@ pub struct Dag {
 }
 
 impl Dag {
+    /// Constructs a directed acyclic graph from packages in ascending order.
+    pub fn from_packages(packages: &[Rc<WhackPackage>]) -> Dag {
+        let mut edges: Vec<DagEdge> = vec![];
+        for (i, pckg) in packages.iter().enumerate() {
+            edges.push(DagEdge {
+                from: pckg.clone(),
+                to: packages.get(i + 1).unwrap_or(pckg).clone(),
+            });
+        }
+        Dag {
+            edges,
+            first: packages.first().unwrap().clone(),
+            last: packages.last().unwrap().clone(),
+        }
+    }
+
     /// Retrieves the directed acyclic graph of the dependency tree.
     ///
     /// # Parameters
     /// 
     /// - `entry_dir` - The directory where the entry point "whack.toml" file lies and where
     ///   the "target" directory is stored.
-    pub async fn retrieve(mut dir: PathBuf, entry_dir: &PathBuf, package: Option<String>, lockfile: &mut WhackLockfile, run_cache_file: &mut RunCacheFile, conflicting_dependencies_tracker: &mut HashMap<String, HashMap<String, VersionReq>>, package_internator: &mut WhackPackageInternator, cycle_prevention_list: Vec<PathBuf>) -> Result<(Dag, Dag), WhackPackageProcessingError> {
+    pub async fn retrieve(mut dir: PathBuf, entry_dir: &PathBuf, package: Option<String>, lockfile: &mut WhackLockfile, run_cache_file: &mut RunCacheFile, conflicting_dependencies_tracker: &mut HashMap<String, HashMap<String, VersionReq>>, package_internator: &mut WhackPackageInternator, offline: bool, cycle_prevention_list: Vec<PathBuf>) -> Result<(Dag, Dag), WhackPackageProcessingError> {
         if cycle_prevention_list.contains(&dir.canonicalize().unwrap()) {
             return Err(WhackPackageProcessingError::CircularDependency { directory: dir.to_str().unwrap().to_owned() });
         }
@@ -63,7 +79,7 @@ impl Dag {
 
                 if !package_ok {
                     // Read the specified package's manifest and move into its directory
-                    let (new_dir, new_manifest_path, new_manifest) = Dag::move_into_workspace_member(&flexdir, p, &workspace.members);
+                    let (new_dir, new_manifest_path, new_manifest) = Dag::move_into_workspace_member(&flexdir, p, &workspace.members)?;
                     dir = new_dir;
                     flexdir = FlexPath::new_native(&dir.to_str().unwrap());
                     manifest_path = new_manifest_path;
@@ -83,6 +99,16 @@ impl Dag {
             return Err(WhackPackageProcessingError::ManifestIsNotAPackage { manifest_path: manifest_path.to_str().unwrap().to_owned() });
         }
 
+        // Make sure lints are known.
+        if let Some(lints) = manifest.lints.as_ref() {
+            let mut probe = CompilerWarningOptions::default();
+            for (name, level) in lints.iter() {
+                if !CommandProcessCommons::apply_lint(&mut probe, name, level.warning_level()) {
+                    return Err(WhackPackageProcessingError::UnknownLint { manifest_path: manifest_path.to_str().unwrap().to_owned(), name: name.clone() });
+                }
+            }
+        }
+
         // Check for manifest updates (check the RunCacheFile). Mutate the
         // RunCacheFile, as well; writing new content to it.
         let manifest_last_modified = std::fs::metadata(&manifest_path).unwrap().modified().unwrap();
@@ -107,21 +133,18 @@ impl Dag {
                 ManifestDependency::Version(ver) => {
                     let ver = VersionReq::from_str(ver);
                     if ver.is_err() {
-                        println!("{} Whack manifest at {:?} contains invalid SemVer version at a dependency.", "Error:".red(), manifest_path);
-                        std::process::exit(1);
+                        return Err(WhackPackageProcessingError::InvalidDependencyVersion { manifest_path: manifest_path.to_str().unwrap().to_owned() });
                     }
                     tracker1.insert(name.clone(), ver.unwrap());
                 },
                 ManifestDependency::Advanced { version, path, git, .. } => {
                     if path.is_some() && git.is_some() {
-                        println!("{} Whack manifest at {:?} contains a dependency that is a Git or local dependency at the same time.", "Error:".red(), manifest_path);
-                        std::process::exit(1);
+                        return Err(WhackPackageProcessingError::AmbiguousDependencySource { manifest_path: manifest_path.to_str().unwrap().to_owned() });
                     }
                     if let Some(version) = version {
                         let ver = VersionReq::from_str(version);
                         if ver.is_err() {
-                            println!("{} Whack manifest at {:?} contains invalid SemVer version at a dependency.", "Error:".red(), manifest_path);
-                            std::process::exit(1);
+                            return Err(WhackPackageProcessingError::InvalidDependencyVersion { manifest_path: manifest_path.to_str().unwrap().to_owned() });
                         }
                         tracker1.insert(name.clone(), ver.unwrap());
                     }
@@ -135,9 +158,13 @@ impl Dag {
         // Remember that the lock file must be considered for the
         // exact versions of registry dependencies.
         if manifest_updated {
-            Box::pin(DependencyUpdate::update_dependencies(entry_dir, &manifest, run_cache_file, conflicting_dependencies_tracker, lockfile)).await?;
+            Box::pin(DependencyUpdate::update_dependencies(entry_dir, &manifest, run_cache_file, conflicting_dependencies_tracker, lockfile, offline)).await?;
         }
 
+        // Report conflicting version requirements even when
+        // dependencies are up to date.
+        DependencyUpdate::check_conflicts(&manifest, conflicting_dependencies_tracker, lockfile)?;
+
         // Build a directed acyclic graph (DAG) of the dependencies:
         // one for the project's dependencies and one for the
         // build script's dependencies.
@@ -158,18 +185,20 @@ impl Dag {
                 match dep {
                     ManifestDependency::Version(_) => {
                         let next_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/downloads", dep_name]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
-                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, next_cycle_prevention_list.clone())).await?;
+                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, offline, next_cycle_prevention_list.clone())).await?;
                         do_append_dag(prepend_dag_1, &mut edges1, &mut first1, &mut last1);
                         do_append_dag(prepend_dag_2, &mut edges2, &mut first2, &mut last2);
                     },
-                    ManifestDependency::Advanced { path, .. } => {
+                    ManifestDependency::Advanced { path, git, .. } => {
                         let next_dir: PathBuf;
                         if let Some(path) = path {
                             next_dir = PathBuf::from_str(&FlexPath::from_n_native([dir.to_str().unwrap(), path]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
+                        } else if git.is_some() {
+                            next_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/git", dep_name]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
                         } else {
                             next_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/downloads", dep_name]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
                         }
-                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, next_cycle_prevention_list.clone())).await?;
+                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, offline, next_cycle_prevention_list.clone())).await?;
                         do_append_dag(prepend_dag_1, &mut edges1, &mut first1, &mut last1);
                         do_append_dag(prepend_dag_2, &mut edges2, &mut first2, &mut last2);
                     },
@@ -182,18 +211,20 @@ impl Dag {
                 match dep {
                     ManifestDependency::Version(_version) => {
                         let next_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/downloads", dep_name]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
-                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, next_cycle_prevention_list.clone())).await?;
+                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, offline, next_cycle_prevention_list.clone())).await?;
                         do_append_dag(prepend_dag_1, &mut edges2, &mut first2, &mut last2);
                         do_append_dag(prepend_dag_2, &mut edges2, &mut first2, &mut last2);
                     },
-                    ManifestDependency::Advanced { path, .. } => {
+                    ManifestDependency::Advanced { path, git, .. } => {
                         let next_dir: PathBuf;
                         if let Some(path) = path {
                             next_dir = PathBuf::from_str(&FlexPath::from_n_native([dir.to_str().unwrap(), path]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
+                        } else if git.is_some() {
+                            next_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/git", dep_name]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
                         } else {
                             next_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/downloads", dep_name]).to_string_with_flex_separator()).unwrap().canonicalize().unwrap();
                         }
-                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, next_cycle_prevention_list.clone())).await?;
+                        let (prepend_dag_1, prepend_dag_2) = Box::pin(Dag::retrieve(next_dir, entry_dir, None, lockfile, run_cache_file, conflicting_dependencies_tracker, package_internator, offline, next_cycle_prevention_list.clone())).await?;
                         do_append_dag(prepend_dag_1, &mut edges2, &mut first2, &mut last2);
                         do_append_dag(prepend_dag_2, &mut edges2, &mut first2, &mut last2);
                     },
@@ -243,7 +274,7 @@ impl Dag {
         ))
     }
 
-    fn move_into_workspace_member(flexdir: &FlexPath, package: &str, members: &Vec<String>) -> (PathBuf, PathBuf, WhackManifest) {
+    fn move_into_workspace_member(flexdir: &FlexPath, package: &str, members: &[String]) -> Result<(PathBuf, PathBuf, WhackManifest), WhackPackageProcessingError> {
         for member in members.iter() {
             let member_flexdir = flexdir.resolve(member);
             let member_manifest_flexpath = member_flexdir.resolve("whack.toml");
@@ -258,20 +289,21 @@ impl Dag {
                     Ok(m) => {
                         if let Some(p) = m.package.as_ref() {
                             if p.name == package {
-                                return (member_dir, member_manifest_path, m);
+                                return Ok((member_dir, member_manifest_path, m));
                             }
                         }
                     },
                     Err(error) => {
-                        println!("{} Whack manifest at {} contains invalid TOML: {}", "Error:".red(), member_manifest_path.to_str().unwrap(), error.message());
-                        std::process::exit(1);
+                        return Err(WhackPackageProcessingError::InvalidManifest {
+                            manifest_path: member_manifest_path.to_str().unwrap().to_owned(),
+                            message: error.message().to_owned(),
+                        });
                     }
                 }
             }
         }
 
-        println!("{} Could not find member {}", "Error:".red(), package);
-        std::process::exit(1);
+        Err(WhackPackageProcessingError::WorkspaceMemberNotFound { name: package.to_owned() })
     }
 
     fn check_manifest_modified(manifest_last_modified: SystemTime, cur_relative_path: String, run_cache_file: &mut RunCacheFile, dependencies: Option<&HashMap<String, ManifestDependency>>, build_dependencies: Option<&HashMap<String, ManifestDependency>>, flexdir: &FlexPath, entry_dir: &PathBuf) -> bool {
@@ -282,7 +314,8 @@ impl Dag {
                 found_run_cache = true;
 
                 // Found the package into the run cache file
-                if (SystemTime::UNIX_EPOCH + Duration::from_secs(p.manifest_last_modified)) == manifest_last_modified {
+                // The run cache file stores seconds.
+                if p.manifest_last_modified == manifest_last_modified.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs() {
                     manifest_updated = false;
                 } else {
                     p.manifest_last_modified = manifest_last_modified.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
@@ -307,7 +340,22 @@ impl Dag {
         if let Some(deps1) = build_dependencies {
             deps.extend(deps1.iter().map(|(k, v)| (k.clone(), v.clone())));
         }
-        for (_, dep) in deps.iter() {
+        for (dep_name, dep) in deps.iter() {
+            // Registry dependencies that have not been downloaded yet
+            // or Git dependencies that have not been checked out yet
+            let download_dir = match dep {
+                ManifestDependency::Version(_) => Some("target/downloads"),
+                ManifestDependency::Advanced { path: None, git: None, .. } => Some("target/downloads"),
+                ManifestDependency::Advanced { path: None, git: Some(_), .. } => Some("target/git"),
+                _ => None,
+            };
+            if let Some(download_dir) = download_dir {
+                let downloaded_manifest_path = FlexPath::from_n_native([entry_dir.to_str().unwrap(), download_dir, dep_name, "whack.toml"]).to_string_with_flex_separator();
+                if !std::fs::exists(&downloaded_manifest_path).unwrap() {
+                    manifest_updated = true;
+                }
+            }
+
             if let ManifestDependency::Advanced { ref path, .. } = dep {
                 if let Some(path) = path {
                     let local_dep_flexdir = flexdir.resolve(path);
@@ -360,7 +408,9 @@ impl Dag {
         let mut last: Option<Rc<WhackPackage>> = None;
         let mut found: HashSet<ByAddress<Rc<WhackPackage>>> = HashSet::new();
 
-        for pckg in self.iter() {
+        // Edges are stored in ascending order; following them through `iter()`
+        // would revisit packages that occur more than once.
+        for pckg in self.edges.iter().map(|edge| edge.from.clone()) {
             if found.contains(&ByAddress(pckg.clone())) {
                 continue;
             }
@@ -1,17 +1,15 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, path::{Path, PathBuf}, str::FromStr};
 use colored::Colorize;
+use hydroperfox_filepaths::FlexPath;
 use lazy_regex::regex_is_match;
-use semver::VersionReq;
+use semver::{Version, VersionReq};
 use crate::commandprocesses::WhackPackageProcessingError;
 use crate::packagemanager::*;
 
 pub struct DependencyUpdate;
 
 impl DependencyUpdate {
-    pub async fn update_dependencies(entry_dir: &PathBuf, manifest: &WhackManifest, run_cache_file: &mut RunCacheFile, conflicting_dependencies_tracker: &mut HashMap<String, HashMap<String, VersionReq>>, lockfile: &mut WhackLockfile) -> Result<(), WhackPackageProcessingError> {
-        // TODO: detect version conflicts by reading the
-        // `conflicting_dependencies_tracker` table.
-
+    pub async fn update_dependencies(entry_dir: &Path, manifest: &WhackManifest, run_cache_file: &mut RunCacheFile, conflicting_dependencies_tracker: &mut HashMap<String, HashMap<String, VersionReq>>, lockfile: &mut WhackLockfile, offline: bool) -> Result<(), WhackPackageProcessingError> {
         let mut deps = HashMap::<String, ManifestDependency>::new();
         if let Some(deps1) = manifest.dependencies.as_ref() {
             deps.extend(deps1.iter().map(|(k, v)| (k.clone(), v.clone())));
@@ -21,17 +19,23 @@ impl DependencyUpdate {
         }
 
         for (name, dep) in deps.iter() {
-            if !regex_is_match!(r"[A-Za-z0-9.\-_]+", name) {
-                return Err(WhackPackageProcessingError::IllegalPackageName { name: name.clone() });
-            }
+            DependencyUpdate::check_package_name(name)?;
             match dep {
                 // Always store downloaded packages at `target/downloads`
-                ManifestDependency::Version(_ver) => {
-                    panic!("Registry dependencies are not implemented yet.");
+                ManifestDependency::Version(ver) => {
+                    let requirements = DependencyUpdate::requirements(conflicting_dependencies_tracker, manifest, name, ver);
+                    DependencyUpdate::update_registry_dependency(entry_dir, name, &requirements, lockfile, offline).await?;
                 },
-                ManifestDependency::Advanced { version: _, path, git, rev, branch } => {
+                ManifestDependency::Advanced { version, path, git, rev, branch } => {
                     if path.is_none() {
-                        panic!("Registry or Git dependencies are not implemented yet.");
+                        // Git dependencies are stored at `target/git`
+                        if let Some(git) = git {
+                            let requirements = DependencyUpdate::requirements(conflicting_dependencies_tracker, manifest, name, version.as_deref().unwrap_or("*"));
+                            DependencyUpdate::update_git_dependency(entry_dir, name, git, rev.as_ref(), branch.as_ref(), &requirements, lockfile, offline).await?;
+                        } else {
+                            let requirements = DependencyUpdate::requirements(conflicting_dependencies_tracker, manifest, name, version.as_deref().unwrap_or("*"));
+                            DependencyUpdate::update_registry_dependency(entry_dir, name, &requirements, lockfile, offline).await?;
+                        }
                     }
                 },
             }
@@ -39,4 +43,205 @@ impl DependencyUpdate {
 
         Ok(())
     }
+
+    /// Checks the resolved versions of the registry and Git dependencies
+    /// of the package described by `manifest` against the requirements
+    /// of every package in the graph that has been read so far,
+    /// including when no dependency had to be updated.
+    pub fn check_conflicts(manifest: &WhackManifest, conflicting_dependencies_tracker: &HashMap<String, HashMap<String, VersionReq>>, lockfile: &WhackLockfile) -> Result<(), WhackPackageProcessingError> {
+        let mut deps = HashMap::<String, ManifestDependency>::new();
+        if let Some(deps1) = manifest.dependencies.as_ref() {
+            deps.extend(deps1.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        if let Some(deps1) = manifest.build_dependencies.as_ref() {
+            deps.extend(deps1.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        for (name, dep) in deps.iter() {
+            let version_req = match dep {
+                ManifestDependency::Version(ver) => ver.as_str(),
+                ManifestDependency::Advanced { version, path, .. } => {
+                    if path.is_some() {
+                        continue;
+                    }
+                    version.as_deref().unwrap_or("*")
+                },
+            };
+            let Some(locked) = lockfile.package.iter().find(|p| &p.name == name) else {
+                continue;
+            };
+            let requirements = DependencyUpdate::requirements(conflicting_dependencies_tracker, manifest, name, version_req);
+            if !DependencyUpdate::matches_requirements(&requirements, &locked.version) {
+                return Err(DependencyUpdate::conflict_error(name, &requirements, vec![locked.version.clone()]));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether `name` is a legal dependency name. Dependency names
+    /// become directory names under `target`, so they may not be
+    /// `.`, `..` or contain path separators.
+    pub fn is_legal_package_name(name: &str) -> bool {
+        regex_is_match!(r"^[A-Za-z0-9_\-][A-Za-z0-9._\-]*$", name) && name != "." && name != ".."
+    }
+
+    fn check_package_name(name: &str) -> Result<(), WhackPackageProcessingError> {
+        if DependencyUpdate::is_legal_package_name(name) {
+            Ok(())
+        } else {
+            Err(WhackPackageProcessingError::IllegalPackageName { name: name.to_owned() })
+        }
+    }
+
+    /// Collects the version requirements on the dependency `name` from every
+    /// package in the graph that has been read so far, as (requirer, requirement) pairs.
+    /// `version_req` is the requirement of the package described by `manifest`.
+    fn requirements(conflicting_dependencies_tracker: &HashMap<String, HashMap<String, VersionReq>>, manifest: &WhackManifest, name: &str, version_req: &str) -> Vec<(String, VersionReq)> {
+        let package_name = &manifest.package.as_ref().unwrap().name;
+        let mut requirements = vec![(package_name.clone(), VersionReq::from_str(version_req).unwrap())];
+        for (requirer, deps) in conflicting_dependencies_tracker.iter() {
+            if requirer == package_name {
+                continue;
+            }
+            if let Some(version_req) = deps.get(name) {
+                requirements.push((requirer.clone(), version_req.clone()));
+            }
+        }
+        requirements[1..].sort_by(|a, b| a.0.cmp(&b.0));
+        requirements
+    }
+
+    fn matches_requirements(requirements: &[(String, VersionReq)], version: &Version) -> bool {
+        requirements.iter().all(|(_, version_req)| version_req.matches(version))
+    }
+
+    fn conflict_error(name: &str, requirements: &[(String, VersionReq)], mut candidates: Vec<Version>) -> WhackPackageProcessingError {
+        candidates.sort_by(|a, b| b.cmp(a));
+        candidates.dedup();
+        WhackPackageProcessingError::DependencyVersionConflict {
+            name: name.to_owned(),
+            requirements: requirements.iter().map(|(requirer, version_req)| (requirer.clone(), version_req.to_string())).collect(),
+            candidates,
+        }
+    }
+
+    /// Downloads the highest registry version of a package matching all `requirements`
+    /// into `target/downloads`, unless a matching version is already there,
+    /// and records it in the lockfile. A locked version is preferred
+    /// as long as it still matches the requirements.
+    async fn update_registry_dependency(entry_dir: &Path, name: &str, requirements: &[(String, VersionReq)], lockfile: &mut WhackLockfile, offline: bool) -> Result<(), WhackPackageProcessingError> {
+        DependencyUpdate::check_package_name(name)?;
+        let downloads_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/downloads"]).to_string_with_flex_separator()).unwrap();
+        let package_dir = downloads_dir.join(name);
+
+        let locked_version = lockfile.package.iter()
+            .find(|p| p.name == name && p.source.as_ref().map(|s| s.starts_with("registry+")).unwrap_or(false))
+            .map(|p| p.version.clone())
+            .filter(|v| DependencyUpdate::matches_requirements(requirements, v));
+
+        // Skip if a compatible version is already downloaded.
+        if let Some(downloaded) = DependencyUpdate::read_downloaded_manifest(&package_dir) {
+            let downloaded_version = &downloaded.package.as_ref().unwrap().version;
+            if DependencyUpdate::matches_requirements(requirements, downloaded_version) && locked_version.as_ref().map(|v| v == downloaded_version).unwrap_or(true) {
+                if locked_version.is_none() {
+                    let source = WhackRegistry::from_env().map(|r| r.lockfile_source()).ok();
+                    DependencyUpdate::lock_package(lockfile, name, downloaded_version, source, &downloaded);
+                }
+                return Ok(());
+            }
+        }
+
+        let registry = WhackRegistry::from_env()?;
+        registry.check_offline(offline)?;
+        let index = registry.index(name).await?;
+        let version_reqs = requirements.iter().map(|(_, version_req)| version_req.clone()).collect::<Vec<_>>();
+        let Some(version) = WhackRegistry::select_version(&index, &version_reqs, locked_version.as_ref()) else {
+            // Requirements that could each be satisfied alone are conflicting.
+            let candidates = index.versions.iter()
+                .filter(|v| !v.yanked.unwrap_or(false) && version_reqs.iter().any(|version_req| version_req.matches(&v.version)))
+                .map(|v| v.version.clone())
+                .collect::<Vec<_>>();
+            if requirements.len() > 1 && version_reqs.iter().all(|version_req| candidates.iter().any(|v| version_req.matches(v))) {
+                return Err(DependencyUpdate::conflict_error(name, requirements, candidates));
+            }
+            return Err(WhackPackageProcessingError::NoMatchingRegistryVersion {
+                name: name.to_owned(),
+                version_req: version_reqs.iter().map(|version_req| version_req.to_string()).collect::<Vec<_>>().join(", "),
+            });
+        };
+
+        eprintln!("{} {} v{}", "Downloading".green(), name, version.version);
+        let archive_path = registry.download(name, version, &downloads_dir).await?;
+        WhackRegistry::extract(&archive_path, &package_dir)?;
+
+        let Some(downloaded) = DependencyUpdate::read_downloaded_manifest(&package_dir) else {
+            return Err(WhackPackageProcessingError::InvalidPackageArchive {
+                path: archive_path.to_str().unwrap().to_owned(),
+                message: "Archive does not contain a valid package manifest.".to_owned(),
+            });
+        };
+        DependencyUpdate::lock_package(lockfile, name, &version.version, Some(registry.lockfile_source()), &downloaded);
+
+        Ok(())
+    }
+
+    /// Checks out a Git dependency at `target/git`, preferring the locked
+    /// commit as long as the lockfile source matches the `git`, `rev` and
+    /// `branch` fields, and records the resolved commit in the lockfile.
+    async fn update_git_dependency(entry_dir: &Path, name: &str, url: &str, rev: Option<&String>, branch: Option<&String>, requirements: &[(String, VersionReq)], lockfile: &mut WhackLockfile, offline: bool) -> Result<(), WhackPackageProcessingError> {
+        DependencyUpdate::check_package_name(name)?;
+        let git_dir = PathBuf::from_str(&FlexPath::from_n_native([entry_dir.to_str().unwrap(), "target/git"]).to_string_with_flex_separator()).unwrap();
+        let checkout_dir = git_dir.join(name);
+        if checkout_dir.parent() != Some(git_dir.as_path()) {
+            return Err(WhackPackageProcessingError::IllegalPackageName { name: name.to_owned() });
+        }
+        let source = GitDependency::lockfile_source(url, rev, branch);
+
+        let locked_commit = lockfile.package.iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.source.as_ref())
+            .and_then(|s| s.strip_prefix(&format!("{}#", source)).map(|commit| commit.to_owned()));
+
+        if locked_commit.is_none() {
+            eprintln!("{} {} ({})", "Updating".green(), name, url);
+        }
+        let commit = GitDependency::checkout(url, rev, branch, locked_commit.as_ref(), &checkout_dir, offline).await?;
+
+        let Some(manifest) = DependencyUpdate::read_downloaded_manifest(&checkout_dir) else {
+            return Err(WhackPackageProcessingError::ManifestIsNotAPackage {
+                manifest_path: checkout_dir.join("whack.toml").to_str().unwrap().to_owned(),
+            });
+        };
+        let version = manifest.package.as_ref().unwrap().version.clone();
+        if !DependencyUpdate::matches_requirements(requirements, &version) {
+            return Err(DependencyUpdate::conflict_error(name, requirements, vec![version]));
+        }
+        DependencyUpdate::lock_package(lockfile, name, &version, Some(format!("{}#{}", source, commit)), &manifest);
+
+        Ok(())
+    }
+
+    fn read_downloaded_manifest(package_dir: &Path) -> Option<WhackManifest> {
+        let manifest_path = package_dir.join("whack.toml");
+        if !(std::fs::exists(&manifest_path).unwrap() && std::fs::metadata(&manifest_path).unwrap().is_file()) {
+            return None;
+        }
+        let manifest = toml::from_str::<WhackManifest>(&std::fs::read_to_string(&manifest_path).unwrap()).ok()?;
+        manifest.package.is_some().then_some(manifest)
+    }
+
+    fn lock_package(lockfile: &mut WhackLockfile, name: &str, version: &Version, source: Option<String>, manifest: &WhackManifest) {
+        let entry = WhackLockfilePackage {
+            name: name.to_owned(),
+            version: version.clone(),
+            source,
+            dependencies: WhackLockfile::dependency_names(manifest),
+        };
+        if let Some(p) = lockfile.package.iter_mut().find(|p| p.name == name) {
+            *p = entry;
+        } else {
+            lockfile.package.push(entry);
+        }
+    }
 }
\ No newline at end of file
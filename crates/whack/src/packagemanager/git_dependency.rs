@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use crate::commandprocesses::WhackPackageProcessingError;
+
+/// Operations on Git dependencies, which are cloned into
+/// `target/git/NAME` by invoking the `git` command.
+pub struct GitDependency;
+
+impl GitDependency {
+    /// Value of the `source` field of lockfile entries for a Git dependency,
+    /// without the resolved commit (which follows a `#` character).
+    pub fn lockfile_source(url: &str, rev: Option<&String>, branch: Option<&String>) -> String {
+        if let Some(rev) = rev {
+            format!("git+{}?rev={}", url, rev)
+        } else if let Some(branch) = branch {
+            format!("git+{}?branch={}", url, branch)
+        } else {
+            format!("git+{}", url)
+        }
+    }
+
+    /// Makes sure `checkout_dir` is a clone of `url` with `commit` checked out,
+    /// cloning or fetching as needed. If `commit` is `None`, the commit is
+    /// resolved from the `rev`, the tip of `branch` or the tip of the default branch.
+    ///
+    /// Returns the checked out commit hash. If `offline` is true, fails
+    /// when the repository must be fetched from a remote URL.
+    pub async fn checkout(url: &str, rev: Option<&String>, branch: Option<&String>, commit: Option<&String>, checkout_dir: &PathBuf, offline: bool) -> Result<String, WhackPackageProcessingError> {
+        // Arguments starting with a dash would be parsed as options by `git`.
+        for argument in [Some(url), rev.map(|s| s.as_str()), branch.map(|s| s.as_str()), commit.map(|s| s.as_str())].into_iter().flatten() {
+            if argument.starts_with('-') {
+                return Err(WhackPackageProcessingError::IllegalGitArgument { url: url.to_owned(), argument: argument.to_owned() });
+            }
+        }
+
+        let cloned = std::fs::exists(checkout_dir.join(".git")).unwrap();
+
+        // Nothing to fetch if the locked commit is already in the clone.
+        if cloned {
+            if let Some(commit) = commit {
+                if GitDependency::git(url, checkout_dir, &["rev-parse", "HEAD"]).await.ok().as_ref() == Some(commit) {
+                    return Ok(commit.clone());
+                }
+                if GitDependency::git(url, checkout_dir, &["cat-file", "-e", &format!("{}^{{commit}}", commit)]).await.is_ok() {
+                    GitDependency::git(url, checkout_dir, &["checkout", "--quiet", "--force", "--detach", commit]).await?;
+                    return Ok(commit.clone());
+                }
+            }
+        }
+
+        if offline && !GitDependency::is_local(url) {
+            return Err(WhackPackageProcessingError::NetworkAccessDisallowed { url: url.to_owned() });
+        }
+
+        if cloned {
+            GitDependency::git(url, checkout_dir, &["remote", "set-url", "origin", "--", url]).await?;
+            GitDependency::git(url, checkout_dir, &["fetch", "--quiet", "--tags", "--force", "origin", "+refs/heads/*:refs/remotes/origin/*"]).await?;
+            GitDependency::git(url, checkout_dir, &["remote", "set-head", "origin", "--auto"]).await?;
+        } else {
+            // Never remove anything outside of `target/git`.
+            if !GitDependency::is_checkout_dir(checkout_dir) {
+                return Err(WhackPackageProcessingError::IllegalPackageName {
+                    name: checkout_dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+                });
+            }
+            if std::fs::exists(checkout_dir).unwrap() {
+                std::fs::remove_dir_all(checkout_dir).unwrap();
+            }
+            std::fs::create_dir_all(checkout_dir.parent().unwrap()).unwrap();
+            GitDependency::git(url, checkout_dir.parent().unwrap(), &["clone", "--quiet", "--no-checkout", "--", url, checkout_dir.to_str().unwrap()]).await?;
+        }
+
+        let target = if let Some(commit) = commit {
+            commit.clone()
+        } else if let Some(rev) = rev {
+            rev.clone()
+        } else if let Some(branch) = branch {
+            format!("origin/{}", branch)
+        } else {
+            "origin/HEAD".to_owned()
+        };
+        let commit = GitDependency::git(url, checkout_dir, &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", target)]).await?;
+        GitDependency::git(url, checkout_dir, &["checkout", "--quiet", "--force", "--detach", &commit]).await?;
+
+        Ok(commit)
+    }
+
+    /// Whether `checkout_dir` is a direct child of a `target/git` directory.
+    fn is_checkout_dir(checkout_dir: &Path) -> bool {
+        let Some(name) = checkout_dir.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        let Some(git_dir) = checkout_dir.parent() else {
+            return false;
+        };
+        name != "." && name != ".."
+            && git_dir.file_name().map(|n| n == "git").unwrap_or(false)
+            && git_dir.parent().and_then(|p| p.file_name()).map(|n| n == "target").unwrap_or(false)
+    }
+
+    /// Whether a Git URL refers to a repository in the file system.
+    fn is_local(url: &str) -> bool {
+        url.starts_with("file://") || !(url.contains("://") || url.contains('@'))
+    }
+
+    /// Runs `git` at `dir`, returning its trimmed standard output.
+    async fn git(url: &str, dir: &Path, args: &[&str]) -> Result<String, WhackPackageProcessingError> {
+        let output = Command::new("git").args(args).current_dir(dir).output().await.map_err(|error| WhackPackageProcessingError::GitCommandFailed {
+            url: url.to_owned(),
+            message: error.to_string(),
+        })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            return Err(WhackPackageProcessingError::GitCommandFailed {
+                url: url.to_owned(),
+                message: if stderr.is_empty() { format!("git {} failed", args[0]) } else { stderr },
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    }
+}
@@ -1,5 +1,7 @@
+use std::rc::Rc;
 use semver::Version;
 use serde::{Serialize, Deserialize};
+use crate::packagemanager::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct WhackLockfile {
@@ -12,4 +14,44 @@ pub struct WhackLockfilePackage {
     pub version: Version,
     pub source: Option<String>,
     pub dependencies: Option<Vec<String>>,
+}
+
+impl WhackLockfile {
+    /// Replaces the entries with the given resolved packages, sorted by name,
+    /// preserving the sources of registry and Git packages.
+    pub fn populate(&mut self, packages: &[Rc<WhackPackage>]) {
+        let mut entries: Vec<WhackLockfilePackage> = vec![];
+        for pckg in packages.iter() {
+            let package_manifest = pckg.manifest.package.as_ref().unwrap();
+            if entries.iter().any(|p| p.name == package_manifest.name && p.version == package_manifest.version) {
+                continue;
+            }
+            let source = self.package.iter()
+                .find(|p| p.name == package_manifest.name && p.version == package_manifest.version)
+                .and_then(|p| p.source.clone());
+            entries.push(WhackLockfilePackage {
+                name: package_manifest.name.clone(),
+                version: package_manifest.version.clone(),
+                source,
+                dependencies: WhackLockfile::dependency_names(&pckg.manifest),
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+        self.package = entries;
+    }
+
+    /// Returns the sorted names of the dependencies and build dependencies
+    /// of a package, or `None` if there are none.
+    pub fn dependency_names(manifest: &WhackManifest) -> Option<Vec<String>> {
+        let mut dependencies: Vec<String> = vec![];
+        if let Some(deps) = manifest.dependencies.as_ref() {
+            dependencies.extend(deps.keys().cloned());
+        }
+        if let Some(deps) = manifest.build_dependencies.as_ref() {
+            dependencies.extend(deps.keys().cloned());
+        }
+        dependencies.sort();
+        dependencies.dedup();
+        if dependencies.is_empty() { None } else { Some(dependencies) }
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use semver::Version;
 use serde::{Serialize, Deserialize};
+use whackengine_verifier::ns::WarningLevel;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct WhackManifest {
@@ -16,6 +17,27 @@ pub struct WhackManifest {
     pub javascript: Option<Vec<ManifestJscript>>,
     /// Configuration constants.
     pub define: Option<HashMap<String, toml::Value>>,
+    /// Warning levels by diagnostic name or ID. The `warnings`
+    /// name applies to every warning.
+    pub lints: Option<HashMap<String, ManifestLintLevel>>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestLintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl ManifestLintLevel {
+    pub fn warning_level(&self) -> WarningLevel {
+        match self {
+            Self::Allow => WarningLevel::Allow,
+            Self::Warn => WarningLevel::Warn,
+            Self::Deny => WarningLevel::Deny,
+        }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
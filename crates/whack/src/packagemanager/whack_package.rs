@@ -1,4 +1,4 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf};
 use std::rc::Rc;
 use whackengine_verifier::ns::{shared_array, CompilationUnit, Mxml, Program, SharedArray};
 use crate::packagemanager::*;
@@ -14,6 +14,19 @@ pub struct WhackPackage {
     pub sources: SharedArray<WhackSource>,
     /// List of ActionScript build script sources.
     pub build_script_sources: SharedArray<WhackSource>,
+    /// Directory where the build script writes generated sources,
+    /// which contributes to the source path.
+    pub generated_source_path: RefCell<Option<PathBuf>>,
+}
+
+impl WhackPackage {
+    /// Whether the package is the entry package or another package inside
+    /// the entry point directory, such as a workspace member, as opposed to
+    /// a downloaded dependency, a Git dependency or a package elsewhere.
+    pub fn is_workspace_member(&self) -> bool {
+        let path = &self.relative_path;
+        !(path == ".." || path.starts_with("../") || path == "target" || path.starts_with("target/"))
+    }
 }
 
 #[derive(Clone)]
@@ -54,6 +67,7 @@ impl WhackPackageInternator {
             manifest: manifest.clone(),
             sources: shared_array![],
             build_script_sources: shared_array![],
+            generated_source_path: RefCell::new(None),
         });
         self.m_by_relative_path.insert(relative_path.to_owned(), r.clone());
         r
@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use hydroperfox_filepaths::FlexPath;
+use semver::{Version, VersionReq};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use crate::commandprocesses::WhackPackageProcessingError;
+
+/// A package registry implementing the Whack index protocol.
+///
+/// # Protocol
+///
+/// The registry is located by a base URL, either `http://`, `https://` or `file://`,
+/// read from the `WHACK_REGISTRY` environment variable. For a package `NAME`:
+///
+/// - `BASE/NAME/index.json` is the index of the package, a `WhackRegistryIndex` JSON object.
+/// - `BASE/NAME/VERSION.tar.gz` is the archive of a version of the package, containing
+///   the `whack.toml` file either at the root or at a single top-level directory.
+pub struct WhackRegistry {
+    url: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WhackRegistryIndex {
+    pub versions: Vec<WhackRegistryIndexVersion>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WhackRegistryIndexVersion {
+    pub version: Version,
+    /// SHA-256 hexadecimal checksum of the archive.
+    pub checksum: Option<String>,
+    pub yanked: Option<bool>,
+}
+
+impl WhackRegistry {
+    pub const ENV_VAR: &'static str = "WHACK_REGISTRY";
+
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_owned(),
+        }
+    }
+
+    /// Returns the registry configured through the `WHACK_REGISTRY`
+    /// environment variable.
+    pub fn from_env() -> Result<Self, WhackPackageProcessingError> {
+        match std::env::var(Self::ENV_VAR) {
+            Ok(url) if !url.is_empty() => Ok(Self::new(&url)),
+            _ => Err(WhackPackageProcessingError::RegistryNotConfigured),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Value of the `source` field of lockfile entries
+    /// for packages from this registry.
+    pub fn lockfile_source(&self) -> String {
+        format!("registry+{}", self.url)
+    }
+
+    /// Fails if `offline` is true and the registry is not in the file system.
+    pub fn check_offline(&self, offline: bool) -> Result<(), WhackPackageProcessingError> {
+        if offline && !self.url.starts_with("file://") {
+            return Err(WhackPackageProcessingError::NetworkAccessDisallowed { url: self.url.clone() });
+        }
+        Ok(())
+    }
+
+    pub async fn index(&self, name: &str) -> Result<WhackRegistryIndex, WhackPackageProcessingError> {
+        let url = format!("{}/{}/index.json", self.url, name);
+        let bytes = Self::fetch(&url).await?;
+        serde_json::from_slice::<WhackRegistryIndex>(&bytes).map_err(|error| WhackPackageProcessingError::InvalidRegistryIndex {
+            url,
+            message: error.to_string(),
+        })
+    }
+
+    /// Returns the highest version that matches all requirements and
+    /// that is not yanked, unless it is the `preferred` version.
+    pub fn select_version<'a>(index: &'a WhackRegistryIndex, version_reqs: &[VersionReq], preferred: Option<&Version>) -> Option<&'a WhackRegistryIndexVersion> {
+        if let Some(preferred) = preferred {
+            if let Some(v) = index.versions.iter().find(|v| &v.version == preferred && version_reqs.iter().all(|r| r.matches(&v.version))) {
+                return Some(v);
+            }
+        }
+        index.versions.iter()
+            .filter(|v| !v.yanked.unwrap_or(false) && version_reqs.iter().all(|r| r.matches(&v.version)))
+            .max_by(|a, b| a.version.cmp(&b.version))
+    }
+
+    /// Downloads the archive of a package version into `downloads_dir`,
+    /// returning the archive path.
+    pub async fn download(&self, name: &str, version: &WhackRegistryIndexVersion, downloads_dir: &PathBuf) -> Result<PathBuf, WhackPackageProcessingError> {
+        let url = format!("{}/{}/{}.tar.gz", self.url, name, version.version);
+        let bytes = Self::fetch(&url).await?;
+
+        if let Some(checksum) = version.checksum.as_ref() {
+            let actual = Sha256::digest(&bytes).iter().map(|b| format!("{b:02x}")).collect::<String>();
+            if !actual.eq_ignore_ascii_case(checksum) {
+                return Err(WhackPackageProcessingError::RegistryChecksumMismatch {
+                    name: name.to_owned(),
+                    version: version.version.clone(),
+                });
+            }
+        }
+
+        std::fs::create_dir_all(downloads_dir).unwrap();
+        let archive_path = downloads_dir.join(format!("{}-{}.tar.gz", name, version.version));
+        std::fs::write(&archive_path, &bytes).unwrap();
+        Ok(archive_path)
+    }
+
+    /// Extracts a downloaded archive into `dest`, replacing any
+    /// previous contents.
+    pub fn extract(archive_path: &PathBuf, dest: &PathBuf) -> Result<(), WhackPackageProcessingError> {
+        let archive_error = |message: String| WhackPackageProcessingError::InvalidPackageArchive {
+            path: archive_path.to_str().unwrap().to_owned(),
+            message,
+        };
+
+        let mut staging_name = dest.file_name().unwrap().to_os_string();
+        staging_name.push(".staging");
+        let staging = dest.with_file_name(staging_name);
+        if std::fs::exists(&staging).unwrap() {
+            std::fs::remove_dir_all(&staging).unwrap();
+        }
+        std::fs::create_dir_all(&staging).unwrap();
+
+        let file = std::fs::File::open(archive_path).unwrap();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        archive.unpack(&staging).map_err(|error| archive_error(error.to_string()))?;
+
+        // Strip a single top-level directory.
+        let mut root = staging.clone();
+        if !std::fs::exists(root.join("whack.toml")).unwrap() {
+            let entries = std::fs::read_dir(&staging).unwrap().filter_map(|e| e.ok()).collect::<Vec<_>>();
+            if entries.len() == 1 && entries[0].path().is_dir() && std::fs::exists(entries[0].path().join("whack.toml")).unwrap() {
+                root = entries[0].path();
+            } else {
+                std::fs::remove_dir_all(&staging).unwrap();
+                return Err(archive_error("Archive does not contain a whack.toml file.".to_owned()));
+            }
+        }
+
+        if std::fs::exists(dest).unwrap() {
+            std::fs::remove_dir_all(dest).unwrap();
+        }
+        std::fs::rename(&root, dest).unwrap();
+        if std::fs::exists(&staging).unwrap() {
+            std::fs::remove_dir_all(&staging).unwrap();
+        }
+        Ok(())
+    }
+
+    async fn fetch(url: &str) -> Result<Vec<u8>, WhackPackageProcessingError> {
+        let request_error = |message: String| WhackPackageProcessingError::RegistryRequestFailed {
+            url: url.to_owned(),
+            message,
+        };
+
+        if let Some(path) = url.strip_prefix("file://") {
+            let path = PathBuf::from_str(&FlexPath::new_native(path).to_string_with_flex_separator()).unwrap();
+            return tokio::fs::read(&path).await.map_err(|error| request_error(error.to_string()));
+        }
+
+        let response = reqwest::get(url).await.map_err(|error| request_error(error.to_string()))?;
+        if !response.status().is_success() {
+            return Err(request_error(response.status().to_string()));
+        }
+        let bytes = response.bytes().await.map_err(|error| request_error(error.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+#[test]
+fn build_writes_a_script_per_package() {
+    let project = Project::with_main("build-writes-scripts", r#"
+        package com.x {
+            public class Greeter {
+                public function greet(name: String): String {
+                    return "Hello, " + name;
+                }
+            }
+        }
+    "#, "");
+    let output = project.whack("build").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.exists("target/js/builtins.js"));
+    let script = project.read("target/js/com.x.app.js");
+    assert!(script.contains("Greeter"));
+    assert!(script.contains("greet"));
+}
+
+#[test]
+fn build_does_not_generate_code_for_invalid_sources() {
+    let project = Project::with_main("build-invalid-sources", r#"
+        package com.x {
+            public class Greeter {
+                public function greet(): Number {
+                    return new Error();
+                }
+            }
+        }
+    "#, "");
+    let output = project.whack("build").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+    assert!(!project.exists("target/js/com.x.app.js"));
+}
+
+#[test]
+fn bindable_properties_dispatch_change_events() {
+    let project = Project::with_main("build-bindable", r#"
+        package com.x {
+            public class Model {
+                [Bindable]
+                public var x: Number = 0;
+
+                public function dispatchEvent(event: Object): Boolean {
+                    return true;
+                }
+            }
+        }
+    "#, "");
+    let output = project.whack("build").output().unwrap();
+    assert!(output.status.success(), "{}", stdout(&output));
+    let script = project.read("target/js/com.x.app.js");
+    assert!(script.contains(r#"set: $core.bindablesetter(function(value) { this["#), "{script}");
+    assert!(script.contains(r#""propertyChange")"#), "{script}");
+}
+
+#[test]
+fn embedded_assets_are_copied() {
+    let project = Project::with_main("build-embed", r#"
+        package com.x {
+            public class Assets {
+                [Embed(source="logo.png")]
+                public static const LOGO: Class;
+            }
+        }
+    "#, "");
+    project.write("src/logo.png", "PNG");
+    let output = project.whack("build").output().unwrap();
+    assert!(output.status.success(), "{}", stdout(&output));
+    let script = project.read("target/js/com.x.app.js");
+    assert!(script.contains(r#"$core.embedasset("assets/0-logo.png", "image/png", "#), "{script}");
+    assert_eq!(project.read("target/js/assets/0-logo.png"), "PNG");
+}
+
+#[test]
+fn default_xml_namespace_is_declared_per_function() {
+    let project = Project::with_main("build-dxns", r#"
+        package com.x {
+            public class Feed {
+                public function items(x: XML, c: Boolean): XMLList {
+                    if (c) {
+                        default xml namespace = "http://example.com";
+                    }
+                    return x.item;
+                }
+                public function plain(x: XML): XMLList {
+                    return x.item;
+                }
+            }
+        }
+    "#, "");
+    let output = project.whack("build").output().unwrap();
+    assert!(output.status.success(), "{}", stdout(&output));
+    let script = project.read("target/js/com.x.app.js");
+
+    // Only the function containing the statement declares `$$dxns`,
+    // at its start rather than where the statement appears.
+    let declaration = script.find(r#"var $$dxns = $core.construct($Namespace, "");"#).unwrap();
+    let assignment = script.find(r#"$$dxns = $core.construct($Namespace, "http://example.com");"#).unwrap();
+    assert!(declaration < assignment, "{script}");
+    assert_eq!(script.matches("var $$dxns").count(), 1, "{script}");
+    assert!(script.contains(r#"return $core.getproperty(x, $core.qname($$dxns, "item"));"#), "{script}");
+    assert!(script.contains(r#"return $core.getproperty(x, "item");"#), "{script}");
+}
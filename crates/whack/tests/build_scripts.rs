@@ -0,0 +1,84 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::os::unix::fs::PermissionsExt;
+use std::time::{Duration, SystemTime};
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const MAIN_SOURCE: &str = r#"
+    package com.x {
+        public class Main {
+            public var generated: Generated = new Generated();
+        }
+    }
+"#;
+
+const BUILD_SCRIPT_SOURCE: &str = r#"
+    package {
+        trace("build");
+    }
+"#;
+
+/// JavaScript runtime standing for `node` that records its arguments
+/// and writes a class into the output directory.
+const RUNTIME: &str = r#"#!/bin/sh
+echo "$1" >> "$WHACK_PACKAGE_DIR/runs.txt"
+mkdir -p "$WHACK_OUT_DIR/com/x"
+echo 'package com.x { public class Generated {} }' > "$WHACK_OUT_DIR/com/x/Generated.as"
+"#;
+
+fn project(name: &str, runtime: &str) -> Project {
+    let project = Project::with_main(name, MAIN_SOURCE, "build-script = [\"build\"]\n");
+    project.write("build/Build.as", BUILD_SCRIPT_SOURCE);
+    project.write_root("runtime.sh", runtime);
+    std::fs::set_permissions(project.root.join("runtime.sh"), std::fs::Permissions::from_mode(0o755)).unwrap();
+    project
+}
+
+fn check(project: &Project) -> std::process::Output {
+    project.whack("check").env("WHACK_JS_RUNTIME", project.root.join("runtime.sh")).output().unwrap()
+}
+
+#[test]
+fn generated_sources_are_verified() {
+    let project = project("build-script-generated-sources", RUNTIME);
+    let output = check(&project);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let script_path = project.dir.join("target/build-script/com.x.app/build.js");
+    assert!(script_path.exists());
+    assert_eq!(project.read("runs.txt").lines().collect::<Vec<_>>(), vec![script_path.to_str().unwrap()]);
+}
+
+#[test]
+fn build_script_reruns_only_after_manifest_changes() {
+    let project = project("build-script-reruns", RUNTIME);
+    assert!(check(&project).status.success());
+    assert!(check(&project).status.success());
+    assert_eq!(project.read("runs.txt").lines().count(), 1);
+
+    let manifest = std::fs::File::options().write(true).open(project.dir.join("whack.toml")).unwrap();
+    manifest.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+    assert!(check(&project).status.success());
+    assert_eq!(project.read("runs.txt").lines().count(), 2);
+}
+
+#[test]
+fn build_script_errors_are_reported() {
+    let project = project("build-script-errors", RUNTIME);
+    project.write("build/Build.as", "package { var n: Number = new Error(); }");
+    let output = check(&project);
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Build script of com.x.app contains errors."), "{}", stderr(&output));
+    assert!(!project.exists("runs.txt"));
+}
+
+#[test]
+fn failing_build_script_is_reported() {
+    let project = project("build-script-failure", "#!/bin/sh\nexit 3\n");
+    let output = check(&project);
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Build script of com.x.app failed"), "{}", stderr(&output));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::*;
+use serde_json::Value;
+use whackengine_verifier::ns::WhackDiagnosticKind;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const INVALID_SOURCE: &str = r#"package com.x {
+    public class Main {
+        public function f(): Number {
+            return new Error();
+        }
+    }
+}"#;
+
+#[test]
+fn json_message_format() {
+    let project = Project::with_main("check-json", INVALID_SOURCE, "");
+    let output = project.whack("check").arg("--message-format=json").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+
+    // The built-ins contribute warnings of their own.
+    let main_path = project.dir.join("src/Main.as").canonicalize().unwrap();
+    let diagnostics = stdout(&output).lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .filter(|diagnostic| diagnostic["file"] == main_path.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["id"], WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id());
+    assert_eq!(diagnostic["kind"], "ImplicitCoercionToUnrelatedType");
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["range"]["start"], serde_json::json!({ "line": 4, "column": 20 }));
+    assert_eq!(diagnostic["message"], "Implicit coercion of a value of type Error to an unrelated type Number.");
+    assert_eq!(diagnostic["arguments"], serde_json::json!(["Error", "Number"]));
+}
+
+#[test]
+fn sarif_message_format() {
+    let project = Project::with_main("check-sarif", INVALID_SOURCE, "");
+    let output = project.whack("check").arg("--message-format=sarif").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+
+    let log = serde_json::from_str::<Value>(&stdout(&output)).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let results = log["runs"][0]["results"].as_array().unwrap().iter()
+        .filter(|result| result["level"] == "error")
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id().to_string());
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(results[0]["properties"]["kind"], "ImplicitCoercionToUnrelatedType");
+    assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 4);
+}
+
+#[test]
+fn json_output_excludes_progress() {
+    let project = Project::with_main("check-json-progress", INVALID_SOURCE, "\n[dependencies]\n\"com.x.lib\" = \"1.0.0\"\n");
+    let registry = Registry::new(&project);
+    registry.publish("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", "package com.x.lib { public class Util {} }")], false);
+
+    let output = project.whack("check").arg("--message-format=json").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(stderr(&output).contains("Downloading com.x.lib v1.0.0"));
+    for line in stdout(&output).lines() {
+        serde_json::from_str::<Value>(line).unwrap();
+    }
+
+    // Failures are written to the standard error stream as well.
+    project.write("whack.toml", "[package]\nname = \"com.x.app\"\n");
+    let output = project.whack("check").arg("--message-format=json").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).contains("Error:"));
+}
+
+#[test]
+fn summary_of_failed_check() {
+    let project = Project::with_main("check-failed", INVALID_SOURCE, "");
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+    assert!(stdout(&output).contains("Failed 1 error, 1 warning in 2 packages"), "{}", stdout(&output));
+}
+
+#[test]
+fn summary_of_finished_check() {
+    let project = Project::with_main("check-finished", "package com.x { public class Main {} }", "");
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Finished 0 errors, 1 warning in 2 packages"), "{}", stdout(&output));
+}
+
+#[test]
+fn package_processing_failure_exit_code() {
+    let project = Project::with_main("check-package-failure", "package com.x { public class Main {} }", "");
+    project.write("whack.toml", "[package\n");
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(!stdout(&output).contains("Finished"));
+}
@@ -0,0 +1,209 @@
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use sha2::{Digest, Sha256};
+
+/// Minimal top-level definitions required by the verifier.
+const BUILTINS: &str = include_str!("../../../verifier/tests/common/builtins.as");
+
+/// A temporary directory containing an application package at `app`
+/// and a built-ins package at `builtins`.
+pub struct Project {
+    pub root: PathBuf,
+    pub dir: PathBuf,
+}
+
+impl Project {
+    /// Creates an empty project named `name`, removing
+    /// any project left over by a previous run.
+    pub fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join("whack-tests").join(format!("{name}-{}", std::process::id()));
+        if root.exists() {
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+        let project = Self {
+            dir: root.join("app"),
+            root,
+        };
+        project.write_root("builtins/whack.toml", "[package]\nname = \"builtins\"\nversion = \"1.0.0\"\nsource-path = [\"src\"]\n");
+        project.write_root("builtins/src/library.as", BUILTINS);
+        project
+    }
+
+    /// Creates a project whose application package has the given
+    /// source file at `src/Main.as` and the given additional
+    /// manifest text.
+    pub fn with_main(name: &str, main: &str, manifest: &str) -> Self {
+        Self::with_main_in(Self::new(name), main, manifest)
+    }
+
+    /// Writes the application package of a project with the given
+    /// source file at `src/Main.as` and the given additional
+    /// manifest text.
+    pub fn with_main_in(project: Self, main: &str, manifest: &str) -> Self {
+        project.write("whack.toml", &format!("[package]\nname = \"com.x.app\"\nversion = \"1.0.0\"\nsource-path = [\"src\"]\n{manifest}"));
+        project.write("src/Main.as", main);
+        project
+    }
+
+    /// Writes a file relative to the application package.
+    pub fn write(&self, path: &str, text: &str) {
+        write_file(&self.dir.join(path), text);
+    }
+
+    /// Writes a file relative to the project root.
+    pub fn write_root(&self, path: &str, text: &str) {
+        write_file(&self.root.join(path), text);
+    }
+
+    /// Reads a file relative to the application package.
+    pub fn read(&self, path: &str) -> String {
+        std::fs::read_to_string(self.dir.join(path)).unwrap()
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.dir.join(path).exists()
+    }
+
+    /// Returns a `whack` command running `subcommand` over the application
+    /// package with the project built-ins and English messages.
+    pub fn whack(&self, subcommand: &str) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_whackengine-whack"));
+        command.arg(subcommand)
+            .arg("--builtins").arg(self.root.join("builtins"))
+            .current_dir(&self.dir)
+            .env_remove("WHACK_REGISTRY")
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .env_remove("LANG")
+            .env("NO_COLOR", "1");
+        command
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// A `file://` package registry at the `registry` directory of a project.
+pub struct Registry {
+    pub dir: PathBuf,
+}
+
+impl Registry {
+    pub fn new(project: &Project) -> Self {
+        Self {
+            dir: project.root.join("registry"),
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("file://{}", self.dir.to_str().unwrap())
+    }
+
+    /// Publishes a version of a package consisting of the given
+    /// additional manifest text and source files under `src`.
+    pub fn publish(&self, name: &str, version: &str, manifest: &str, files: &[(&str, &str)], yanked: bool) {
+        self.publish_with_checksum(name, version, manifest, files, yanked, None);
+    }
+
+    /// Publishes a version of a package, listing `checksum` in
+    /// the index instead of the checksum of the archive if given.
+    pub fn publish_with_checksum(&self, name: &str, version: &str, manifest: &str, files: &[(&str, &str)], yanked: bool, checksum: Option<&str>) {
+        let package_dir = self.dir.join(name);
+        std::fs::create_dir_all(&package_dir).unwrap();
+
+        // The archive contains a single top-level directory.
+        let mut archive = tar::Builder::new(flate2::write::GzEncoder::new(vec![], flate2::Compression::default()));
+        let manifest = format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\nsource-path = [\"src\"]\n{manifest}");
+        let entries = [("whack.toml".to_owned(), manifest)].into_iter()
+            .chain(files.iter().map(|(path, text)| (format!("src/{path}"), text.to_string())));
+        for (path, text) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(text.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            archive.append_data(&mut header, format!("{name}-{version}/{path}"), text.as_bytes()).unwrap();
+        }
+        let bytes = archive.into_inner().unwrap().finish().unwrap();
+        std::fs::write(package_dir.join(format!("{version}.tar.gz")), &bytes).unwrap();
+
+        let checksum = checksum.map(|c| c.to_owned()).unwrap_or_else(|| {
+            Sha256::digest(&bytes).iter().map(|b| format!("{b:02x}")).collect::<String>()
+        });
+        let index_path = package_dir.join("index.json");
+        let mut index = if index_path.exists() {
+            serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(&index_path).unwrap()).unwrap()
+        } else {
+            serde_json::json!({ "versions": [] })
+        };
+        index["versions"].as_array_mut().unwrap().push(serde_json::json!({
+            "version": version,
+            "checksum": checksum,
+            "yanked": yanked,
+        }));
+        std::fs::write(&index_path, index.to_string()).unwrap();
+    }
+}
+
+/// A local Git repository of a package at a directory of a project.
+pub struct GitRepository {
+    pub dir: PathBuf,
+}
+
+impl GitRepository {
+    /// Initializes a repository whose default branch is `main`.
+    pub fn new(project: &Project, name: &str) -> Self {
+        let repository = Self {
+            dir: project.root.join(name),
+        };
+        std::fs::create_dir_all(&repository.dir).unwrap();
+        repository.git(&["init", "--quiet", "--initial-branch=main"]);
+        repository
+    }
+
+    pub fn url(&self) -> String {
+        format!("file://{}", self.dir.to_str().unwrap())
+    }
+
+    /// Commits a version of a package consisting of the given
+    /// additional manifest text and source files under `src`,
+    /// returning the commit hash.
+    pub fn commit(&self, name: &str, version: &str, manifest: &str, files: &[(&str, &str)]) -> String {
+        write_file(&self.dir.join("whack.toml"), &format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\nsource-path = [\"src\"]\n{manifest}"));
+        for (path, text) in files.iter() {
+            write_file(&self.dir.join("src").join(path), text);
+        }
+        self.git(&["add", "--all"]);
+        self.git(&["commit", "--quiet", "--message", &format!("Release {version}")]);
+        self.git(&["rev-parse", "HEAD"])
+    }
+
+    /// Runs a Git command in the repository, returning its trimmed output.
+    pub fn git(&self, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Whack", "-c", "user.email=whack@localhost"])
+            .args(args)
+            .current_dir(&self.dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {}: {}", args.join(" "), stderr(&output));
+        stdout(&output).trim().to_owned()
+    }
+}
+
+pub fn write_file(path: &Path, text: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, text).unwrap();
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
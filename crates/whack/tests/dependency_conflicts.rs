@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const LIB_SOURCE: &str = r#"
+    package com.x.lib {
+        public class Util {
+        }
+    }
+"#;
+
+const UTIL_SOURCE: &str = r#"
+    package com.x.util {
+        import com.x.lib.Util;
+
+        public class Tool extends Util {
+        }
+    }
+"#;
+
+fn publish(registry: &Registry) {
+    registry.publish("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+    registry.publish("com.x.lib", "2.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+    registry.publish("com.x.util", "1.0.0", "\n[dependencies]\n\"com.x.lib\" = \"^2\"\n", &[("com/x/util/Tool.as", UTIL_SOURCE)], false);
+}
+
+#[test]
+fn conflicting_requirements_are_reported() {
+    let project = Project::with_main("conflict-reported", "", "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n\"com.x.util\" = \"1\"\n");
+    let registry = Registry::new(&project);
+    publish(&registry);
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    let stderr = stderr(&output);
+    assert!(stderr.contains("Conflicting version requirements for dependency com.x.lib:"), "{stderr}");
+    assert!(stderr.contains("com.x.app requires ^1"), "{stderr}");
+    assert!(stderr.contains("com.x.util requires ^2"), "{stderr}");
+}
+
+#[test]
+fn compatible_requirements_are_unified() {
+    let project = Project::with_main("conflict-unified", "", "\n[dependencies]\n\"com.x.lib\" = \">=1\"\n\"com.x.util\" = \"1\"\n");
+    let registry = Registry::new(&project);
+    publish(&registry);
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/downloads/com.x.lib/whack.toml").contains("version = \"2.0.0\""));
+}
+
+#[test]
+fn conflict_with_locked_version_is_reported() {
+    let project = Project::with_main("conflict-locked", "", "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n");
+    let registry = Registry::new(&project);
+    publish(&registry);
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    // Add a dependency requiring another version of the locked package.
+    let manifest = project.read("whack.toml").replace("\"com.x.lib\" = \"^1\"", "\"com.x.lib\" = \"^1\"\n\"com.x.util\" = \"1\"");
+    project.write("whack.toml", &manifest);
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Conflicting version requirements for dependency com.x.lib:"), "{}", stderr(&output));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::*;
+
+const UTIL_SOURCE: &str = "package com.x.util { public class Util {} }";
+
+const MAIN_SOURCE: &str = r#"package com.x {
+    import com.x.util.Util;
+
+    public class Main {
+        public function f(): void {
+            var counter: Number = 0;
+            countre++;
+        }
+    }
+}"#;
+
+#[test]
+fn machine_applicable_fixes_are_applied() {
+    let project = Project::with_main("fix-apply", MAIN_SOURCE, "");
+    project.write("src/com/x/util/Util.as", UTIL_SOURCE);
+    let output = project.whack("fix").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Fixed 1 diagnostic in 1 file"), "{}", stdout(&output));
+
+    // The unused import is removed along with its line, but
+    // the suggested name is not applied.
+    assert_eq!(project.read("src/Main.as"), MAIN_SOURCE.replace("    import com.x.util.Util;\n", ""));
+}
+
+#[test]
+fn dry_run() {
+    let project = Project::with_main("fix-dry-run", MAIN_SOURCE, "");
+    project.write("src/com/x/util/Util.as", UTIL_SOURCE);
+    let output = project.whack("fix").arg("--dry-run").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Would fix"), "{stdout}");
+    assert!(stdout.contains("Fixable 1 diagnostic in 1 file"), "{stdout}");
+    assert_eq!(project.read("src/Main.as"), MAIN_SOURCE);
+}
+
+#[test]
+fn fixes_are_listed_in_json() {
+    let project = Project::with_main("fix-json", MAIN_SOURCE, "");
+    project.write("src/com/x/util/Util.as", UTIL_SOURCE);
+    let output = project.whack("check").arg("--message-format=json").output().unwrap();
+    let diagnostics = stdout(&output).lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .filter(|diagnostic| diagnostic["file"].as_str().unwrap().ends_with("Main.as"))
+        .collect::<Vec<_>>();
+    let fixes = diagnostics.iter()
+        .flat_map(|diagnostic| diagnostic["fixes"].as_array().unwrap().iter())
+        .map(|fix| (fix["title"].as_str().unwrap(), fix["applicability"].as_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert!(fixes.contains(&("Remove unused import", "machine-applicable")), "{fixes:?}");
+    assert!(fixes.contains(&("Change to 'counter'", "maybe-incorrect")), "{fixes:?}");
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const LIB_SOURCE: &str = r#"
+    package com.x.lib {
+        public class Util {
+        }
+    }
+"#;
+
+const MAIN_SOURCE: &str = r#"
+    package com.x {
+        import com.x.lib.Util;
+
+        public class Main {
+            public var util: Util = new Util();
+        }
+    }
+"#;
+
+fn git_dependency(repository: &GitRepository, pin: &str) -> String {
+    format!("\n[dependencies]\n\"com.x.lib\" = {{ git = \"{}\"{pin} }}\n", repository.url())
+}
+
+#[test]
+fn branch_tip_is_checked_out_and_locked() {
+    let project = Project::new("git-branch-tip");
+    let repository = GitRepository::new(&project, "lib");
+    repository.commit("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)]);
+    repository.git(&["checkout", "--quiet", "-b", "next"]);
+    let next = repository.commit("com.x.lib", "1.1.0", "", &[]);
+    repository.git(&["checkout", "--quiet", "main"]);
+
+    let project = Project::with_main_in(project, MAIN_SOURCE, &git_dependency(&repository, ", branch = \"next\""));
+    let output = project.whack("check").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/git/com.x.lib/whack.toml").contains("version = \"1.1.0\""));
+
+    let lockfile = project.read("whack.lock");
+    assert!(lockfile.contains(&format!("source = \"git+{}?branch=next#{next}\"", repository.url())), "{lockfile}");
+}
+
+#[test]
+fn rev_is_checked_out() {
+    let project = Project::new("git-rev");
+    let repository = GitRepository::new(&project, "lib");
+    let first = repository.commit("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)]);
+    repository.commit("com.x.lib", "2.0.0", "", &[]);
+
+    let project = Project::with_main_in(project, MAIN_SOURCE, &git_dependency(&repository, &format!(", rev = \"{first}\"")));
+    let output = project.whack("check").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/git/com.x.lib/whack.toml").contains("version = \"1.0.0\""));
+}
+
+#[test]
+fn locked_commit_is_kept() {
+    let project = Project::new("git-locked-commit");
+    let repository = GitRepository::new(&project, "lib");
+    let first = repository.commit("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)]);
+
+    let project = Project::with_main_in(project, MAIN_SOURCE, &git_dependency(&repository, ""));
+    let output = project.whack("check").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    repository.commit("com.x.lib", "1.1.0", "", &[]);
+    let output = project.whack("check").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/git/com.x.lib/whack.toml").contains("version = \"1.0.0\""));
+    assert!(project.read("whack.lock").contains(&format!("#{first}\"")));
+}
+
+#[test]
+fn version_requirement_is_checked() {
+    let project = Project::new("git-version-requirement");
+    let repository = GitRepository::new(&project, "lib");
+    repository.commit("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)]);
+
+    let project = Project::with_main_in(project, MAIN_SOURCE, &git_dependency(&repository, ", version = \"^2\""));
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Conflicting version requirements for dependency com.x.lib"), "{}", stderr(&output));
+}
+
+#[test]
+fn checkout_stays_inside_target_git() {
+    let project = Project::new("git-illegal-name");
+    let repository = GitRepository::new(&project, "lib");
+    repository.commit("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)]);
+    project.write_root("app/keep.txt", "");
+
+    let manifest = format!("\n[dependencies]\n\"..\" = {{ git = \"{}\" }}\n", repository.url());
+    let project = Project::with_main_in(project, MAIN_SOURCE, &manifest);
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Found illegal package name: .."), "{}", stderr(&output));
+    assert!(project.exists("keep.txt"));
+}
+
+#[test]
+fn option_like_arguments_are_rejected() {
+    let project = Project::new("git-option-like-arguments");
+    let manifest = "\n[dependencies]\n\"com.x.lib\" = { git = \"--upload-pack=touch pwned\" }\n";
+    let project = Project::with_main_in(project, MAIN_SOURCE, manifest);
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Found illegal argument for Git dependency --upload-pack=touch pwned: --upload-pack=touch pwned"), "{}", stderr(&output));
+    assert!(!project.exists("target/git/pwned"));
+
+    let repository = GitRepository::new(&project, "lib");
+    repository.commit("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)]);
+    let project = Project::with_main_in(project, MAIN_SOURCE, &git_dependency(&repository, ", branch = \"--orphan\""));
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains(": --orphan"), "{}", stderr(&output));
+}
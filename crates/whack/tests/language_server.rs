@@ -0,0 +1,170 @@
+mod common;
+
+use std::io::BufReader;
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use common::*;
+use lsp_server::{Message, Notification, Request, RequestId};
+use lsp_types as lsp;
+use whackengine_verifier::ns::WhackDiagnosticKind;
+
+const GREETER_SOURCE: &str = r#"
+    package com.x {
+        public class Greeter {
+            public function greet(): String {
+                return "Hello";
+            }
+        }
+    }
+"#;
+
+/// A language server process driven through the standard streams.
+struct Client {
+    process: Child,
+    stdin: ChildStdin,
+    messages: Receiver<Message>,
+    next_id: i32,
+}
+
+impl Client {
+    /// Starts and initializes the language server at the application package.
+    fn start(project: &Project) -> Self {
+        let mut process = project.whack("lsp")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = process.stdin.take().unwrap();
+        let mut stdout = BufReader::new(process.stdout.take().unwrap());
+        let (sender, messages) = channel();
+        std::thread::spawn(move || {
+            while let Ok(Some(message)) = Message::read(&mut stdout) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut client = Self { process, stdin, messages, next_id: 0 };
+        client.request("initialize", serde_json::json!({ "capabilities": {} }));
+        client.notify("initialized", serde_json::json!({}));
+        client
+    }
+
+    fn notify(&mut self, method: &str, params: serde_json::Value) {
+        Message::Notification(Notification::new(method.to_owned(), params)).write(&mut self.stdin).unwrap();
+    }
+
+    /// Sends a request and returns its result, skipping notifications.
+    fn request(&mut self, method: &str, params: serde_json::Value) -> serde_json::Value {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        Message::Request(Request::new(id.clone(), method.to_owned(), params)).write(&mut self.stdin).unwrap();
+        loop {
+            if let Message::Response(response) = self.receive() {
+                if response.id == id {
+                    return response.result.unwrap_or_default();
+                }
+            }
+        }
+    }
+
+    fn receive(&mut self) -> Message {
+        self.messages.recv_timeout(Duration::from_secs(60)).expect("Language server did not respond.")
+    }
+
+    /// Waits for a notification of the given method.
+    fn notification<N: lsp::notification::Notification>(&mut self) -> N::Params {
+        loop {
+            if let Message::Notification(notification) = self.receive() {
+                if notification.method == N::METHOD {
+                    return serde_json::from_value(notification.params).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Waits for the diagnostics of the given document.
+    fn diagnostics(&mut self, uri: &lsp::Url) -> Vec<lsp::Diagnostic> {
+        loop {
+            let params = self.notification::<lsp::notification::PublishDiagnostics>();
+            if &params.uri == uri {
+                return params.diagnostics;
+            }
+        }
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+fn main_uri(project: &Project) -> lsp::Url {
+    lsp::Url::from_file_path(project.dir.join("src/Main.as").canonicalize().unwrap()).unwrap()
+}
+
+#[test]
+fn diagnostics_follow_open_documents() {
+    let project = Project::with_main("lsp-diagnostics", GREETER_SOURCE, "");
+    let uri = main_uri(&project);
+    let mut client = Client::start(&project);
+
+    let invalid = GREETER_SOURCE.replace("return \"Hello\";", "return new Error();");
+    client.notify("textDocument/didOpen", serde_json::json!({
+        "textDocument": { "uri": uri, "languageId": "actionscript", "version": 1, "text": invalid },
+    }));
+    let diagnostics = client.diagnostics(&uri);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, Some(lsp::NumberOrString::Number(WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id())));
+    assert_eq!(diagnostics[0].range.start, lsp::Position { line: 4, character: 23 });
+
+    client.notify("textDocument/didChange", serde_json::json!({
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [{ "text": GREETER_SOURCE }],
+    }));
+    assert_eq!(client.diagnostics(&uri), vec![]);
+}
+
+#[test]
+fn hover_and_definition() {
+    let project = Project::with_main("lsp-hover", GREETER_SOURCE, "");
+    project.write("src/com/x/User.as", r#"package com.x {
+    public class User {
+        public var greeter: Greeter = new Greeter();
+    }
+}"#);
+    let user_uri = lsp::Url::from_file_path(project.dir.join("src/com/x/User.as").canonicalize().unwrap()).unwrap();
+    let mut client = Client::start(&project);
+
+    // `greeter` in `public var greeter`
+    let position = serde_json::json!({ "textDocument": { "uri": user_uri }, "position": { "line": 2, "character": 22 } });
+    let hover = serde_json::from_value::<lsp::Hover>(client.request("textDocument/hover", position)).unwrap();
+    let lsp::HoverContents::Markup(contents) = hover.contents else {
+        panic!("Hover contents are not markup.");
+    };
+    assert_eq!(contents.value, "```actionscript\nvar greeter: com.x.Greeter\n```");
+
+    // `Greeter` in the type annotation
+    let position = serde_json::json!({ "textDocument": { "uri": user_uri }, "position": { "line": 2, "character": 32 } });
+    let definition = serde_json::from_value::<lsp::GotoDefinitionResponse>(client.request("textDocument/definition", position)).unwrap();
+    let lsp::GotoDefinitionResponse::Scalar(location) = definition else {
+        panic!("Definition is not a single location.");
+    };
+    assert_eq!(location.uri, main_uri(&project));
+}
+
+#[test]
+fn package_processing_failures_are_shown() {
+    let project = Project::with_main("lsp-package-failure", GREETER_SOURCE, "\n[dependencies]\n\"../lib\" = \"^1\"\n");
+    let mut client = Client::start(&project);
+    let params = client.notification::<lsp::notification::ShowMessage>();
+    assert_eq!(params.typ, lsp::MessageType::ERROR);
+    assert_eq!(params.message, "Found illegal package name: ../lib");
+
+    // The server keeps running.
+    assert_eq!(client.request("shutdown", serde_json::Value::Null), serde_json::Value::Null);
+}
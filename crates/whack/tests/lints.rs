@@ -0,0 +1,64 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const UNREACHABLE_SOURCE: &str = r#"package com.x {
+    public class Main {
+        public function f(): void {
+            return;
+            trace("never");
+        }
+    }
+}"#;
+
+#[test]
+fn deny_warnings() {
+    let project = Project::with_main("lints-deny", UNREACHABLE_SOURCE, "");
+    let output = project.whack("check").args(["-D", "warnings"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("src/Main.as:5:13: Verify error #2163"), "{stdout}");
+
+    // Command line lints do not apply to dependencies such as the built-ins.
+    assert!(stdout.contains("library.as:9:30: Warning #2158"), "{stdout}");
+}
+
+#[test]
+fn allow_warning_by_name() {
+    let project = Project::with_main("lints-allow", UNREACHABLE_SOURCE, "");
+    let output = project.whack("check").args(["-D", "warnings", "-A", "UnreachableCode"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(!stdout(&output).contains("#2163"));
+}
+
+#[test]
+fn lints_table() {
+    let project = Project::with_main("lints-table", UNREACHABLE_SOURCE, "\n[lints]\n2163 = \"deny\"\n");
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+    assert!(stdout(&output).contains("Verify error #2163"));
+
+    // The command line takes precedence over the manifest.
+    let output = project.whack("check").args(["-W", "UnreachableCode"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Warning #2163"));
+}
+
+#[test]
+fn unknown_lints() {
+    let project = Project::with_main("lints-unknown", UNREACHABLE_SOURCE, "");
+    let output = project.whack("check").args(["-A", "Unknown"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Unknown lint: Unknown"));
+
+    // Only warnings may be configured.
+    let output = project.whack("check").args(["-A", "ImplicitCoercionToUnrelatedType"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Unknown lint: ImplicitCoercionToUnrelatedType"));
+
+    let project = Project::with_main("lints-unknown-table", UNREACHABLE_SOURCE, "\n[lints]\nUnknown = \"allow\"\n");
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("contains an unknown lint: Unknown"));
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const INVALID_SOURCE: &str = r#"package com.x {
+    public class Main {
+        public function f(): Number {
+            return new Error();
+        }
+    }
+}"#;
+
+const PT_BR_MESSAGE: &str = "Coerção implícita de um valor do tipo Error para um tipo não relacionado Number.";
+
+#[test]
+fn shipped_locale() {
+    let project = Project::with_main("locale-shipped", INVALID_SOURCE, "");
+    let output = project.whack("check").args(["--locale", "pt-BR"]).output().unwrap();
+    assert!(stdout(&output).contains(PT_BR_MESSAGE), "{}", stdout(&output));
+
+    // JSON messages are localized as well.
+    let output = project.whack("check").args(["--locale", "pt-BR", "--message-format=json"]).output().unwrap();
+    assert!(stdout(&output).contains(PT_BR_MESSAGE));
+}
+
+#[test]
+fn environment_locale() {
+    let project = Project::with_main("locale-environment", INVALID_SOURCE, "");
+    let output = project.whack("check").env("LANG", "pt_BR.UTF-8").output().unwrap();
+    assert!(stdout(&output).contains(PT_BR_MESSAGE));
+
+    // Unknown environment locales fall back to English.
+    let output = project.whack("check").env("LANG", "xx_XX.UTF-8").output().unwrap();
+    assert!(stdout(&output).contains("Implicit coercion of a value of type Error to an unrelated type Number."));
+}
+
+#[test]
+fn locale_file() {
+    let project = Project::with_main("locale-file", INVALID_SOURCE, "");
+    project.write("messages.toml", "2049 = \"De {1} para {2}.\"\n");
+    let output = project.whack("check").args(["--locale", "messages.toml"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::SOURCE_VERIFICATION_FAILURE_EXIT_CODE));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("De Error para Number."), "{stdout}");
+
+    // Messages missing from the file are in English.
+    assert!(stdout.contains("args is unused."), "{stdout}");
+}
+
+#[test]
+fn unknown_locale() {
+    let project = Project::with_main("locale-unknown", INVALID_SOURCE, "");
+    let output = project.whack("check").args(["--locale", "xx"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Unknown locale: xx. Available locales: en, pt-BR."), "{}", stderr(&output));
+
+    let output = project.whack("check").args(["--locale", "missing.toml"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Could not read locale file missing.toml"));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+
+const LIB_SOURCE: &str = r#"
+    package com.x.lib {
+        public class Util {
+        }
+    }
+"#;
+
+fn project(name: &str) -> (Project, Registry) {
+    let project = Project::with_main(name, "", "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n");
+    let registry = Registry::new(&project);
+    registry.publish("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+    (project, registry)
+}
+
+#[test]
+fn lockfile_lists_resolved_packages() {
+    let (project, registry) = project("lockfile-resolved-packages");
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let lockfile = project.read("whack.lock");
+    assert!(lockfile.contains("name = \"com.x.app\"\nversion = \"1.0.0\"\ndependencies = [\"com.x.lib\"]"), "{lockfile}");
+    assert!(lockfile.contains(&format!("name = \"com.x.lib\"\nversion = \"1.0.0\"\nsource = \"registry+{}\"", registry.url())), "{lockfile}");
+}
+
+#[test]
+fn locked_resolution_is_reused() {
+    let (project, registry) = project("lockfile-reused");
+    assert!(project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap().status.success());
+
+    registry.publish("com.x.lib", "1.1.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+    std::fs::remove_dir_all(project.dir.join("target")).unwrap();
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/downloads/com.x.lib/whack.toml").contains("version = \"1.0.0\""));
+}
+
+#[test]
+fn locked_requires_an_up_to_date_lockfile() {
+    let (project, registry) = project("lockfile-locked");
+    let output = project.whack("check").arg("--locked").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("The lock file needs to be updated, but --locked was passed."), "{}", stderr(&output));
+    assert!(!project.exists("whack.lock"));
+
+    assert!(project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap().status.success());
+    let output = project.whack("check").arg("--locked").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn frozen_disallows_network_access() {
+    let (project, registry) = project("lockfile-frozen");
+    assert!(project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap().status.success());
+
+    // Downloaded packages may be used offline.
+    let output = project.whack("check").arg("--frozen").env("WHACK_REGISTRY", "http://localhost:1").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    std::fs::remove_dir_all(project.dir.join("target/downloads")).unwrap();
+    let output = project.whack("check").arg("--frozen").env("WHACK_REGISTRY", "http://localhost:1").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Network access is required to fetch http://localhost:1, but --frozen was passed."), "{}", stderr(&output));
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::*;
+use whackengine_whack::commandprocesses::CommandProcessCommons;
+use whackengine_whack::packagemanager::DependencyUpdate;
+
+const LIB_SOURCE: &str = r#"
+    package com.x.lib {
+        public class Util {
+            public static function twice(n: Number): Number {
+                return n * 2;
+            }
+        }
+    }
+"#;
+
+const MAIN_SOURCE: &str = r#"
+    package com.x {
+        import com.x.lib.Util;
+
+        public class Main {
+            public function run(): Number {
+                return Util.twice(2);
+            }
+        }
+    }
+"#;
+
+fn publish_lib(registry: &Registry) {
+    registry.publish("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+    registry.publish("com.x.lib", "1.2.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+    registry.publish("com.x.lib", "1.3.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], true);
+    registry.publish("com.x.lib", "2.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false);
+}
+
+#[test]
+fn highest_matching_version_is_downloaded_and_locked() {
+    let project = Project::with_main("registry-highest-version", MAIN_SOURCE, "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n");
+    let registry = Registry::new(&project);
+    publish_lib(&registry);
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/downloads/com.x.lib/whack.toml").contains("version = \"1.2.0\""));
+
+    let lockfile = project.read("whack.lock");
+    assert!(lockfile.contains("name = \"com.x.lib\"\nversion = \"1.2.0\""), "{lockfile}");
+    assert!(lockfile.contains(&format!("source = \"registry+{}\"", registry.url())), "{lockfile}");
+}
+
+#[test]
+fn locked_version_is_preferred() {
+    let project = Project::with_main("registry-locked-version", MAIN_SOURCE, "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n");
+    let registry = Registry::new(&project);
+    publish_lib(&registry);
+    project.write("whack.lock", &format!("[[package]]\nname = \"com.x.lib\"\nversion = \"1.0.0\"\nsource = \"registry+{}\"\n", registry.url()));
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.read("target/downloads/com.x.lib/whack.toml").contains("version = \"1.0.0\""));
+}
+
+#[test]
+fn checksum_mismatch_is_reported() {
+    let project = Project::with_main("registry-checksum-mismatch", MAIN_SOURCE, "\n[dependencies]\n\"com.x.lib\" = \"1.0.0\"\n");
+    let registry = Registry::new(&project);
+    registry.publish_with_checksum("com.x.lib", "1.0.0", "", &[("com/x/lib/Util.as", LIB_SOURCE)], false, Some("00"));
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Checksum mismatch for com.x.lib v1.0.0"), "{}", stderr(&output));
+    assert!(!project.exists("target/downloads/com.x.lib"));
+}
+
+#[test]
+fn registry_must_be_configured() {
+    let project = Project::with_main("registry-not-configured", MAIN_SOURCE, "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n");
+
+    let output = project.whack("check").output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("WHACK_REGISTRY"), "{}", stderr(&output));
+}
+
+#[test]
+fn illegal_dependency_name_is_rejected() {
+    let project = Project::with_main("registry-illegal-name", MAIN_SOURCE, "\n[dependencies]\n\"../lib\" = \"^1\"\n");
+    let registry = Registry::new(&project);
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert_eq!(output.status.code(), Some(CommandProcessCommons::PACKAGE_PROCESSING_FAILURE_EXIT_CODE));
+    assert!(stderr(&output).contains("Found illegal package name: ../lib"), "{}", stderr(&output));
+    assert!(!project.exists("target/lib"));
+}
+
+#[test]
+fn legal_package_names() {
+    assert!(DependencyUpdate::is_legal_package_name("com.x.lib"));
+    assert!(DependencyUpdate::is_legal_package_name("my-lib_2"));
+    assert!(!DependencyUpdate::is_legal_package_name("."));
+    assert!(!DependencyUpdate::is_legal_package_name(".."));
+    assert!(!DependencyUpdate::is_legal_package_name(".hidden"));
+    assert!(!DependencyUpdate::is_legal_package_name("a/b"));
+    assert!(!DependencyUpdate::is_legal_package_name("a\\b"));
+    assert!(!DependencyUpdate::is_legal_package_name(""));
+}
+
+#[test]
+fn diamond_dependencies_are_resolved_once() {
+    // Both dependencies of the application depend on com.x.lib,
+    // which therefore occurs in more than one edge of the graph.
+    let manifest = "\n[dependencies]\n\"com.x.left\" = \"1\"\n\"com.x.right\" = \"1\"\n\"com.x.lib\" = \"1\"\n";
+    let project = Project::with_main("registry-diamond", MAIN_SOURCE, manifest);
+    let registry = Registry::new(&project);
+    publish_lib(&registry);
+    for side in ["left", "right"] {
+        let source = format!("package com.x.{side} {{ import com.x.lib.Util; public class Side extends Util {{}} }}");
+        registry.publish(&format!("com.x.{side}"), "1.0.0", "\n[dependencies]\n\"com.x.lib\" = \"^1\"\n", &[(&format!("com/x/{side}/Side.as"), &source)], false);
+    }
+
+    let output = project.whack("check").env("WHACK_REGISTRY", registry.url()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(project.read("whack.lock").matches("name = \"com.x.lib\"").count(), 1);
+}
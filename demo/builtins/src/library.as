@@ -41,6 +41,10 @@ package
     {
     }
 
+    public final class Generator.<T>
+    {
+    }
+
     public final class int
     {
     }
@@ -53,6 +57,10 @@ package
     {
     }
 
+    public interface Iterator.<T>
+    {
+    }
+
     public final class Map.<K, V>
     {
     }